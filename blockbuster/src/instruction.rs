@@ -43,9 +43,15 @@ pub fn order_instructions<'a>(
                 (ix.index == outer_instruction_index as u8).then_some(&ix.instructions)
             })
             .flatten()
-            .map(|inner_ix| {
+            .filter_map(|inner_ix| {
                 let cix = &inner_ix.instruction;
-                (account_keys[cix.program_id_index as usize], cix)
+                match account_keys.get(cix.program_id_index as usize) {
+                    Some(program_id) => Some((*program_id, cix)),
+                    None => {
+                        eprintln!("inner program id deserialization error");
+                        None
+                    }
+                }
             })
             .collect::<Vec<IxPair>>();
 
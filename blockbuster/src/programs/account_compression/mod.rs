@@ -20,7 +20,10 @@ use spl_noop;
 use anchor_lang::Discriminator;
 use spl_account_compression::instruction::*;
 
-fn get_instruction_type(full_bytes: &[u8]) -> Instruction {
+fn get_instruction_type(full_bytes: &[u8]) -> Result<Instruction, BlockbusterError> {
+    if full_bytes.len() < 8 {
+        return Ok(Instruction::Unknown);
+    }
     let (disc_slice, args_bytes) = full_bytes.split_at(8);
     let disc: [u8; 8] = {
         let mut disc = [0; 8];
@@ -28,16 +31,16 @@ fn get_instruction_type(full_bytes: &[u8]) -> Instruction {
         disc
     };
 
-    match disc {
+    Ok(match disc {
         InitEmptyMerkleTree::DISCRIMINATOR => {
-            let init_empty_merkle_tree = InitEmptyMerkleTree::try_from_slice(&args_bytes).unwrap();
+            let init_empty_merkle_tree = InitEmptyMerkleTree::try_from_slice(args_bytes)?;
             Instruction::InitTree {
                 max_depth: init_empty_merkle_tree.max_depth,
                 max_buffer_size: init_empty_merkle_tree.max_buffer_size,
             }
         }
         ReplaceLeaf::DISCRIMINATOR => {
-            let replace_leaf = ReplaceLeaf::try_from_slice(&args_bytes).unwrap();
+            let replace_leaf = ReplaceLeaf::try_from_slice(args_bytes)?;
             Instruction::ReplaceLeaf {
                 root: replace_leaf.root,
                 previous_leaf: replace_leaf.previous_leaf,
@@ -46,13 +49,13 @@ fn get_instruction_type(full_bytes: &[u8]) -> Instruction {
             }
         }
         TransferAuthority::DISCRIMINATOR => {
-            let transfer_authority = TransferAuthority::try_from_slice(&args_bytes).unwrap();
+            let transfer_authority = TransferAuthority::try_from_slice(args_bytes)?;
             Instruction::TransferAuthority {
                 new_authority: transfer_authority.new_authority,
             }
         }
         VerifyLeaf::DISCRIMINATOR => {
-            let verify_leaf = VerifyLeaf::try_from_slice(&args_bytes).unwrap();
+            let verify_leaf = VerifyLeaf::try_from_slice(args_bytes)?;
             Instruction::VerifyLeaf {
                 root: verify_leaf.root,
                 leaf: verify_leaf.leaf,
@@ -60,11 +63,11 @@ fn get_instruction_type(full_bytes: &[u8]) -> Instruction {
             }
         }
         Append::DISCRIMINATOR => {
-            let append = Append::try_from_slice(&args_bytes).unwrap();
+            let append = Append::try_from_slice(args_bytes)?;
             Instruction::Append { leaf: append.leaf }
         }
         InsertOrAppend::DISCRIMINATOR => {
-            let insert_or_append = InsertOrAppend::try_from_slice(&args_bytes).unwrap();
+            let insert_or_append = InsertOrAppend::try_from_slice(args_bytes)?;
             Instruction::InsertOrAppend {
                 root: insert_or_append.root,
                 leaf: insert_or_append.leaf,
@@ -73,7 +76,7 @@ fn get_instruction_type(full_bytes: &[u8]) -> Instruction {
         }
         CloseEmptyTree::DISCRIMINATOR => Instruction::CloseTree,
         _ => Instruction::Unknown,
-    }
+    })
 }
 
 #[derive(Eq, PartialEq, Debug)]
@@ -175,7 +178,7 @@ impl ProgramParser for AccountCompressionParser {
             Some(cix) => cix.data.as_ref(),
             _ => return Err(BlockbusterError::DeserializationError),
         };
-        let ix_type = get_instruction_type(&outer_ix_data);
+        let ix_type = get_instruction_type(outer_ix_data)?;
         let mut b_inst = AccountCompressionInstruction::new(ix_type);
         if let Some(ixs) = inner_ix {
             for (pid, cix) in ixs.iter() {
@@ -124,7 +124,12 @@ impl ProgramParser for BubblegumParser {
             Some(cix) => cix.data.as_ref(),
             _ => return Err(BlockbusterError::DeserializationError),
         };
-        let ix_type = get_instruction_type(outer_ix_data);
+        // `get_instruction_type` slices the discriminator without checking the length.
+        let ix_type = if outer_ix_data.len() >= 8 {
+            get_instruction_type(outer_ix_data)
+        } else {
+            InstructionName::Unknown
+        };
         let mut b_inst = BubblegumInstruction::new(ix_type);
         if let Some(ixs) = inner_ix {
             for (pid, cix) in ixs.iter() {
@@ -61,6 +61,9 @@ impl ProgramParser for HplResourceManagerParser {
         if account_data.is_empty() {
             return Ok(Box::new(HplResourceManagerAccount::Uninitialized));
         }
+        if account_data.len() < 8 {
+            return Err(BlockbusterError::InvalidDataLength);
+        }
         let mut discriminator = [0; 8];
         discriminator.copy_from_slice(&account_data[..8]);
         Ok(Box::new(match discriminator {
@@ -0,0 +1,410 @@
+use anchor_lang::AnchorSerialize;
+use blockbuster::{
+    instruction::{order_instructions, InstructionBundle, IxPair},
+    program_handler::{ParseResult, ProgramParser},
+    programs::{
+        account_compression::AccountCompressionParser,
+        bubblegum::BubblegumParser,
+        hpl_character_manager::{self, HplCharacterManagerAccount, HplCharacterManagerParser},
+        hpl_currency_manager::{self, HplCurrencyManagerAccount, HplCurrencyManagerParser},
+        hpl_hive_control::{self, HplHiveControlAccount, HplHiveControlParser},
+        hpl_nectar_missions::{self, HplNectarMissionsAccount, HplNectarMissionsParser},
+        hpl_nectar_staking::{self, HplNectarStakingAccount, HplNectarStakingParser},
+        hpl_resource_manager::{self, HplResourceManagerAccount, HplResourceManagerParser},
+        mpl_core_program::MplCoreParser,
+        noop::NoopParser,
+        token_account::TokenAccountParser,
+        token_extensions::Token2022AccountParser,
+        token_metadata::TokenMetadataParser,
+        ProgramParseResult,
+    },
+};
+use proptest::prelude::*;
+use solana_sdk::{hash::hash, instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::{InnerInstruction, InnerInstructions};
+use std::collections::HashSet;
+
+fn parsers() -> Vec<Box<dyn ProgramParser>> {
+    vec![
+        Box::new(BubblegumParser),
+        Box::new(AccountCompressionParser),
+        Box::new(NoopParser),
+        Box::new(TokenMetadataParser),
+        Box::new(TokenAccountParser),
+        Box::new(Token2022AccountParser),
+        Box::new(MplCoreParser),
+        Box::new(HplCharacterManagerParser),
+        Box::new(HplCurrencyManagerParser),
+        Box::new(HplHiveControlParser),
+        Box::new(HplNectarStakingParser),
+        Box::new(HplNectarMissionsParser),
+        Box::new(HplResourceManagerParser),
+    ]
+}
+
+/// Every HPL account type with the parser that owns it.
+fn hpl_accounts() -> Vec<(Box<dyn ProgramParser>, [u8; 8])> {
+    vec![
+        (
+            Box::new(HplCharacterManagerParser),
+            hpl_character_manager::AssemblerConfig::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplCharacterManagerParser),
+            hpl_character_manager::CharacterModel::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplCharacterManagerParser),
+            hpl_character_manager::AssetCustody::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplCurrencyManagerParser),
+            hpl_currency_manager::Currency::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplCurrencyManagerParser),
+            hpl_currency_manager::HolderAccount::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplHiveControlParser),
+            hpl_hive_control::Global::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplHiveControlParser),
+            hpl_hive_control::Project::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplHiveControlParser),
+            hpl_hive_control::DelegateAuthority::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplNectarStakingParser),
+            hpl_nectar_staking::StakingPool::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplNectarStakingParser),
+            hpl_nectar_staking::Multipliers::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplNectarStakingParser),
+            hpl_nectar_staking::Staker::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplNectarMissionsParser),
+            hpl_nectar_missions::MissionPool::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplNectarMissionsParser),
+            hpl_nectar_missions::Mission::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplResourceManagerParser),
+            hpl_resource_manager::Resource::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplResourceManagerParser),
+            hpl_resource_manager::Faucet::DISCRIMINATOR,
+        ),
+        (
+            Box::new(HplResourceManagerParser),
+            hpl_resource_manager::Recipe::DISCRIMINATOR,
+        ),
+    ]
+}
+
+fn with_discriminator(discriminator: [u8; 8], account: &impl AnchorSerialize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend(account.try_to_vec().expect("serializable account"));
+    data
+}
+
+/// Serializes a parsed HPL account back into account data, `None` for anything that is not a
+/// decoded HPL account.
+fn reserialize(parsed: &dyn ParseResult) -> Option<Vec<u8>> {
+    use hpl_character_manager::{AssemblerConfig, AssetCustody, CharacterModel};
+    use hpl_currency_manager::{Currency, HolderAccount};
+    use hpl_hive_control::{DelegateAuthority, Global, Project};
+    use hpl_nectar_missions::{Mission, MissionPool};
+    use hpl_nectar_staking::{Multipliers, Staker, StakingPool};
+    use hpl_resource_manager::{Faucet, Recipe, Resource};
+
+    Some(match parsed.result_type() {
+        ProgramParseResult::HplCharacterManager(account) => match account {
+            HplCharacterManagerAccount::AssemblerConfig(a) => {
+                with_discriminator(AssemblerConfig::DISCRIMINATOR, a)
+            }
+            HplCharacterManagerAccount::CharacterModel(a) => {
+                with_discriminator(CharacterModel::DISCRIMINATOR, a)
+            }
+            HplCharacterManagerAccount::AssetCustody(a) => {
+                with_discriminator(AssetCustody::DISCRIMINATOR, a)
+            }
+            _ => return None,
+        },
+        ProgramParseResult::HplCurrencyManager(account) => match account {
+            HplCurrencyManagerAccount::Currency(a) => {
+                with_discriminator(Currency::DISCRIMINATOR, a)
+            }
+            HplCurrencyManagerAccount::HolderAccount(a) => {
+                with_discriminator(HolderAccount::DISCRIMINATOR, a)
+            }
+            _ => return None,
+        },
+        ProgramParseResult::HplHiveControl(account) => match account {
+            HplHiveControlAccount::Global(a) => with_discriminator(Global::DISCRIMINATOR, a),
+            HplHiveControlAccount::Project(a) => with_discriminator(Project::DISCRIMINATOR, a),
+            HplHiveControlAccount::DelegateAuthority(a) => {
+                with_discriminator(DelegateAuthority::DISCRIMINATOR, a)
+            }
+            _ => return None,
+        },
+        ProgramParseResult::HplNectarStaking(account) => match account {
+            HplNectarStakingAccount::StakingPool(a) => {
+                with_discriminator(StakingPool::DISCRIMINATOR, a)
+            }
+            HplNectarStakingAccount::Multipliers(a) => {
+                with_discriminator(Multipliers::DISCRIMINATOR, a)
+            }
+            HplNectarStakingAccount::Staker(a) => with_discriminator(Staker::DISCRIMINATOR, a),
+            _ => return None,
+        },
+        ProgramParseResult::HplNectarMissions(account) => match account {
+            HplNectarMissionsAccount::MissionPool(a) => {
+                with_discriminator(MissionPool::DISCRIMINATOR, a)
+            }
+            HplNectarMissionsAccount::Mission(a) => with_discriminator(Mission::DISCRIMINATOR, a),
+            _ => return None,
+        },
+        ProgramParseResult::HplResourceManager(account) => match account {
+            HplResourceManagerAccount::Resource(a) => {
+                with_discriminator(Resource::DISCRIMINATOR, a)
+            }
+            HplResourceManagerAccount::Recipe(a) => with_discriminator(Recipe::DISCRIMINATOR, a),
+            HplResourceManagerAccount::Faucet(a) => with_discriminator(Faucet::DISCRIMINATOR, a),
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Anchor instruction discriminators of the Bubblegum and account compression instructions the
+/// parsers decode arguments for.
+fn instruction_discriminators() -> Vec<Vec<u8>> {
+    [
+        "mint_v1",
+        "mint_to_collection_v1",
+        "decompress_v1",
+        "cancel_redeem",
+        "verify_creator",
+        "unverify_creator",
+        "verify_collection",
+        "unverify_collection",
+        "set_and_verify_collection",
+        "update_metadata",
+        "init_empty_merkle_tree",
+        "replace_leaf",
+        "transfer_authority",
+        "verify_leaf",
+        "append",
+        "insert_or_append",
+        "close_empty_tree",
+    ]
+    .iter()
+    .map(|name| hash(format!("global:{name}").as_bytes()).to_bytes()[..8].to_vec())
+    .collect()
+}
+
+/// Bytes skewed towards zero and small values so that borsh length prefixes, option tags and
+/// enum variants decode often enough to reach past the first field.
+fn borsh_like_bytes(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(
+        prop_oneof![4 => Just(0u8), 2 => 0u8..4, 1 => any::<u8>()],
+        0..max_len,
+    )
+}
+
+fn prefixed(prefixes: Vec<Vec<u8>>, max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    (prop::sample::select(prefixes), borsh_like_bytes(max_len))
+        .prop_map(|(prefix, rest)| [prefix, rest].concat())
+}
+
+fn account_data() -> impl Strategy<Value = Vec<u8>> {
+    let mut prefixes = hpl_accounts()
+        .into_iter()
+        .map(|(_, discriminator)| discriminator.to_vec())
+        .collect::<Vec<_>>();
+    // Token Metadata and MPL Core lead with a one byte `Key`.
+    prefixes.extend((0..=20).map(|key| vec![key]));
+
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..1024),
+        prefixed(prefixes, 1024),
+        // SPL token mint and account sizes, with and without extensions.
+        prop::sample::select(vec![82usize, 165, 166, 170, 234, 300])
+            .prop_flat_map(|len| prop::collection::vec(any::<u8>(), len)),
+    ]
+}
+
+fn instruction_data() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..256),
+        prefixed(instruction_discriminators(), 512),
+    ]
+}
+
+fn noop_data() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..256),
+        // `AccountCompressionEvent::ChangeLog` and `ApplicationData`, both `V1`.
+        prefixed(vec![vec![0, 0], vec![1, 0]], 512),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn account_parsers_never_panic(data in account_data()) {
+        for parser in parsers() {
+            let _ = parser.handle_account(&data);
+        }
+    }
+
+    #[test]
+    fn instruction_parsers_never_panic(
+        outer in instruction_data(),
+        inner in prop::collection::vec((any::<bool>(), noop_data()), 0..6),
+        key_count in 0usize..12,
+    ) {
+        let keys = (0..key_count).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let outer = CompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![],
+            data: outer,
+        };
+        let inner_ixs = inner
+            .iter()
+            .map(|(is_noop, data)| {
+                let program = if *is_noop {
+                    spl_noop::id()
+                } else {
+                    Pubkey::new_unique()
+                };
+                let ix = CompiledInstruction {
+                    program_id_index: 0,
+                    accounts: vec![],
+                    data: data.clone(),
+                };
+                (program, ix)
+            })
+            .collect::<Vec<_>>();
+        let inner_ix = inner_ixs
+            .iter()
+            .map(|(program, ix)| (*program, ix))
+            .collect::<Vec<IxPair>>();
+
+        for parser in parsers().iter().filter(|parser| parser.handles_instructions()) {
+            let bundle = InstructionBundle {
+                txn_id: "",
+                program: parser.key(),
+                instruction: Some(&outer),
+                inner_ix: Some(inner_ix.as_slice()),
+                keys: &keys,
+                slot: 0,
+            };
+            let _ = parser.handle_instruction(&bundle);
+        }
+    }
+
+    #[test]
+    fn order_instructions_never_panics(
+        key_count in 0usize..8,
+        known in prop::collection::vec(0usize..8, 0..4),
+        outer in prop::collection::vec(0u8..12, 0..6),
+        inner in prop::collection::vec(
+            (0u8..8, prop::collection::vec(0u8..12, 0..6)),
+            0..6,
+        ),
+    ) {
+        let account_keys = (0..key_count).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let programs = known
+            .iter()
+            .filter_map(|index| account_keys.get(*index).copied())
+            .collect::<HashSet<_>>();
+        let compiled = |program_id_index| CompiledInstruction {
+            program_id_index,
+            accounts: vec![],
+            data: vec![],
+        };
+        let message_instructions = outer.into_iter().map(compiled).collect::<Vec<_>>();
+        let meta_inner_instructions = inner
+            .into_iter()
+            .map(|(index, program_id_indexes)| InnerInstructions {
+                index,
+                instructions: program_id_indexes
+                    .into_iter()
+                    .map(|program_id_index| InnerInstruction {
+                        instruction: compiled(program_id_index),
+                        stack_height: None,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        let ordered = order_instructions(
+            &programs,
+            &account_keys,
+            &message_instructions,
+            &meta_inner_instructions,
+        );
+
+        for ((program, ix), inner) in ordered {
+            prop_assert!(programs.contains(&program));
+            prop_assert_eq!(account_keys.get(ix.program_id_index as usize), Some(&program));
+            for (program, ix) in inner.unwrap_or_default() {
+                prop_assert_eq!(account_keys.get(ix.program_id_index as usize), Some(&program));
+            }
+        }
+    }
+
+    #[test]
+    fn hpl_accounts_round_trip(
+        (index, payload) in (0..hpl_accounts().len(), borsh_like_bytes(2048)),
+    ) {
+        let (parser, discriminator) = &hpl_accounts()[index];
+        let data = [discriminator.as_slice(), &payload].concat();
+
+        if let Ok(parsed) = parser.handle_account(&data) {
+            let serialized = reserialize(parsed.as_ref());
+            prop_assert!(serialized.is_some(), "known discriminator parsed as an unknown account");
+            let serialized = serialized.unwrap();
+            prop_assert_eq!(&serialized[..8], discriminator.as_slice());
+
+            let reparsed = parser.handle_account(&serialized);
+            prop_assert!(reparsed.is_ok(), "serialized account failed to parse");
+            prop_assert_eq!(reserialize(reparsed.unwrap().as_ref()), Some(serialized));
+        }
+    }
+}
+
+#[test]
+fn hpl_accounts_shorter_than_discriminator() {
+    for (parser, discriminator) in hpl_accounts() {
+        for len in 1..discriminator.len() {
+            assert!(parser.handle_account(&discriminator[..len]).is_err());
+        }
+    }
+}
+
+#[test]
+fn zeroed_hpl_accounts_round_trip() {
+    for (parser, discriminator) in hpl_accounts() {
+        let data = [discriminator.as_slice(), &[0; 1024]].concat();
+        let parsed = parser
+            .handle_account(&data)
+            .expect("zeroed account should parse");
+        let serialized = reserialize(parsed.as_ref()).expect("decoded HPL account");
+        assert_eq!(serialized, data[..serialized.len()]);
+
+        let reparsed = parser.handle_account(&serialized).expect("reparse");
+        assert_eq!(reserialize(reparsed.as_ref()), Some(serialized));
+    }
+}
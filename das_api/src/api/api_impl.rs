@@ -1,5 +1,6 @@
 use digital_asset_types::{
     dao::{
+        asset,
         scopes::asset::get_grouping,
         sea_orm_active_enums::{
             OwnerType, RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions,
@@ -8,28 +9,43 @@ use digital_asset_types::{
     },
     dapi::{
         get_asset, get_asset_proofs, get_asset_signatures, get_assets, get_assets_by_authority,
-        get_assets_by_creator, get_assets_by_group, get_assets_by_owner, get_characters,
-        get_compressed_accounts, get_compressed_data, get_proof, get_proof_for_asset,
-        search_assets,
+        get_assets_by_creator, get_assets_by_group, get_assets_by_owner, get_character_by_asset,
+        get_characters, get_compressed_accounts, get_compressed_data,
+        get_compressed_data_signatures, get_currency_holdings, get_delegate_authorities,
+        get_mission_participations, get_profiles, get_project, get_projects, get_proof,
+        get_proof_for_asset, get_resource_holdings, get_staking_positions, get_token_accounts,
+        get_token_balances, search_assets,
     },
     rpc::{
         filter::{AssetSortBy, SearchConditionType},
-        response::GetGroupingResponse,
+        response::{
+            CompressedDataSignatureList, GetGroupingResponse, ProfileList, TokenAccountList,
+            TokenBalanceList,
+        },
         OwnershipModel, RoyaltyModel,
     },
 };
 use open_rpc_derive::document_rpc;
-use sea_orm::{sea_query::ConditionType, ConnectionTrait, DbBackend, Statement};
+use sea_orm::{sea_query::ConditionType, ConnectionTrait, DbBackend, EntityTrait, Statement};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::error::DasApiError;
-use crate::validation::{validate_opt_pubkey, validate_search_with_name};
+use crate::validation::{
+    validate_changelog_cursor, validate_opt_pubkey, validate_search_text,
+    validate_search_with_name, validate_sort_by,
+};
 use open_rpc_schema::document::OpenrpcDocument;
 use {
     crate::api::*,
     crate::config::Config,
     crate::validation::validate_pubkey,
     async_trait::async_trait,
-    digital_asset_types::rpc::{response::AssetList, Asset, AssetProof, CompressedData},
+    digital_asset_types::rpc::{
+        response::AssetList, Asset, AssetProof, CompressedData, CurrencyHolding, DelegateAuthority,
+        MissionParticipation, Project, ResourceHoldings, StakingPosition,
+    },
     sea_orm::{DatabaseConnection, DbErr, SqlxPostgresConnector},
     sqlx::postgres::PgPoolOptions,
 };
@@ -51,6 +67,10 @@ impl DasApi {
         })
     }
 
+    pub const fn from_connection(db_connection: DatabaseConnection) -> Self {
+        DasApi { db_connection }
+    }
+
     fn get_cursor(&self, cursor: &Option<String>) -> Result<Cursor, DasApiError> {
         match cursor {
             Some(cursor_b64) => {
@@ -66,6 +86,25 @@ impl DasApi {
         }
     }
 
+    fn validate_limit(&self, limit: Option<u32>) -> Result<u64, DasApiError> {
+        match limit {
+            Some(limit) if limit > 1000 => Err(DasApiError::PaginationExceededError),
+            Some(limit) => Ok(limit as u64),
+            None => Ok(1000),
+        }
+    }
+
+    fn decode_cursor(&self, cursor: Option<String>) -> Result<Option<Vec<u8>>, DasApiError> {
+        cursor
+            .filter(|cursor| !cursor.is_empty())
+            .map(|cursor| {
+                bs58::decode(&cursor)
+                    .into_vec()
+                    .map_err(|_| DasApiError::CursorValidationError(cursor))
+            })
+            .transpose()
+    }
+
     fn validate_pagination(
         &self,
         limit: Option<u32>,
@@ -181,11 +220,12 @@ impl ApiContract for DasApi {
 
     async fn get_compressed_data(
         &self,
-        payload: LeafTreePayload,
+        payload: GetCompressedData,
     ) -> Result<CompressedData, DasApiError> {
         let tree = validate_pubkey(payload.tree.clone())?;
         let tree_bytes = tree.to_bytes().to_vec();
-        get_compressed_data(&self.db_connection, tree_bytes, payload.leaf_idx)
+        let options = payload.options.unwrap_or_default();
+        get_compressed_data(&self.db_connection, tree_bytes, payload.leaf_idx, &options)
             .await
             .map_err(Into::into)
     }
@@ -195,6 +235,7 @@ impl ApiContract for DasApi {
         payload: GetCompressedAccounts,
     ) -> Result<Vec<CompressedData>, DasApiError> {
         let program_id = validate_pubkey(payload.program_id.clone())?;
+        let project = validate_opt_pubkey(&payload.project)?;
         get_compressed_accounts(
             &self.db_connection,
             anchor_lang::solana_program::keccak::hashv(
@@ -202,6 +243,7 @@ impl ApiContract for DasApi {
             )
             .to_bytes()
             .to_vec(),
+            project,
         )
         .await
         .map_err(Into::into)
@@ -217,9 +259,17 @@ impl ApiContract for DasApi {
         } else {
             None
         };
-        get_characters(&self.db_connection, wallet.to_string(), merkle_tree)
-            .await
-            .map_err(Into::into)
+        let project = validate_opt_pubkey(&payload.project)?;
+        let options = payload.options.unwrap_or_default();
+        get_characters(
+            &self.db_connection,
+            wallet.to_string(),
+            merkle_tree,
+            project,
+            &options,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn get_proof(self: &DasApi, payload: LeafTreePayload) -> Result<AssetProof, DasApiError> {
@@ -316,6 +366,7 @@ impl ApiContract for DasApi {
         let owner_address = validate_pubkey(owner_address.clone())?;
         let owner_address_bytes = owner_address.to_bytes().to_vec();
         let sort_by = sort_by.unwrap_or_default();
+        validate_sort_by(&sort_by)?;
         let options = options.unwrap_or_default();
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
@@ -348,6 +399,7 @@ impl ApiContract for DasApi {
         let before: Option<String> = before.filter(|before| !before.is_empty());
         let after: Option<String> = after.filter(|after| !after.is_empty());
         let sort_by = sort_by.unwrap_or_default();
+        validate_sort_by(&sort_by)?;
         let options = options.unwrap_or_default();
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
@@ -382,6 +434,7 @@ impl ApiContract for DasApi {
         let creator_address_bytes = creator_address.to_bytes().to_vec();
 
         let sort_by = sort_by.unwrap_or_default();
+        validate_sort_by(&sort_by)?;
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
         let only_verified = only_verified.unwrap_or_default();
@@ -413,6 +466,7 @@ impl ApiContract for DasApi {
             cursor,
         } = payload;
         let sort_by = sort_by.unwrap_or_default();
+        validate_sort_by(&sort_by)?;
         let authority_address = validate_pubkey(authority_address.clone())?;
         let authority_address_bytes = authority_address.to_bytes().to_vec();
         let options = options.unwrap_or_default();
@@ -460,6 +514,9 @@ impl ApiContract for DasApi {
             options,
             cursor,
             name,
+            symbol,
+            attributes,
+            attribute_condition_type,
         } = payload;
 
         // Deserialize search assets query
@@ -471,8 +528,13 @@ impl ApiContract for DasApi {
             SearchConditionType::Any => ConditionType::Any,
             SearchConditionType::All => ConditionType::All,
         });
+        let attribute_condition_type = attribute_condition_type.map(|x| match x {
+            SearchConditionType::Any => ConditionType::Any,
+            SearchConditionType::All => ConditionType::All,
+        });
         let owner_address = validate_opt_pubkey(&owner_address)?;
         let name = validate_search_with_name(&name, &owner_address)?;
+        let symbol = validate_search_text("symbol", &symbol, &owner_address)?;
         let creator_address = validate_opt_pubkey(&creator_address)?;
         let delegate = validate_opt_pubkey(&delegate)?;
 
@@ -513,6 +575,9 @@ impl ApiContract for DasApi {
             burnt,
             json_uri,
             name,
+            symbol,
+            attributes,
+            attribute_condition_type,
         };
         let options = options.unwrap_or_default();
         let sort_by = sort_by.unwrap_or_default();
@@ -571,11 +636,235 @@ impl ApiContract for DasApi {
             group_key,
             group_value,
         } = payload;
-        let gs = get_grouping(&self.db_connection, group_key.clone(), group_value.clone()).await?;
+        let stats =
+            get_grouping(&self.db_connection, group_key.clone(), group_value.clone()).await?;
+
+        // MPL Core collections are assets themselves and track their own counters
+        let core_collection = match (group_key.as_str(), Pubkey::from_str(&group_value)) {
+            ("collection", Ok(collection)) => {
+                asset::Entity::find_by_id(collection.to_bytes().to_vec())
+                    .one(&self.db_connection)
+                    .await?
+            }
+            _ => None,
+        };
+        let to_u64 = |value: i64| u64::try_from(value).unwrap_or_default();
         Ok(GetGroupingResponse {
             group_key,
             group_name: group_value,
-            group_size: gs.size,
+            group_size: to_u64(stats.verified),
+            compressed: to_u64(stats.compressed),
+            uncompressed: to_u64(stats.uncompressed),
+            burnt: to_u64(stats.burnt),
+            unique_holders: to_u64(stats.unique_holders),
+            verified: to_u64(stats.verified),
+            unverified: to_u64(stats.unverified),
+            first_mint_slot: stats.first_mint_slot.map(to_u64),
+            last_mint_slot: stats.last_mint_slot.map(to_u64),
+            mpl_core_num_minted: core_collection
+                .as_ref()
+                .and_then(|asset| asset.mpl_core_collection_num_minted)
+                .map(|value| to_u64(value.into())),
+            mpl_core_current_size: core_collection
+                .as_ref()
+                .and_then(|asset| asset.mpl_core_collection_current_size)
+                .map(|value| to_u64(value.into())),
         })
     }
+
+    async fn get_token_accounts(
+        self: &DasApi,
+        payload: GetTokenAccounts,
+    ) -> Result<TokenAccountList, DasApiError> {
+        let GetTokenAccounts {
+            owner_address,
+            mint_address,
+            limit,
+            cursor,
+        } = payload;
+        if owner_address.is_none() && mint_address.is_none() {
+            return Err(DasApiError::ValidationError(
+                "Either ownerAddress or mintAddress must be provided".to_owned(),
+            ));
+        }
+        let owner = validate_opt_pubkey(&owner_address)?;
+        let mint = validate_opt_pubkey(&mint_address)?;
+        let limit = self.validate_limit(limit)?;
+        let cursor = self.decode_cursor(cursor)?;
+        get_token_accounts(&self.db_connection, owner, mint, limit, cursor)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_token_balances(
+        self: &DasApi,
+        payload: GetTokenBalances,
+    ) -> Result<TokenBalanceList, DasApiError> {
+        let GetTokenBalances {
+            owner_address,
+            limit,
+            cursor,
+            show_zero_balance,
+        } = payload;
+        let owner = validate_pubkey(owner_address)?;
+        let limit = self.validate_limit(limit)?;
+        let cursor = self.decode_cursor(cursor)?;
+        get_token_balances(
+            &self.db_connection,
+            owner.to_bytes().to_vec(),
+            limit,
+            cursor,
+            show_zero_balance.unwrap_or(false),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_currency_holdings(
+        self: &DasApi,
+        payload: GetCurrencyHoldings,
+    ) -> Result<Vec<CurrencyHolding>, DasApiError> {
+        let owner = validate_pubkey(payload.owner_address)?;
+        let project = payload
+            .project
+            .map(|project| validate_pubkey(project).map(|project| project.to_string()))
+            .transpose()?;
+        get_currency_holdings(&self.db_connection, owner.to_string(), project)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_staking_positions(
+        self: &DasApi,
+        payload: GetStakingPositions,
+    ) -> Result<Vec<StakingPosition>, DasApiError> {
+        let wallet = validate_pubkey(payload.wallet)?;
+        let project = validate_opt_pubkey(&payload.project)?;
+        get_staking_positions(&self.db_connection, wallet.to_string(), project)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_mission_participations(
+        self: &DasApi,
+        payload: GetMissionParticipations,
+    ) -> Result<Vec<MissionParticipation>, DasApiError> {
+        let wallet = validate_pubkey(payload.wallet)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs() as i64)
+            .unwrap_or_default();
+        let project = validate_opt_pubkey(&payload.project)?;
+        get_mission_participations(&self.db_connection, wallet.to_string(), project, now)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_character_by_asset(
+        self: &DasApi,
+        payload: GetCharacterByAsset,
+    ) -> Result<Option<CompressedData>, DasApiError> {
+        let GetCharacterByAsset { asset_id, options } = payload;
+        let asset_id = validate_pubkey(asset_id)?.to_bytes().to_vec();
+        let options = options.unwrap_or_default();
+        get_character_by_asset(&self.db_connection, asset_id, &options)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_resource_holdings(
+        self: &DasApi,
+        payload: GetResourceHoldings,
+    ) -> Result<ResourceHoldings, DasApiError> {
+        let wallet = validate_pubkey(payload.wallet)?;
+        let project = payload
+            .project
+            .map(|project| validate_pubkey(project).map(|project| project.to_string()))
+            .transpose()?;
+        get_resource_holdings(&self.db_connection, wallet.to_string(), project)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_project(self: &DasApi, payload: GetProject) -> Result<Project, DasApiError> {
+        let id = validate_pubkey(payload.id)?.to_bytes().to_vec();
+        get_project(&self.db_connection, id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_projects(
+        self: &DasApi,
+        payload: GetProjects,
+    ) -> Result<Vec<Project>, DasApiError> {
+        let GetProjects { authority, driver } = payload;
+        if authority.is_none() && driver.is_none() {
+            return Err(DasApiError::ValidationError(
+                "Either authority or driver must be provided".to_owned(),
+            ));
+        }
+        let authority = authority
+            .map(|authority| validate_pubkey(authority).map(|authority| authority.to_string()))
+            .transpose()?;
+        let driver = driver
+            .map(|driver| validate_pubkey(driver).map(|driver| driver.to_string()))
+            .transpose()?;
+        get_projects(&self.db_connection, authority, driver)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_profiles(self: &DasApi, payload: GetProfiles) -> Result<ProfileList, DasApiError> {
+        let GetProfiles {
+            project,
+            wallet,
+            limit,
+            cursor,
+        } = payload;
+        let project = validate_pubkey(project)?.to_bytes().to_vec();
+        let wallet = wallet
+            .map(|wallet| validate_pubkey(wallet).map(|wallet| wallet.to_string()))
+            .transpose()?;
+        let limit = self.validate_limit(limit)?;
+        let cursor = self.decode_cursor(cursor)?;
+        get_profiles(&self.db_connection, project, wallet, limit, cursor)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_delegate_authorities(
+        self: &DasApi,
+        payload: GetDelegateAuthorities,
+    ) -> Result<Vec<DelegateAuthority>, DasApiError> {
+        let project = validate_pubkey(payload.project)?;
+        get_delegate_authorities(&self.db_connection, project.to_string())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_compressed_data_signatures(
+        self: &DasApi,
+        payload: GetCompressedDataSignatures,
+    ) -> Result<CompressedDataSignatureList, DasApiError> {
+        let GetCompressedDataSignatures {
+            tree,
+            leaf_idx,
+            limit,
+            cursor,
+            sort_direction,
+        } = payload;
+        let tree = validate_pubkey(tree)?.to_bytes().to_vec();
+        let limit = self.validate_limit(limit)?;
+        let cursor = validate_changelog_cursor(cursor)?;
+        get_compressed_data_signatures(
+            &self.db_connection,
+            tree,
+            leaf_idx,
+            limit,
+            cursor,
+            sort_direction,
+        )
+        .await
+        .map_err(Into::into)
+    }
 }
@@ -1,191 +1,20 @@
 use crate::error::DasApiError;
 use async_trait::async_trait;
-use digital_asset_types::rpc::filter::{AssetSortDirection, SearchConditionType};
-use digital_asset_types::rpc::options::Options;
-use digital_asset_types::rpc::response::{AssetList, TransactionSignatureList};
-use digital_asset_types::rpc::{filter::AssetSorting, response::GetGroupingResponse};
+pub use digital_asset_types::rpc::request::*;
+use digital_asset_types::rpc::response::{
+    AssetList, CompressedDataSignatureList, GetGroupingResponse, ProfileList, TokenAccountList,
+    TokenBalanceList, TransactionSignatureList,
+};
 use digital_asset_types::rpc::{
-    Asset, AssetProof, CompressedData, Interface, OwnershipModel, RoyaltyModel,
+    Asset, AssetProof, CompressedData, CurrencyHolding, DelegateAuthority, MissionParticipation,
+    Project, ResourceHoldings, StakingPosition,
 };
 use open_rpc_derive::{document_rpc, rpc};
-use open_rpc_schema::schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 mod api_impl;
 pub use api_impl::*;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetsByGroup {
-    pub group_key: String,
-    pub group_value: String,
-    pub sort_by: Option<AssetSorting>,
-    pub limit: Option<u32>,
-    pub page: Option<u32>,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-    #[serde(default)]
-    pub cursor: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetsByOwner {
-    pub owner_address: String,
-    pub sort_by: Option<AssetSorting>,
-    pub limit: Option<u32>,
-    pub page: Option<u32>,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-    #[serde(default)]
-    pub cursor: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAsset {
-    pub id: String,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssets {
-    pub ids: Vec<String>,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetProof {
-    pub id: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct LeafTreePayload {
-    pub tree: String,
-    pub leaf_idx: u32,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetCompressedAccounts {
-    pub program_id: String,
-    pub account_name: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetCharacters {
-    pub wallet: String,
-    pub merkle_tree: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetProofs {
-    pub ids: Vec<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetsByCreator {
-    pub creator_address: String,
-    pub only_verified: Option<bool>,
-    pub sort_by: Option<AssetSorting>,
-    pub limit: Option<u32>,
-    pub page: Option<u32>,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-    #[serde(default)]
-    pub cursor: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct SearchAssets {
-    pub negate: Option<bool>,
-    pub condition_type: Option<SearchConditionType>,
-    pub interface: Option<Interface>,
-    pub owner_address: Option<String>,
-    pub owner_type: Option<OwnershipModel>,
-    pub creator_address: Option<String>,
-    pub creator_verified: Option<bool>,
-    pub authority_address: Option<String>,
-    pub grouping: Option<(String, String)>,
-    pub delegate: Option<String>,
-    pub frozen: Option<bool>,
-    pub supply: Option<u64>,
-    pub supply_mint: Option<String>,
-    pub compressed: Option<bool>,
-    pub compressible: Option<bool>,
-    pub royalty_target_type: Option<RoyaltyModel>,
-    pub royalty_target: Option<String>,
-    pub royalty_amount: Option<u32>,
-    pub burnt: Option<bool>,
-    pub sort_by: Option<AssetSorting>,
-    pub limit: Option<u32>,
-    pub page: Option<u32>,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    #[serde(default)]
-    pub json_uri: Option<String>,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-    #[serde(default)]
-    pub cursor: Option<String>,
-    #[serde(default)]
-    pub name: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetsByAuthority {
-    pub authority_address: String,
-    pub sort_by: Option<AssetSorting>,
-    pub limit: Option<u32>,
-    pub page: Option<u32>,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    #[serde(default, alias = "displayOptions")]
-    pub options: Option<Options>,
-    #[serde(default)]
-    pub cursor: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetGrouping {
-    pub group_key: String,
-    pub group_value: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct GetAssetSignatures {
-    pub id: Option<String>,
-    pub limit: Option<u32>,
-    pub page: Option<u32>,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    pub tree: Option<String>,
-    pub leaf_index: Option<i64>,
-    #[serde(default)]
-    pub cursor: Option<String>,
-    #[serde(default)]
-    pub sort_direction: Option<AssetSortDirection>,
-}
-
 #[document_rpc]
 #[async_trait]
 pub trait ApiContract: Send + Sync + 'static {
@@ -203,7 +32,7 @@ pub trait ApiContract: Send + Sync + 'static {
     )]
     async fn get_compressed_data(
         &self,
-        payload: LeafTreePayload,
+        payload: GetCompressedData,
     ) -> Result<CompressedData, DasApiError>;
     #[rpc(
         name = "getCompressedAccounts",
@@ -304,7 +133,106 @@ pub trait ApiContract: Send + Sync + 'static {
     #[rpc(
         name = "getGrouping",
         params = "named",
-        summary = "Get a list of assets grouped by a specific authority"
+        summary = "Get statistics for a group, e.g. a collection"
     )]
     async fn get_grouping(&self, payload: GetGrouping) -> Result<GetGroupingResponse, DasApiError>;
+    #[rpc(
+        name = "getTokenAccounts",
+        params = "named",
+        summary = "Get token accounts by owner and/or mint"
+    )]
+    async fn get_token_accounts(
+        &self,
+        payload: GetTokenAccounts,
+    ) -> Result<TokenAccountList, DasApiError>;
+    #[rpc(
+        name = "getTokenBalances",
+        params = "named",
+        summary = "Get the fungible token balances of an owner"
+    )]
+    async fn get_token_balances(
+        &self,
+        payload: GetTokenBalances,
+    ) -> Result<TokenBalanceList, DasApiError>;
+    #[rpc(
+        name = "getCurrencyHoldings",
+        params = "named",
+        summary = "Get the HPL currency holder accounts of a wallet with their balances"
+    )]
+    async fn get_currency_holdings(
+        &self,
+        payload: GetCurrencyHoldings,
+    ) -> Result<Vec<CurrencyHolding>, DasApiError>;
+    #[rpc(
+        name = "getStakingPositions",
+        params = "named",
+        summary = "Get the characters a wallet has staked in Nectar staking pools"
+    )]
+    async fn get_staking_positions(
+        &self,
+        payload: GetStakingPositions,
+    ) -> Result<Vec<StakingPosition>, DasApiError>;
+    #[rpc(
+        name = "getMissionParticipations",
+        params = "named",
+        summary = "Get the characters a wallet has sent on Nectar missions"
+    )]
+    async fn get_mission_participations(
+        &self,
+        payload: GetMissionParticipations,
+    ) -> Result<Vec<MissionParticipation>, DasApiError>;
+    #[rpc(
+        name = "getCharacterByAsset",
+        params = "named",
+        summary = "Get the character wrapping an NFT or cNFT"
+    )]
+    async fn get_character_by_asset(
+        &self,
+        payload: GetCharacterByAsset,
+    ) -> Result<Option<CompressedData>, DasApiError>;
+    #[rpc(
+        name = "getResourceHoldings",
+        params = "named",
+        summary = "Get a wallet's HPL resource balances with the recipes and faucets of their projects"
+    )]
+    async fn get_resource_holdings(
+        &self,
+        payload: GetResourceHoldings,
+    ) -> Result<ResourceHoldings, DasApiError>;
+    #[rpc(
+        name = "getProject",
+        params = "named",
+        summary = "Get an HPL Hive Control project"
+    )]
+    async fn get_project(&self, payload: GetProject) -> Result<Project, DasApiError>;
+    #[rpc(
+        name = "getProjects",
+        params = "named",
+        summary = "Get the HPL Hive Control projects of an authority or driver"
+    )]
+    async fn get_projects(&self, payload: GetProjects) -> Result<Vec<Project>, DasApiError>;
+    #[rpc(
+        name = "getProfiles",
+        params = "named",
+        summary = "Get the profiles of an HPL Hive Control project"
+    )]
+    async fn get_profiles(&self, payload: GetProfiles) -> Result<ProfileList, DasApiError>;
+    #[rpc(
+        name = "getDelegateAuthorities",
+        params = "named",
+        summary = "Get the delegate authorities of an HPL Hive Control project"
+    )]
+    async fn get_delegate_authorities(
+        &self,
+        payload: GetDelegateAuthorities,
+    ) -> Result<Vec<DelegateAuthority>, DasApiError>;
+    #[rpc(
+        name = "getCompressedDataSignatures",
+        params = "named",
+        summary = "Get the transaction signatures that wrote a compressed data leaf"
+    )]
+    async fn get_compressed_data_signatures(
+        &self,
+        payload: GetCompressedDataSignatures,
+    ) -> Result<CompressedDataSignatureList, DasApiError>;
 }
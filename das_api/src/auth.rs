@@ -0,0 +1,532 @@
+use std::{
+    collections::HashMap,
+    error::Error as StdError,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use cadence_macros::{is_global_default_set, statsd_count, statsd_time};
+use hyper::{header, Body, Method, Request, Response, StatusCode};
+use log::{debug, error, info};
+use serde::Deserialize;
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tower::{Layer, Service};
+
+use crate::{config::Config, error::DasApiError};
+
+const API_KEY_HEADER: &str = "x-api-key";
+const API_KEY_QUERY_PARAM: &str = "api-key";
+const DEFAULT_CAPACITY: i64 = 100;
+const DEFAULT_REFILL_PER_SECOND: i64 = 10;
+const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 60;
+
+/// An API key as stored in the keys file or the `api_keys` table.
+///
+/// `capacity` is the size of the key's token bucket and `refill_per_second` the rate at which
+/// it refills. Every call spends as many tokens as its method costs. When `allowed_methods` is
+/// set the key may only call those methods (either the snake_case or camelCase name works).
+#[derive(Debug, Clone, Deserialize, sqlx::FromRow)]
+pub struct ApiKey {
+    pub id: String,
+    pub key: String,
+    #[serde(default)]
+    pub capacity: Option<i64>,
+    #[serde(default)]
+    pub refill_per_second: Option<i64>,
+    #[serde(default)]
+    pub allowed_methods: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ApiKeysFile {
+    #[serde(default)]
+    pub keys: Vec<ApiKey>,
+    #[serde(default)]
+    pub method_costs: HashMap<String, u32>,
+    #[serde(default)]
+    pub default_capacity: Option<i64>,
+    #[serde(default)]
+    pub default_refill_per_second: Option<i64>,
+}
+
+/// Converts `getAssetsByOwner` style names to `get_assets_by_owner` so limits and allow-lists
+/// apply to a method regardless of which alias the caller used.
+fn normalize_method(method: &str) -> String {
+    let mut normalized = String::with_capacity(method.len() + 4);
+    for c in method.chars() {
+        if c.is_ascii_uppercase() {
+            normalized.push('_');
+            normalized.push(c.to_ascii_lowercase());
+        } else {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+fn default_method_cost(method: &str) -> u32 {
+    match method {
+        "healthz" => 0,
+        "search_assets" | "get_compressed_accounts" | "get_grouping" => 10,
+        "get_characters"
+        | "get_assets"
+        | "get_asset_batch"
+        | "get_asset_proofs"
+        | "get_asset_proof_batch"
+        | "get_assets_by_owner"
+        | "get_assets_by_creator"
+        | "get_assets_by_authority"
+        | "get_assets_by_group"
+        | "get_token_accounts"
+        | "get_token_balances"
+        | "get_currency_holdings"
+        | "get_staking_positions"
+        | "get_mission_participations"
+        | "get_resource_holdings"
+        | "get_projects"
+        | "get_profiles"
+        | "get_compressed_data_signatures" => 5,
+        "get_asset_signatures" | "get_signatures_for_asset" => 3,
+        _ => 1,
+    }
+}
+
+struct ResolvedKey {
+    id: String,
+    capacity: f64,
+    refill_per_second: f64,
+    allowed_methods: Option<Vec<String>>,
+}
+
+impl ResolvedKey {
+    fn is_allowed(&self, method: &str) -> bool {
+        match &self.allowed_methods {
+            Some(allowed) => method == "healthz" || allowed.iter().any(|m| m == method),
+            None => true,
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: f64) -> Self {
+        Self {
+            tokens: capacity,
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Spends `cost` tokens, or returns how long the caller has to wait until it could.
+    fn take(&mut self, cost: f64, capacity: f64, refill_per_second: f64) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_second).min(capacity);
+        self.updated_at = now;
+
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            return Ok(());
+        }
+        if refill_per_second <= 0.0 || cost > capacity {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64(
+            (cost - self.tokens) / refill_per_second,
+        ))
+    }
+}
+
+enum Rejection {
+    MissingKey,
+    UnknownKey,
+    MethodNotAllowed(String),
+    WebsocketNotAllowed,
+    RateLimited(Duration),
+}
+
+impl Rejection {
+    const fn reason(&self) -> &'static str {
+        match self {
+            Rejection::MissingKey => "missing_key",
+            Rejection::UnknownKey => "unknown_key",
+            Rejection::MethodNotAllowed(_) => "method_not_allowed",
+            Rejection::WebsocketNotAllowed => "websocket_not_allowed",
+            Rejection::RateLimited(_) => "rate_limited",
+        }
+    }
+
+    fn into_response(self) -> Response<Body> {
+        let (status, code, message) = match &self {
+            Rejection::MissingKey => (
+                StatusCode::UNAUTHORIZED,
+                -32001,
+                "Missing API key".to_string(),
+            ),
+            Rejection::UnknownKey => (
+                StatusCode::UNAUTHORIZED,
+                -32001,
+                "Invalid API key".to_string(),
+            ),
+            Rejection::MethodNotAllowed(method) => (
+                StatusCode::FORBIDDEN,
+                -32003,
+                format!("Method {} is not allowed for this API key", method),
+            ),
+            Rejection::WebsocketNotAllowed => (
+                StatusCode::FORBIDDEN,
+                -32003,
+                "Websocket connections are not allowed for API keys with a method allow-list"
+                    .to_string(),
+            ),
+            Rejection::RateLimited(_) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                -32005,
+                "Rate limit exceeded for this API key".to_string(),
+            ),
+        };
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "error": { "code": code, "message": message },
+            "id": null,
+        });
+        let mut builder = Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Rejection::RateLimited(retry_after) = self {
+            if retry_after != Duration::MAX {
+                builder = builder.header(
+                    header::RETRY_AFTER,
+                    retry_after.as_secs_f64().ceil().max(1.0).to_string(),
+                );
+            }
+        }
+        builder
+            .body(Body::from(body.to_string()))
+            .expect("static response parts are valid")
+    }
+}
+
+#[derive(Default)]
+struct Keys {
+    by_key: HashMap<String, Arc<ResolvedKey>>,
+}
+
+pub struct AuthState {
+    keys: RwLock<Keys>,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    method_costs: HashMap<String, u32>,
+    default_capacity: i64,
+    default_refill_per_second: i64,
+}
+
+impl AuthState {
+    fn new(file: &ApiKeysFile) -> Self {
+        Self {
+            keys: RwLock::new(Keys::default()),
+            buckets: Mutex::new(HashMap::new()),
+            method_costs: file
+                .method_costs
+                .iter()
+                .map(|(method, cost)| (normalize_method(method), *cost))
+                .collect(),
+            default_capacity: file.default_capacity.unwrap_or(DEFAULT_CAPACITY),
+            default_refill_per_second: file
+                .default_refill_per_second
+                .unwrap_or(DEFAULT_REFILL_PER_SECOND),
+        }
+    }
+
+    fn set_keys(&self, keys: Vec<ApiKey>) {
+        let by_key = keys
+            .into_iter()
+            .map(|key| {
+                let resolved = ResolvedKey {
+                    id: key.id,
+                    capacity: key.capacity.unwrap_or(self.default_capacity) as f64,
+                    refill_per_second: key
+                        .refill_per_second
+                        .unwrap_or(self.default_refill_per_second)
+                        as f64,
+                    allowed_methods: key
+                        .allowed_methods
+                        .map(|methods| methods.iter().map(|m| normalize_method(m)).collect()),
+                };
+                (key.key, Arc::new(resolved))
+            })
+            .collect();
+        self.keys
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .by_key = by_key;
+    }
+
+    fn lookup(&self, key: &str) -> Option<Arc<ResolvedKey>> {
+        self.keys
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .by_key
+            .get(key)
+            .map(Arc::clone)
+    }
+
+    fn method_cost(&self, method: &str) -> u32 {
+        self.method_costs
+            .get(method)
+            .copied()
+            .unwrap_or_else(|| default_method_cost(method))
+    }
+
+    fn take(&self, key: &ResolvedKey, cost: u32) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        buckets
+            .entry(key.id.clone())
+            .or_insert_with(|| TokenBucket::full(key.capacity))
+            .take(cost as f64, key.capacity, key.refill_per_second)
+    }
+}
+
+async fn fetch_database_keys(pool: &PgPool) -> Result<Vec<ApiKey>, sqlx::Error> {
+    sqlx::query_as::<_, ApiKey>(
+        "SELECT id, key, capacity, refill_per_second, allowed_methods FROM api_keys WHERE enabled",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+fn read_keys_file(path: &str) -> Result<ApiKeysFile, DasApiError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        DasApiError::ConfigurationError(format!("failed to read api keys file {}: {}", path, e))
+    })?;
+    serde_json::from_str(&contents).map_err(|e| {
+        DasApiError::ConfigurationError(format!("failed to parse api keys file {}: {}", path, e))
+    })
+}
+
+/// Tower layer enforcing API keys, per-key method allow-lists and token-bucket rate limits.
+///
+/// The layer is a pass-through unless `api_keys_file` or `api_keys_from_database` is configured.
+#[derive(Clone, Default)]
+pub struct AuthLayer {
+    state: Option<Arc<AuthState>>,
+}
+
+impl AuthLayer {
+    pub async fn from_config(config: &Config) -> Result<Self, DasApiError> {
+        let from_database = config.api_keys_from_database.unwrap_or(false);
+        let file = match &config.api_keys_file {
+            Some(path) => read_keys_file(path)?,
+            None if from_database => ApiKeysFile::default(),
+            None => return Ok(Self::default()),
+        };
+
+        let state = Arc::new(AuthState::new(&file));
+        let file_keys = file.keys;
+
+        if from_database {
+            let pool = PgPoolOptions::new()
+                .max_connections(2)
+                .connect(&config.database_url)
+                .await?;
+            let mut keys = file_keys.clone();
+            keys.extend(fetch_database_keys(&pool).await?);
+            info!("Loaded {} api keys", keys.len());
+            state.set_keys(keys);
+
+            let refresh_interval = Duration::from_secs(
+                config
+                    .api_keys_refresh_interval_secs
+                    .unwrap_or(DEFAULT_REFRESH_INTERVAL_SECS),
+            );
+            let refresh_state = Arc::clone(&state);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(refresh_interval);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    match fetch_database_keys(&pool).await {
+                        Ok(db_keys) => {
+                            let mut keys = file_keys.clone();
+                            keys.extend(db_keys);
+                            debug!("Reloaded {} api keys", keys.len());
+                            refresh_state.set_keys(keys);
+                        }
+                        Err(err) => error!("Failed to reload api keys: {}", err),
+                    }
+                }
+            });
+        } else {
+            info!("Loaded {} api keys", file_keys.len());
+            state.set_keys(file_keys);
+        }
+
+        Ok(Self { state: Some(state) })
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Service created by [`AuthLayer`].
+///
+/// The request body has to be read before the inner service is called, so every call takes the
+/// instance that was polled ready into its future and leaves a clone behind for the next one.
+#[derive(Clone)]
+pub struct Auth<S> {
+    inner: S,
+    state: Option<Arc<AuthState>>,
+}
+
+fn extract_key(req: &Request<Body>) -> Option<String> {
+    if let Some(value) = req.headers().get(API_KEY_HEADER) {
+        return value.to_str().ok().map(str::to_string);
+    }
+    if let Some(value) = req.headers().get(header::AUTHORIZATION) {
+        if let Some(token) = value.to_str().ok().and_then(|v| v.strip_prefix("Bearer ")) {
+            return Some(token.trim().to_string());
+        }
+    }
+    req.uri().query().and_then(|query| {
+        query.split('&').find_map(|pair| {
+            pair.strip_prefix(API_KEY_QUERY_PARAM)
+                .and_then(|rest| rest.strip_prefix('='))
+                .map(str::to_string)
+        })
+    })
+}
+
+fn is_websocket_upgrade(req: &Request<Body>) -> bool {
+    req.headers()
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+}
+
+/// Returns the normalized method names of a single or batch JSON-RPC request body.
+fn request_methods(body: &[u8]) -> Vec<String> {
+    let method_of = |call: &serde_json::Value| {
+        call.get("method")
+            .and_then(serde_json::Value::as_str)
+            .map(normalize_method)
+    };
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Array(calls)) => calls.iter().filter_map(method_of).collect(),
+        Ok(call) => method_of(&call).into_iter().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn record_rejection(key_id: &str, rejection: &Rejection) {
+    if is_global_default_set() {
+        statsd_count!("api_key_rejected", 1, "key_id" => key_id, "reason" => rejection.reason());
+    }
+}
+
+impl<S> Service<Request<Body>> for Auth<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>> + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = Box<dyn StdError + Send + Sync + 'static>;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let state = match &self.state {
+            Some(state) => Arc::clone(state),
+            None => {
+                let fut = inner.call(req);
+                return Box::pin(async move { fut.await.map_err(Into::into) });
+            }
+        };
+
+        Box::pin(async move {
+            // Health checks and CORS preflights never need a key.
+            if req.method() == Method::OPTIONS
+                || (req.method() == Method::GET && req.uri().path() == "/health")
+            {
+                return inner.call(req).await.map_err(Into::into);
+            }
+
+            let key = match extract_key(&req).map(|key| state.lookup(&key)) {
+                Some(Some(key)) => key,
+                Some(None) => {
+                    record_rejection("unknown", &Rejection::UnknownKey);
+                    return Ok(Rejection::UnknownKey.into_response());
+                }
+                None => {
+                    record_rejection("unknown", &Rejection::MissingKey);
+                    return Ok(Rejection::MissingKey.into_response());
+                }
+            };
+
+            let (req, methods) = if is_websocket_upgrade(&req) {
+                if key.allowed_methods.is_some() {
+                    let rejection = Rejection::WebsocketNotAllowed;
+                    record_rejection(&key.id, &rejection);
+                    return Ok(rejection.into_response());
+                }
+                (req, Vec::new())
+            } else {
+                let (parts, body) = req.into_parts();
+                let bytes = hyper::body::to_bytes(body).await?;
+                let methods = request_methods(&bytes);
+                (Request::from_parts(parts, Body::from(bytes)), methods)
+            };
+
+            if let Some(method) = methods.iter().find(|m| !key.is_allowed(m)) {
+                let rejection = Rejection::MethodNotAllowed(method.clone());
+                record_rejection(&key.id, &rejection);
+                return Ok(rejection.into_response());
+            }
+
+            // Unparseable bodies still cost one token; jsonrpsee answers them with a parse error.
+            let cost = if methods.is_empty() {
+                1
+            } else {
+                methods.iter().map(|m| state.method_cost(m)).sum()
+            };
+            if let Err(retry_after) = state.take(&key, cost) {
+                let rejection = Rejection::RateLimited(retry_after);
+                record_rejection(&key.id, &rejection);
+                return Ok(rejection.into_response());
+            }
+
+            let started_at = Instant::now();
+            let response = inner.call(req).await.map_err(Into::into)?;
+
+            if is_global_default_set() {
+                let key_id = key.id.as_str();
+                statsd_time!("api_key_call", started_at.elapsed(), "key_id" => key_id);
+                statsd_count!("api_key_cost", cost as i64, "key_id" => key_id);
+                for method in &methods {
+                    statsd_count!("api_key_method", 1, "key_id" => key_id, "method" => method);
+                }
+            }
+            Ok(response)
+        })
+    }
+}
@@ -33,7 +33,7 @@ impl RpcApiBuilder {
         module.register_async_method(
             "get_compressed_data",
             |rpc_params, rpc_context| async move {
-                let payload = rpc_params.parse::<LeafTreePayload>()?;
+                let payload = rpc_params.parse::<GetCompressedData>()?;
                 rpc_context
                     .get_compressed_data(payload)
                     .await
@@ -153,6 +153,141 @@ impl RpcApiBuilder {
         })?;
         module.register_alias("searchAssets", "search_assets")?;
 
+        module.register_async_method("get_grouping", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetGrouping>()?;
+            rpc_context.get_grouping(payload).await.map_err(Into::into)
+        })?;
+        module.register_alias("getGrouping", "get_grouping")?;
+
+        module.register_async_method(
+            "get_token_accounts",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetTokenAccounts>()?;
+                rpc_context
+                    .get_token_accounts(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getTokenAccounts", "get_token_accounts")?;
+
+        module.register_async_method(
+            "get_token_balances",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetTokenBalances>()?;
+                rpc_context
+                    .get_token_balances(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getTokenBalances", "get_token_balances")?;
+
+        module.register_async_method(
+            "get_currency_holdings",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetCurrencyHoldings>()?;
+                rpc_context
+                    .get_currency_holdings(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getCurrencyHoldings", "get_currency_holdings")?;
+
+        module.register_async_method(
+            "get_staking_positions",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetStakingPositions>()?;
+                rpc_context
+                    .get_staking_positions(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getStakingPositions", "get_staking_positions")?;
+
+        module.register_async_method(
+            "get_mission_participations",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetMissionParticipations>()?;
+                rpc_context
+                    .get_mission_participations(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getMissionParticipations", "get_mission_participations")?;
+
+        module.register_async_method(
+            "get_character_by_asset",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetCharacterByAsset>()?;
+                rpc_context
+                    .get_character_by_asset(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getCharacterByAsset", "get_character_by_asset")?;
+
+        module.register_async_method(
+            "get_resource_holdings",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetResourceHoldings>()?;
+                rpc_context
+                    .get_resource_holdings(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getResourceHoldings", "get_resource_holdings")?;
+
+        module.register_async_method("get_project", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetProject>()?;
+            rpc_context.get_project(payload).await.map_err(Into::into)
+        })?;
+        module.register_alias("getProject", "get_project")?;
+
+        module.register_async_method("get_projects", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetProjects>()?;
+            rpc_context.get_projects(payload).await.map_err(Into::into)
+        })?;
+        module.register_alias("getProjects", "get_projects")?;
+
+        module.register_async_method("get_profiles", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetProfiles>()?;
+            rpc_context.get_profiles(payload).await.map_err(Into::into)
+        })?;
+        module.register_alias("getProfiles", "get_profiles")?;
+
+        module.register_async_method(
+            "get_delegate_authorities",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetDelegateAuthorities>()?;
+                rpc_context
+                    .get_delegate_authorities(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getDelegateAuthorities", "get_delegate_authorities")?;
+
+        module.register_async_method(
+            "get_compressed_data_signatures",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetCompressedDataSignatures>()?;
+                rpc_context
+                    .get_compressed_data_signatures(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias(
+            "getCompressedDataSignatures",
+            "get_compressed_data_signatures",
+        )?;
+
         module.register_async_method("schema", |_, rpc_context| async move {
             Ok(rpc_context.schema())
         })?;
@@ -11,6 +11,13 @@ pub struct Config {
     pub metrics_host: Option<String>,
     pub server_port: u16,
     pub env: Option<String>,
+    /// Path to a JSON file with API keys and method costs. Enables key auth when set.
+    pub api_keys_file: Option<String>,
+    /// Load API keys from the `api_keys` table. Enables key auth when true.
+    pub api_keys_from_database: Option<bool>,
+    pub api_keys_refresh_interval_secs: Option<u64>,
+    /// Serve the WebSocket subscriptions and enable the `das_changes` triggers they listen to.
+    pub subscriptions_enabled: Option<bool>,
 }
 
 pub fn load_config() -> Result<Config, DasApiError> {
@@ -1,6 +1,9 @@
 pub mod api;
+pub mod auth;
 
 pub mod builder;
 pub mod config;
 pub mod error;
+pub mod server;
+pub mod subscriptions;
 pub mod validation;
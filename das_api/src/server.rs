@@ -0,0 +1,126 @@
+use std::time::Instant;
+use {
+    crate::api::DasApi,
+    crate::auth::AuthLayer,
+    crate::builder::RpcApiBuilder,
+    crate::config::Config,
+    crate::error::DasApiError,
+    crate::subscriptions::{build_subscriptions, ChangeListener},
+    std::net::SocketAddr,
+};
+
+use hyper::Method;
+use log::debug;
+use tower_http::cors::{Any, CorsLayer};
+
+use jsonrpsee::server::{
+    logger::{Logger, TransportProtocol},
+    middleware::proxy_get_request::ProxyGetRequestLayer,
+    ServerBuilder, ServerHandle,
+};
+
+use cadence_macros::{is_global_default_set, statsd_time};
+
+pub fn safe_metric<F: Fn()>(f: F) {
+    if is_global_default_set() {
+        f()
+    }
+}
+
+#[derive(Clone)]
+struct MetricMiddleware;
+
+impl Logger for MetricMiddleware {
+    type Instant = Instant;
+
+    fn on_request(&self, _t: TransportProtocol) -> Self::Instant {
+        Instant::now()
+    }
+
+    fn on_result(
+        &self,
+        name: &str,
+        success: bool,
+        started_at: Self::Instant,
+        _t: TransportProtocol,
+    ) {
+        let stat = match success {
+            true => "success",
+            false => "failure",
+        };
+        debug!(
+            "Call to '{}' {} took {:?}",
+            name,
+            stat,
+            started_at.elapsed()
+        );
+        safe_metric(|| {
+            let success = success.to_string();
+            statsd_time!("api_call", started_at.elapsed(), "method" => name, "success" => &success);
+        });
+    }
+
+    fn on_connect(
+        &self,
+        remote_addr: SocketAddr,
+        _request: &jsonrpsee::server::logger::HttpRequest,
+        _t: TransportProtocol,
+    ) {
+        debug!("Connecting from {}", remote_addr)
+    }
+
+    fn on_call(
+        &self,
+        method_name: &str,
+        params: jsonrpsee::types::Params,
+        _kind: jsonrpsee::server::logger::MethodKind,
+        _transport: TransportProtocol,
+    ) {
+        debug!("Call: {} {:?}", method_name, params);
+    }
+
+    fn on_response(&self, result: &str, _started_at: Self::Instant, _transport: TransportProtocol) {
+        debug!("Response: {}", result);
+    }
+
+    fn on_disconnect(&self, remote_addr: SocketAddr, _transport: TransportProtocol) {
+        debug!("Disconnecting from {}", remote_addr);
+    }
+}
+
+/// Starts the JSON-RPC server on `server_port` with the DAS methods, CORS, `/health`, and key auth
+/// and the subscriptions when configured.
+pub async fn start(config: Config) -> Result<ServerHandle, DasApiError> {
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.server_port));
+    let cors = CorsLayer::new()
+        .allow_methods([Method::POST, Method::GET])
+        .allow_origin(Any)
+        .allow_headers([
+            hyper::header::CONTENT_TYPE,
+            hyper::header::AUTHORIZATION,
+            hyper::header::HeaderName::from_static("x-api-key"),
+        ]);
+    let auth = AuthLayer::from_config(&config).await?;
+    let middleware = tower::ServiceBuilder::new()
+        .layer(cors)
+        .layer(auth)
+        .layer(ProxyGetRequestLayer::new("/health", "healthz")?);
+
+    let server = ServerBuilder::default()
+        .set_middleware(middleware)
+        .set_logger(MetricMiddleware)
+        .build(addr)
+        .await?;
+
+    let changes = if config.subscriptions_enabled.unwrap_or(false) {
+        Some(ChangeListener::start(&config).await?)
+    } else {
+        None
+    };
+    let api = DasApi::from_config(config).await?;
+    let mut rpc = RpcApiBuilder::build(Box::new(api))?;
+    if let Some(changes) = changes {
+        rpc.merge(build_subscriptions(changes)?)?;
+    }
+    Ok(server.start(rpc)?)
+}
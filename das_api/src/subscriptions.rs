@@ -0,0 +1,427 @@
+use std::{sync::Arc, time::Duration};
+
+use digital_asset_types::{
+    dapi::{get_asset, get_character_event_notification, get_compressed_data_notification},
+    rpc::{
+        notification::{
+            AssetNotification, ChangeOperation, CharacterEventNotification,
+            CompressedDataNotification,
+        },
+        options::Options,
+    },
+};
+use jsonrpsee::{
+    types::error::{ErrorCode, ErrorObject},
+    RpcModule, SubscriptionSink,
+};
+use log::{debug, error, info, warn};
+use open_rpc_schema::schemars::JsonSchema;
+use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::postgres::{PgListener, PgPoolOptions};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{config::Config, error::DasApiError, validation::validate_pubkey};
+
+const CHANGES_CHANNEL: &str = "das_changes";
+const CHANGES_CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SubscribeCompressedData {
+    pub tree_id: Option<String>,
+    pub owner: Option<String>,
+    pub program: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SubscribeCharacterEvents {
+    pub character_id: Option<String>,
+    pub tree_id: Option<String>,
+    pub owner: Option<String>,
+    pub events: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SubscribeAssetChanges {
+    pub owner: Option<String>,
+    pub group_key: Option<String>,
+    pub group_value: Option<String>,
+}
+
+/// Validates every pubkey of a subscription request up front so filters can compare base58
+/// strings as they appear in notifications.
+fn validate_opt_pubkey_string(pubkey: Option<String>) -> Result<Option<String>, DasApiError> {
+    pubkey
+        .map(|pubkey| validate_pubkey(pubkey).map(|pubkey| pubkey.to_string()))
+        .transpose()
+}
+
+impl SubscribeCompressedData {
+    fn validate(self) -> Result<Self, DasApiError> {
+        Ok(Self {
+            tree_id: validate_opt_pubkey_string(self.tree_id)?,
+            owner: validate_opt_pubkey_string(self.owner)?,
+            program: validate_opt_pubkey_string(self.program)?,
+        })
+    }
+
+    fn matches(&self, notification: &CompressedDataNotification) -> bool {
+        self.tree_id
+            .as_ref()
+            .map_or(true, |tree_id| *tree_id == notification.tree_id)
+            && self
+                .owner
+                .as_ref()
+                .map_or(true, |owner| Some(owner) == notification.owner.as_ref())
+            && self.program.as_ref().map_or(true, |program| {
+                Some(program) == notification.program.as_ref()
+            })
+    }
+}
+
+impl SubscribeCharacterEvents {
+    fn validate(self) -> Result<Self, DasApiError> {
+        Ok(Self {
+            character_id: validate_opt_pubkey_string(self.character_id)?,
+            tree_id: validate_opt_pubkey_string(self.tree_id)?,
+            owner: validate_opt_pubkey_string(self.owner)?,
+            events: self.events,
+        })
+    }
+
+    fn matches(&self, notification: &CharacterEventNotification) -> bool {
+        self.character_id
+            .as_ref()
+            .map_or(true, |id| *id == notification.character_id)
+            && self.tree_id.as_ref().map_or(true, |tree_id| {
+                Some(tree_id) == notification.tree_id.as_ref()
+            })
+            && self
+                .owner
+                .as_ref()
+                .map_or(true, |owner| Some(owner) == notification.owner.as_ref())
+            && self
+                .events
+                .as_ref()
+                .map_or(true, |events| events.contains(&notification.event))
+    }
+}
+
+impl SubscribeAssetChanges {
+    fn validate(self) -> Result<Self, DasApiError> {
+        if self.group_value.is_some() && self.group_key.is_none() {
+            return Err(DasApiError::ValidationError(
+                "groupKey must be provided with groupValue".to_string(),
+            ));
+        }
+        Ok(Self {
+            owner: validate_opt_pubkey_string(self.owner)?,
+            group_key: self.group_key,
+            group_value: self.group_value,
+        })
+    }
+
+    fn matches(&self, notification: &AssetNotification) -> bool {
+        let asset = &notification.asset;
+        let owner_matches = self
+            .owner
+            .as_ref()
+            .map_or(true, |owner| asset.ownership.owner == *owner);
+        let group_matches = self.group_key.as_ref().map_or(true, |group_key| {
+            asset.grouping.as_ref().map_or(false, |groups| {
+                groups.iter().any(|group| {
+                    group.group_key == *group_key
+                        && self
+                            .group_value
+                            .as_ref()
+                            .map_or(true, |value| group.group_value.as_ref() == Some(value))
+                })
+            })
+        });
+        owner_matches && group_matches
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    CompressedData(Arc<CompressedDataNotification>),
+    CharacterEvent(Arc<CharacterEventNotification>),
+    Asset(Arc<AssetNotification>),
+}
+
+/// Payloads sent by the `das_changes` triggers. Binary ids are hex encoded.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "table", rename_all = "snake_case")]
+enum ChangePayload {
+    CompressedData {
+        op: ChangeOperation,
+        id: String,
+        tree_id: String,
+        leaf_idx: i64,
+        owner: Option<String>,
+    },
+    CharacterHistory {
+        id: i64,
+    },
+    Asset {
+        id: String,
+    },
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, DasApiError> {
+    hex::decode(value).map_err(|e| DasApiError::ValidationError(e.to_string()))
+}
+
+async fn load_change(
+    db: &DatabaseConnection,
+    payload: ChangePayload,
+) -> Result<Option<Change>, DasApiError> {
+    let change = match payload {
+        ChangePayload::CompressedData {
+            op,
+            id,
+            tree_id,
+            leaf_idx,
+            owner,
+        } => {
+            let notification = get_compressed_data_notification(
+                db,
+                op,
+                decode_hex(&id)?,
+                decode_hex(&tree_id)?,
+                leaf_idx,
+                owner,
+            )
+            .await?;
+            Some(Change::CompressedData(Arc::new(notification)))
+        }
+        ChangePayload::CharacterHistory { id } => {
+            let notification = get_character_event_notification(db, id).await?;
+            Some(Change::CharacterEvent(Arc::new(notification)))
+        }
+        ChangePayload::Asset { id } => {
+            match get_asset(db, decode_hex(&id)?, &Options::default()).await {
+                Ok(asset) => Some(Change::Asset(Arc::new(AssetNotification { asset }))),
+                // Assets without metadata yet are announced again once it is indexed.
+                Err(sea_orm::DbErr::RecordNotFound(_)) => None,
+                Err(err) => return Err(err.into()),
+            }
+        }
+    };
+    Ok(change)
+}
+
+/// Fans changes out to subscribers. [`ChangeListener::start`] feeds it from the `das_changes`
+/// channel.
+#[derive(Clone)]
+pub struct ChangeListener {
+    sender: broadcast::Sender<Change>,
+}
+
+impl Default for ChangeListener {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANGES_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl ChangeListener {
+    /// Publishes the changed rows announced on `das_changes`. The triggers are turned on with
+    /// `das-ops subscriptions enable`, the API only listens.
+    pub async fn start(config: &Config) -> Result<Self, DasApiError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&config.database_url)
+            .await?;
+        let enabled: Option<bool> = sqlx::query_scalar(
+            "SELECT bool_and(tgenabled <> 'D') FROM pg_trigger WHERE tgname LIKE '%\\_notify\\_%'",
+        )
+        .fetch_one(&pool)
+        .await?;
+        if enabled != Some(true) {
+            warn!("Change notifications are disabled, run `das-ops subscriptions enable`");
+        }
+        let mut listener = PgListener::connect_with(&pool).await?;
+        listener.listen(CHANGES_CHANNEL).await?;
+        info!("Listening for changes on {}", CHANGES_CHANNEL);
+
+        let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        let changes = Self::default();
+        let publisher = changes.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let notification = match listener.recv().await {
+                    Ok(notification) => notification,
+                    Err(err) => {
+                        error!("Failed to receive change notification: {}", err);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
+                // Nobody is subscribed, skip loading the changed row.
+                if publisher.sender.receiver_count() == 0 {
+                    continue;
+                }
+                let payload = match serde_json::from_str::<ChangePayload>(notification.payload()) {
+                    Ok(payload) => payload,
+                    Err(err) => {
+                        warn!("Invalid change notification payload: {}", err);
+                        continue;
+                    }
+                };
+                match load_change(&db, payload).await {
+                    Ok(Some(change)) => publisher.publish(change),
+                    Ok(None) => {}
+                    Err(err) => error!("Failed to load changed row: {}", err),
+                }
+            }
+        });
+
+        Ok(changes)
+    }
+
+    /// Sends a change to every subscription whose filter matches it.
+    pub fn publish(&self, change: Change) {
+        // Fails only when nobody is subscribed.
+        let _ = self.sender.send(change);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Change> {
+        self.sender.subscribe()
+    }
+}
+
+fn parse_filter<T>(
+    params: jsonrpsee::types::Params,
+    validate: impl FnOnce(T) -> Result<T, DasApiError>,
+) -> Result<T, ErrorObject<'static>>
+where
+    T: DeserializeOwned,
+{
+    params
+        .parse::<T>()
+        .map_err(|err| err.to_string())
+        .and_then(|payload| validate(payload).map_err(|err| err.to_string()))
+        .map_err(|message| ErrorObject::owned(ErrorCode::InvalidParams.code(), message, None::<()>))
+}
+
+/// Forwards the changes selected by `select` to the subscriber until it goes away.
+fn pipe_changes<T, F>(
+    mut sink: SubscriptionSink,
+    mut changes: broadcast::Receiver<Change>,
+    select: F,
+) where
+    T: Serialize + Send + Sync + 'static,
+    F: Fn(&Change) -> Option<Arc<T>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            match changes.recv().await {
+                Ok(change) => {
+                    if let Some(item) = select(&change) {
+                        match sink.send(&*item) {
+                            Ok(true) => {}
+                            Ok(false) => break,
+                            Err(err) => {
+                                error!("Failed to serialize subscription item: {}", err);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Subscriber lagged behind, skipped {} changes", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+        debug!("Subscription closed");
+    });
+}
+
+pub fn build_subscriptions(
+    listener: ChangeListener,
+) -> Result<RpcModule<ChangeListener>, DasApiError> {
+    let mut module = RpcModule::new(listener);
+
+    module.register_subscription(
+        "subscribe_compressed_data",
+        "compressed_data_notification",
+        "unsubscribe_compressed_data",
+        |params, mut sink, listener| {
+            let filter = match parse_filter(params, SubscribeCompressedData::validate) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    let _ = sink.reject(err);
+                    return Ok(());
+                }
+            };
+            if sink.accept().is_err() {
+                return Ok(());
+            }
+            pipe_changes(sink, listener.subscribe(), move |change| match change {
+                Change::CompressedData(n) if filter.matches(n) => Some(Arc::clone(n)),
+                _ => None,
+            });
+            Ok(())
+        },
+    )?;
+    module.register_alias("subscribeCompressedData", "subscribe_compressed_data")?;
+    module.register_alias("unsubscribeCompressedData", "unsubscribe_compressed_data")?;
+
+    module.register_subscription(
+        "subscribe_character_events",
+        "character_event_notification",
+        "unsubscribe_character_events",
+        |params, mut sink, listener| {
+            let filter = match parse_filter(params, SubscribeCharacterEvents::validate) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    let _ = sink.reject(err);
+                    return Ok(());
+                }
+            };
+            if sink.accept().is_err() {
+                return Ok(());
+            }
+            pipe_changes(sink, listener.subscribe(), move |change| match change {
+                Change::CharacterEvent(n) if filter.matches(n) => Some(Arc::clone(n)),
+                _ => None,
+            });
+            Ok(())
+        },
+    )?;
+    module.register_alias("subscribeCharacterEvents", "subscribe_character_events")?;
+    module.register_alias("unsubscribeCharacterEvents", "unsubscribe_character_events")?;
+
+    module.register_subscription(
+        "subscribe_asset_changes",
+        "asset_change_notification",
+        "unsubscribe_asset_changes",
+        |params, mut sink, listener| {
+            let filter = match parse_filter(params, SubscribeAssetChanges::validate) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    let _ = sink.reject(err);
+                    return Ok(());
+                }
+            };
+            if sink.accept().is_err() {
+                return Ok(());
+            }
+            pipe_changes(sink, listener.subscribe(), move |change| match change {
+                Change::Asset(n) if filter.matches(n) => Some(Arc::clone(n)),
+                _ => None,
+            });
+            Ok(())
+        },
+    )?;
+    module.register_alias("subscribeAssetChanges", "subscribe_asset_changes")?;
+    module.register_alias("unsubscribeAssetChanges", "unsubscribe_asset_changes")?;
+
+    Ok(module)
+}
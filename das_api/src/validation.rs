@@ -1,4 +1,5 @@
 use crate::error::DasApiError;
+use digital_asset_types::rpc::filter::{AssetSortBy, AssetSorting};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
@@ -6,21 +7,58 @@ pub fn validate_pubkey(str_pubkey: String) -> Result<Pubkey, DasApiError> {
     Pubkey::from_str(&str_pubkey).map_err(|_| DasApiError::PubkeyValidationError(str_pubkey))
 }
 
+/// Shortest name or symbol that can be searched without an owner, trigram matching needs at
+/// least one full trigram.
+pub const MIN_SEARCH_TEXT_LENGTH: usize = 3;
+
 pub fn validate_search_with_name(
     name: &Option<String>,
     owner: &Option<Vec<u8>>,
 ) -> Result<Option<Vec<u8>>, DasApiError> {
-    let opt_name = if let Some(n) = name {
-        if owner.is_none() {
-            return Err(DasApiError::ValidationError(
-                "Owner address must be provided in order to search assets by name".to_owned(),
-            ));
-        }
-        Some(n.clone().into_bytes())
-    } else {
-        None
+    let opt_name = validate_search_text("name", name, owner)?;
+    Ok(opt_name.map(String::into_bytes))
+}
+
+pub fn validate_search_text(
+    field: &str,
+    text: &Option<String>,
+    owner: &Option<Vec<u8>>,
+) -> Result<Option<String>, DasApiError> {
+    let Some(text) = text else {
+        return Ok(None);
     };
-    Ok(opt_name)
+    if owner.is_none() && text.chars().count() < MIN_SEARCH_TEXT_LENGTH {
+        return Err(DasApiError::ValidationError(format!(
+            "Searching assets by {} requires at least {} characters unless an owner address is provided",
+            field, MIN_SEARCH_TEXT_LENGTH
+        )));
+    }
+    Ok(Some(text.clone()))
+}
+
+/// Rejects sorting by name outside of `searchAssets`, which is the only list joining the names.
+pub fn validate_sort_by(sort_by: &AssetSorting) -> Result<(), DasApiError> {
+    if sort_by.sort_by == AssetSortBy::Name {
+        return Err(DasApiError::ValidationError(
+            "Sorting by name is only supported by searchAssets".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Decodes a `getCompressedDataSignatures` cursor, the base58 big endian id of a changelog entry.
+pub fn validate_changelog_cursor(cursor: Option<String>) -> Result<Option<i64>, DasApiError> {
+    cursor
+        .filter(|cursor| !cursor.is_empty())
+        .map(|cursor| {
+            bs58::decode(&cursor)
+                .into_vec()
+                .ok()
+                .and_then(|bytes| <[u8; 8]>::try_from(bytes.as_slice()).ok())
+                .map(i64::from_be_bytes)
+                .ok_or(DasApiError::CursorValidationError(cursor))
+        })
+        .transpose()
 }
 
 pub fn validate_opt_pubkey(pubkey: &Option<String>) -> Result<Option<Vec<u8>>, DasApiError> {
@@ -0,0 +1,130 @@
+use std::convert::Infallible;
+
+use das_api::{auth::AuthLayer, config::Config};
+use hyper::{header, Body, Request, Response, StatusCode};
+use serde_json::{json, Value};
+use tower::{service_fn, Layer, ServiceExt};
+
+async fn ok(_request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    Ok(Response::new(Body::empty()))
+}
+
+async fn layer(name: &str, keys: Value) -> AuthLayer {
+    let path =
+        std::env::temp_dir().join(format!("das_api_auth_{}_{}.json", name, std::process::id()));
+    std::fs::write(&path, keys.to_string()).unwrap();
+    let config = Config {
+        api_keys_file: Some(path.to_string_lossy().into_owned()),
+        ..Default::default()
+    };
+    let layer = AuthLayer::from_config(&config).await.unwrap();
+    std::fs::remove_file(path).unwrap();
+    layer
+}
+
+async fn call(layer: &AuthLayer, request: Request<Body>) -> Response<Body> {
+    layer.layer(service_fn(ok)).oneshot(request).await.unwrap()
+}
+
+fn rpc(key: Option<&str>, body: Value) -> Request<Body> {
+    let mut builder = Request::post("/");
+    if let Some(key) = key {
+        builder = builder.header("x-api-key", key);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+fn method(name: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "method": name, "params": {} })
+}
+
+#[tokio::test]
+async fn rejects_missing_and_unknown_keys() {
+    let layer = layer(
+        "keys",
+        json!({ "keys": [{ "id": "game", "key": "secret" }] }),
+    )
+    .await;
+
+    let response = call(&layer, rpc(None, method("getAsset"))).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = call(&layer, rpc(Some("other"), method("getAsset"))).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = call(&layer, rpc(Some("secret"), method("getAsset"))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bearer = Request::post("/")
+        .header(header::AUTHORIZATION, "Bearer secret")
+        .body(Body::from(method("getAsset").to_string()))
+        .unwrap();
+    assert_eq!(call(&layer, bearer).await.status(), StatusCode::OK);
+    let query = Request::post("/?api-key=secret")
+        .body(Body::from(method("getAsset").to_string()))
+        .unwrap();
+    assert_eq!(call(&layer, query).await.status(), StatusCode::OK);
+
+    // health checks never need a key
+    let health = Request::get("/health").body(Body::empty()).unwrap();
+    assert_eq!(call(&layer, health).await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn enforces_allowed_methods() {
+    let layer = layer(
+        "methods",
+        json!({ "keys": [{ "id": "game", "key": "secret", "allowed_methods": ["getAsset"] }] }),
+    )
+    .await;
+
+    for name in ["getAsset", "get_asset"] {
+        let response = call(&layer, rpc(Some("secret"), method(name))).await;
+        assert_eq!(response.status(), StatusCode::OK, "{name}");
+    }
+    let response = call(&layer, rpc(Some("secret"), method("searchAssets"))).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // one disallowed call rejects the whole batch
+    let batch = json!([method("getAsset"), method("getAssetProof")]);
+    let response = call(&layer, rpc(Some("secret"), batch)).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn rate_limits_each_key_with_its_own_bucket() {
+    let layer = layer(
+        "buckets",
+        json!({
+            "keys": [
+                { "id": "game", "key": "secret", "capacity": 10, "refill_per_second": 1 },
+                { "id": "other", "key": "other", "capacity": 10, "refill_per_second": 1 },
+            ],
+            "method_costs": { "getAsset": 4 },
+        }),
+    )
+    .await;
+
+    // method costs are configurable, searchAssets costs 10 by default
+    for _ in 0..2 {
+        let response = call(&layer, rpc(Some("secret"), method("getAsset"))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = call(&layer, rpc(Some("secret"), method("getAsset"))).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+
+    let response = call(&layer, rpc(Some("other"), method("searchAssets"))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = call(&layer, rpc(Some("other"), method("searchAssets"))).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "10");
+
+    // a call costing more than the whole bucket never succeeds, so there is no retry hint
+    let layer = self::layer(
+        "small",
+        json!({ "keys": [{ "id": "game", "key": "secret", "capacity": 5 }] }),
+    )
+    .await;
+    let response = call(&layer, rpc(Some("secret"), method("searchAssets"))).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().get(header::RETRY_AFTER).is_none());
+}
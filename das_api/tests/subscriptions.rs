@@ -0,0 +1,205 @@
+use std::{sync::Arc, time::Duration};
+
+use das_api::subscriptions::{build_subscriptions, Change, ChangeListener};
+use digital_asset_types::rpc::{
+    notification::{
+        AssetNotification, ChangeOperation, CharacterEventNotification, CompressedDataNotification,
+    },
+    Asset,
+};
+use jsonrpsee::RpcModule;
+use jsonrpsee_core::{params::ObjectParams, server::rpc_module::Subscription};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use tokio::time::timeout;
+
+fn params(filter: serde_json::Value) -> ObjectParams {
+    let mut params = ObjectParams::new();
+    for (name, value) in filter.as_object().unwrap() {
+        params.insert(name, value).unwrap();
+    }
+    params
+}
+
+async fn subscribe(
+    module: &RpcModule<ChangeListener>,
+    method: &str,
+    filter: serde_json::Value,
+) -> Subscription {
+    module.subscribe(method, params(filter)).await.unwrap()
+}
+
+async fn next<T: DeserializeOwned>(subscription: &mut Subscription) -> Option<T> {
+    match timeout(Duration::from_millis(200), subscription.next::<T>()).await {
+        Ok(item) => Some(item.unwrap().unwrap().0),
+        Err(_) => None,
+    }
+}
+
+fn compressed_data(tree_id: &Pubkey, leaf_idx: i64, owner: &Pubkey) -> Change {
+    Change::CompressedData(Arc::new(CompressedDataNotification {
+        operation: ChangeOperation::Update,
+        tree_id: tree_id.to_string(),
+        leaf_idx,
+        program: None,
+        owner: Some(owner.to_string()),
+        data: None,
+    }))
+}
+
+fn character_event(id: i64, character_id: &Pubkey, event: &str) -> Change {
+    Change::CharacterEvent(Arc::new(CharacterEventNotification {
+        id,
+        character_id: character_id.to_string(),
+        event: event.to_string(),
+        event_data: json!({}),
+        slot_updated: 1,
+        tree_id: None,
+        owner: None,
+    }))
+}
+
+fn asset(owner: &Pubkey, collection: &Pubkey) -> Change {
+    let asset: Asset = serde_json::from_value(json!({
+        "interface": "V1_NFT",
+        "id": Pubkey::new_unique().to_string(),
+        "grouping": [{ "group_key": "collection", "group_value": collection.to_string() }],
+        "ownership": {
+            "frozen": false,
+            "delegated": false,
+            "delegate": null,
+            "ownership_model": "single",
+            "owner": owner.to_string(),
+        },
+        "supply": null,
+        "mutable": true,
+        "burnt": false,
+    }))
+    .unwrap();
+    Change::Asset(Arc::new(AssetNotification { asset }))
+}
+
+#[tokio::test]
+async fn compressed_data_changes_fan_out_to_matching_subscribers() {
+    let listener = ChangeListener::default();
+    let module = build_subscriptions(listener.clone()).unwrap();
+    let (tree, other_tree) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (owner, other_owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut filtered = subscribe(
+        &module,
+        "subscribeCompressedData",
+        json!({ "treeId": tree.to_string(), "owner": owner.to_string() }),
+    )
+    .await;
+    let mut everything = subscribe(&module, "subscribe_compressed_data", json!({})).await;
+
+    listener.publish(compressed_data(&tree, 1, &owner));
+    listener.publish(compressed_data(&other_tree, 2, &owner));
+    listener.publish(compressed_data(&tree, 3, &other_owner));
+    // other kinds of changes are never sent to compressed data subscribers
+    listener.publish(character_event(1, &Pubkey::new_unique(), "NewCharacter"));
+
+    let item = next::<CompressedDataNotification>(&mut filtered).await;
+    assert_eq!(item.map(|item| item.leaf_idx), Some(1));
+    assert!(next::<CompressedDataNotification>(&mut filtered)
+        .await
+        .is_none());
+
+    let mut leaves = vec![];
+    while let Some(item) = next::<CompressedDataNotification>(&mut everything).await {
+        leaves.push(item.leaf_idx);
+    }
+    assert_eq!(leaves, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn character_events_are_filtered_by_character_and_event() {
+    let listener = ChangeListener::default();
+    let module = build_subscriptions(listener.clone()).unwrap();
+    let character = Pubkey::new_unique();
+
+    let mut subscription = subscribe(
+        &module,
+        "subscribeCharacterEvents",
+        json!({ "characterId": character.to_string(), "events": ["Staked", "Unstaked"] }),
+    )
+    .await;
+
+    listener.publish(character_event(1, &character, "NewCharacter"));
+    listener.publish(character_event(2, &character, "Staked"));
+    listener.publish(character_event(3, &Pubkey::new_unique(), "Staked"));
+    listener.publish(character_event(4, &character, "Unstaked"));
+
+    let mut ids = vec![];
+    while let Some(item) = next::<CharacterEventNotification>(&mut subscription).await {
+        ids.push(item.id);
+    }
+    assert_eq!(ids, vec![2, 4]);
+}
+
+#[tokio::test]
+async fn asset_changes_are_filtered_by_owner_and_group() {
+    let listener = ChangeListener::default();
+    let module = build_subscriptions(listener.clone()).unwrap();
+    let (owner, collection) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut by_group = subscribe(
+        &module,
+        "subscribeAssetChanges",
+        json!({ "groupKey": "collection", "groupValue": collection.to_string() }),
+    )
+    .await;
+    let mut by_owner = subscribe(
+        &module,
+        "subscribeAssetChanges",
+        json!({ "owner": owner.to_string() }),
+    )
+    .await;
+
+    listener.publish(asset(&owner, &collection));
+    listener.publish(asset(&Pubkey::new_unique(), &collection));
+    listener.publish(asset(&owner, &Pubkey::new_unique()));
+
+    let mut owners = vec![];
+    while let Some(item) = next::<AssetNotification>(&mut by_group).await {
+        assert_eq!(
+            item.asset.grouping.unwrap()[0].group_value,
+            Some(collection.to_string())
+        );
+        owners.push(item.asset.ownership.owner == owner.to_string());
+    }
+    assert_eq!(owners, vec![true, false]);
+
+    let mut count = 0;
+    while let Some(item) = next::<AssetNotification>(&mut by_owner).await {
+        assert_eq!(item.asset.ownership.owner, owner.to_string());
+        count += 1;
+    }
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn invalid_filters_are_rejected() {
+    let module = build_subscriptions(ChangeListener::default()).unwrap();
+
+    let result = module
+        .subscribe(
+            "subscribeCompressedData",
+            params(json!({ "treeId": "not a pubkey" })),
+        )
+        .await;
+    assert!(result.is_err());
+    let result = module
+        .subscribe(
+            "subscribeAssetChanges",
+            params(json!({ "groupValue": Pubkey::new_unique().to_string() })),
+        )
+        .await;
+    assert!(result.is_err());
+    let result = module
+        .subscribe("subscribeCharacterEvents", params(json!({ "unknown": 1 })))
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,48 @@
+use das_api::{
+    error::DasApiError,
+    validation::{validate_changelog_cursor, validate_sort_by},
+};
+use digital_asset_types::rpc::filter::{AssetSortBy, AssetSorting};
+
+#[test]
+fn changelog_cursor_round_trips_entry_ids() {
+    let cursor = bs58::encode(42i64.to_be_bytes()).into_string();
+    assert_eq!(validate_changelog_cursor(Some(cursor)).unwrap(), Some(42));
+    assert_eq!(validate_changelog_cursor(None).unwrap(), None);
+    assert_eq!(
+        validate_changelog_cursor(Some(String::new())).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn malformed_changelog_cursors_are_validation_errors() {
+    let short = bs58::encode([1u8; 4]).into_string();
+    for cursor in ["not-base58!".to_owned(), short] {
+        assert!(matches!(
+            validate_changelog_cursor(Some(cursor.clone())),
+            Err(DasApiError::CursorValidationError(rejected)) if rejected == cursor
+        ));
+    }
+}
+
+#[test]
+fn sorting_by_name_is_rejected_outside_search() {
+    let sorting = |sort_by| AssetSorting {
+        sort_by,
+        sort_direction: None,
+    };
+    assert!(matches!(
+        validate_sort_by(&sorting(AssetSortBy::Name)),
+        Err(DasApiError::ValidationError(_))
+    ));
+    for sort_by in [
+        AssetSortBy::Id,
+        AssetSortBy::Created,
+        AssetSortBy::Updated,
+        AssetSortBy::RecentAction,
+        AssetSortBy::None,
+    ] {
+        assert!(validate_sort_by(&sorting(sort_by)).is_ok());
+    }
+}
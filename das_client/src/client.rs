@@ -0,0 +1,402 @@
+use {
+    crate::{
+        error::ClientError,
+        pagination::CursorPaginated,
+        transport::{HttpTransport, Transport},
+    },
+    digital_asset_types::rpc::{
+        options::Options,
+        request::{
+            GetAsset, GetAssetProof, GetAssetProofs, GetAssetSignatures, GetAssets,
+            GetAssetsByAuthority, GetAssetsByCreator, GetAssetsByGroup, GetAssetsByOwner,
+            GetCharacterByAsset, GetCharacters, GetCompressedAccounts, GetCompressedData,
+            GetCompressedDataSignatures, GetCurrencyHoldings, GetDelegateAuthorities, GetGrouping,
+            GetMissionParticipations, GetProfiles, GetProject, GetProjects, GetResourceHoldings,
+            GetStakingPositions, GetTokenAccounts, GetTokenBalances, LeafTreePayload, SearchAssets,
+        },
+        response::{
+            AssetList, CompressedDataSignatureList, GetGroupingResponse, ProfileList,
+            TokenAccountList, TokenBalanceList, TransactionSignatureList,
+        },
+        Asset, AssetProof, CompressedData, CurrencyHolding, DelegateAuthority,
+        MissionParticipation, Project, ResourceHoldings, StakingPosition,
+    },
+    futures::{
+        future::try_join_all,
+        stream::{self, BoxStream, StreamExt, TryStreamExt},
+    },
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    serde_json::{json, Value},
+    std::{
+        collections::HashMap,
+        sync::atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// Largest batch accepted by `getAssets` and `getAssetProofs`.
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+/// Largest page accepted by the list methods.
+pub const MAX_PAGE_LIMIT: u32 = 1000;
+
+/// Every RPC method wrapped by [`DasClient`].
+pub const METHODS: &[&str] = &[
+    "healthz",
+    "getAssetProof",
+    "getProof",
+    "getCompressedData",
+    "getCompressedAccounts",
+    "getCharacters",
+    "getAssetProofs",
+    "getAsset",
+    "getAssets",
+    "getAssetsByOwner",
+    "getAssetsByGroup",
+    "getAssetsByCreator",
+    "getAssetsByAuthority",
+    "searchAssets",
+    "getAssetSignatures",
+    "getGrouping",
+    "getTokenAccounts",
+    "getTokenBalances",
+    "getCurrencyHoldings",
+    "getStakingPositions",
+    "getMissionParticipations",
+    "getCharacterByAsset",
+    "getResourceHoldings",
+    "getProject",
+    "getProjects",
+    "getProfiles",
+    "getDelegateAuthorities",
+    "getCompressedDataSignatures",
+    "schema",
+];
+
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+    #[serde(default)]
+    data: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<RpcErrorObject>,
+}
+
+pub struct DasClient {
+    transport: Box<dyn Transport>,
+    next_id: AtomicU64,
+}
+
+impl DasClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_transport(HttpTransport::new(url))
+    }
+
+    pub fn with_transport(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Box::new(transport),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    #[cfg(feature = "in_process")]
+    pub fn in_process(contract: impl das_api::api::ApiContract) -> Result<Self, ClientError> {
+        crate::transport::InProcessTransport::new(Box::new(contract)).map(Self::with_transport)
+    }
+
+    pub async fn request<P: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<R, ClientError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let response = self.transport.send(request.to_string()).await?;
+        let response: RpcResponse = serde_json::from_str(&response)?;
+        match response.error {
+            Some(error) => Err(ClientError::Rpc {
+                code: error.code,
+                message: error.message,
+                data: error.data,
+            }),
+            None => serde_json::from_value(response.result).map_err(Into::into),
+        }
+    }
+
+    pub async fn check_health(&self) -> Result<(), ClientError> {
+        let _: Value = self.request("healthz", json!([])).await?;
+        Ok(())
+    }
+
+    pub async fn get_asset_proof(&self, payload: GetAssetProof) -> Result<AssetProof, ClientError> {
+        self.request("getAssetProof", payload).await
+    }
+
+    pub async fn get_proof(&self, payload: LeafTreePayload) -> Result<AssetProof, ClientError> {
+        self.request("getProof", payload).await
+    }
+
+    pub async fn get_compressed_data(
+        &self,
+        payload: GetCompressedData,
+    ) -> Result<CompressedData, ClientError> {
+        self.request("getCompressedData", payload).await
+    }
+
+    pub async fn get_compressed_accounts(
+        &self,
+        payload: GetCompressedAccounts,
+    ) -> Result<Vec<CompressedData>, ClientError> {
+        self.request("getCompressedAccounts", payload).await
+    }
+
+    pub async fn get_characters(
+        &self,
+        payload: GetCharacters,
+    ) -> Result<Vec<CompressedData>, ClientError> {
+        self.request("getCharacters", payload).await
+    }
+
+    pub async fn get_asset_proofs(
+        &self,
+        payload: GetAssetProofs,
+    ) -> Result<HashMap<String, Option<AssetProof>>, ClientError> {
+        self.request("getAssetProofs", payload).await
+    }
+
+    pub async fn get_asset(&self, payload: GetAsset) -> Result<Asset, ClientError> {
+        self.request("getAsset", payload).await
+    }
+
+    pub async fn get_assets(&self, payload: GetAssets) -> Result<Vec<Option<Asset>>, ClientError> {
+        self.request("getAssets", payload).await
+    }
+
+    pub async fn get_assets_by_owner(
+        &self,
+        payload: GetAssetsByOwner,
+    ) -> Result<AssetList, ClientError> {
+        self.request("getAssetsByOwner", payload).await
+    }
+
+    pub async fn get_assets_by_group(
+        &self,
+        payload: GetAssetsByGroup,
+    ) -> Result<AssetList, ClientError> {
+        self.request("getAssetsByGroup", payload).await
+    }
+
+    pub async fn get_assets_by_creator(
+        &self,
+        payload: GetAssetsByCreator,
+    ) -> Result<AssetList, ClientError> {
+        self.request("getAssetsByCreator", payload).await
+    }
+
+    pub async fn get_assets_by_authority(
+        &self,
+        payload: GetAssetsByAuthority,
+    ) -> Result<AssetList, ClientError> {
+        self.request("getAssetsByAuthority", payload).await
+    }
+
+    pub async fn search_assets(&self, payload: SearchAssets) -> Result<AssetList, ClientError> {
+        self.request("searchAssets", payload).await
+    }
+
+    pub async fn get_asset_signatures(
+        &self,
+        payload: GetAssetSignatures,
+    ) -> Result<TransactionSignatureList, ClientError> {
+        self.request("getAssetSignatures", payload).await
+    }
+
+    pub async fn get_grouping(
+        &self,
+        payload: GetGrouping,
+    ) -> Result<GetGroupingResponse, ClientError> {
+        self.request("getGrouping", payload).await
+    }
+
+    pub async fn get_token_accounts(
+        &self,
+        payload: GetTokenAccounts,
+    ) -> Result<TokenAccountList, ClientError> {
+        self.request("getTokenAccounts", payload).await
+    }
+
+    pub async fn get_token_balances(
+        &self,
+        payload: GetTokenBalances,
+    ) -> Result<TokenBalanceList, ClientError> {
+        self.request("getTokenBalances", payload).await
+    }
+
+    pub async fn get_currency_holdings(
+        &self,
+        payload: GetCurrencyHoldings,
+    ) -> Result<Vec<CurrencyHolding>, ClientError> {
+        self.request("getCurrencyHoldings", payload).await
+    }
+
+    pub async fn get_staking_positions(
+        &self,
+        payload: GetStakingPositions,
+    ) -> Result<Vec<StakingPosition>, ClientError> {
+        self.request("getStakingPositions", payload).await
+    }
+
+    pub async fn get_mission_participations(
+        &self,
+        payload: GetMissionParticipations,
+    ) -> Result<Vec<MissionParticipation>, ClientError> {
+        self.request("getMissionParticipations", payload).await
+    }
+
+    pub async fn get_character_by_asset(
+        &self,
+        payload: GetCharacterByAsset,
+    ) -> Result<Option<CompressedData>, ClientError> {
+        self.request("getCharacterByAsset", payload).await
+    }
+
+    pub async fn get_resource_holdings(
+        &self,
+        payload: GetResourceHoldings,
+    ) -> Result<ResourceHoldings, ClientError> {
+        self.request("getResourceHoldings", payload).await
+    }
+
+    pub async fn get_project(&self, payload: GetProject) -> Result<Project, ClientError> {
+        self.request("getProject", payload).await
+    }
+
+    pub async fn get_projects(&self, payload: GetProjects) -> Result<Vec<Project>, ClientError> {
+        self.request("getProjects", payload).await
+    }
+
+    pub async fn get_profiles(&self, payload: GetProfiles) -> Result<ProfileList, ClientError> {
+        self.request("getProfiles", payload).await
+    }
+
+    pub async fn get_delegate_authorities(
+        &self,
+        payload: GetDelegateAuthorities,
+    ) -> Result<Vec<DelegateAuthority>, ClientError> {
+        self.request("getDelegateAuthorities", payload).await
+    }
+
+    pub async fn get_compressed_data_signatures(
+        &self,
+        payload: GetCompressedDataSignatures,
+    ) -> Result<CompressedDataSignatureList, ClientError> {
+        self.request("getCompressedDataSignatures", payload).await
+    }
+
+    /// The OpenRPC document describing the server.
+    pub async fn schema(&self) -> Result<Value, ClientError> {
+        self.request("schema", json!([])).await
+    }
+
+    /// Fetches any number of assets, splitting the ids into batches the server accepts.
+    /// Results keep the order of `ids`.
+    pub async fn get_assets_batched(
+        &self,
+        ids: Vec<String>,
+        options: Option<Options>,
+    ) -> Result<Vec<Option<Asset>>, ClientError> {
+        let batches = ids.chunks(MAX_BATCH_SIZE).map(|ids| {
+            self.get_assets(GetAssets {
+                ids: ids.to_vec(),
+                options: options.clone(),
+            })
+        });
+        Ok(try_join_all(batches).await?.into_iter().flatten().collect())
+    }
+
+    /// Fetches proofs for any number of assets, splitting the ids into batches the server accepts.
+    pub async fn get_asset_proofs_batched(
+        &self,
+        ids: Vec<String>,
+    ) -> Result<HashMap<String, Option<AssetProof>>, ClientError> {
+        let batches = ids
+            .chunks(MAX_BATCH_SIZE)
+            .map(|ids| self.get_asset_proofs(GetAssetProofs { ids: ids.to_vec() }));
+        Ok(try_join_all(batches).await?.into_iter().flatten().collect())
+    }
+
+    /// Streams pages of an asset list, passing the cursor of every page to the next request.
+    /// Ends after the first page with fewer items than the limit.
+    pub fn pages<P: CursorPaginated + 'static>(
+        &self,
+        payload: P,
+    ) -> BoxStream<'_, Result<AssetList, ClientError>> {
+        if let Err(error) = payload.validate_cursor_pagination() {
+            return stream::once(async move { Err(error) }).boxed();
+        }
+        let limit = payload.limit().unwrap_or(MAX_PAGE_LIMIT) as usize;
+        stream::try_unfold(Some(payload), move |payload| async move {
+            let Some(mut payload) = payload else {
+                return Ok(None);
+            };
+            let page: AssetList = self.request(P::METHOD, &payload).await?;
+            let next = match &page.cursor {
+                Some(cursor) if page.items.len() >= limit && limit > 0 => {
+                    payload.set_cursor(Some(cursor.clone()));
+                    Some(payload)
+                }
+                _ => None,
+            };
+            Ok(Some((page, next)))
+        })
+        .boxed()
+    }
+
+    /// Collects every asset matched by a list query by following cursors.
+    pub async fn collect_all<P: CursorPaginated + 'static>(
+        &self,
+        payload: P,
+    ) -> Result<Vec<Asset>, ClientError> {
+        self.pages(payload)
+            .map_ok(|page| stream::iter(page.items.into_iter().map(Ok)))
+            .try_flatten()
+            .try_collect()
+            .await
+    }
+
+    /// Collects every signature of an asset by walking pages, signatures have no cursor.
+    pub async fn get_all_asset_signatures(
+        &self,
+        mut payload: GetAssetSignatures,
+    ) -> Result<Vec<(String, String)>, ClientError> {
+        if payload.before.is_some() || payload.after.is_some() || payload.cursor.is_some() {
+            return Err(ClientError::Pagination(
+                "page pagination can't be combined with before, after or cursor".to_string(),
+            ));
+        }
+        let limit = payload.limit.unwrap_or(MAX_PAGE_LIMIT);
+        payload.limit = Some(limit);
+        let mut page = payload.page.unwrap_or(1);
+        let mut items = Vec::new();
+        loop {
+            payload.page = Some(page);
+            let list = self.get_asset_signatures(payload.clone()).await?;
+            let done = list.items.len() < limit as usize || limit == 0;
+            items.extend(list.items);
+            if done {
+                return Ok(items);
+            }
+            page += 1;
+        }
+    }
+}
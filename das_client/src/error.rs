@@ -0,0 +1,21 @@
+use {serde_json::Value, thiserror::Error};
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Unexpected HTTP status {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("Transport error: {0}")]
+    Transport(String),
+    #[error("RPC error {code}: {message}")]
+    Rpc {
+        code: i64,
+        message: String,
+        data: Option<Value>,
+    },
+    #[error("Invalid response: {0}")]
+    Deserialization(#[from] serde_json::Error),
+    #[error("Pagination Error: {0}")]
+    Pagination(String),
+}
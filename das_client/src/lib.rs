@@ -0,0 +1,13 @@
+pub mod client;
+pub mod error;
+pub mod pagination;
+pub mod transport;
+
+#[cfg(feature = "in_process")]
+pub use transport::InProcessTransport;
+pub use {
+    client::{DasClient, METHODS},
+    error::ClientError,
+    pagination::CursorPaginated,
+    transport::{HttpTransport, Transport},
+};
@@ -0,0 +1,68 @@
+use {
+    crate::error::ClientError,
+    digital_asset_types::rpc::{
+        filter::{AssetSortBy, AssetSorting},
+        request::{
+            GetAssetsByAuthority, GetAssetsByCreator, GetAssetsByGroup, GetAssetsByOwner,
+            SearchAssets,
+        },
+    },
+    serde::Serialize,
+};
+
+/// Asset list payloads that can be walked with the `cursor` returned by the previous page.
+pub trait CursorPaginated: Clone + Serialize + Send + Sync {
+    const METHOD: &'static str;
+
+    fn limit(&self) -> Option<u32>;
+
+    fn set_cursor(&mut self, cursor: Option<String>);
+
+    /// The server only accepts a cursor for id-sorted queries without page, before or after.
+    fn validate_cursor_pagination(&self) -> Result<(), ClientError>;
+}
+
+fn validate_cursor_pagination(
+    sort_by: &Option<AssetSorting>,
+    page: Option<u32>,
+    before: &Option<String>,
+    after: &Option<String>,
+) -> Result<(), ClientError> {
+    if page.is_some() || before.is_some() || after.is_some() {
+        return Err(ClientError::Pagination(
+            "cursor pagination can't be combined with page, before or after".to_string(),
+        ));
+    }
+    if matches!(sort_by, Some(sorting) if sorting.sort_by != AssetSortBy::Id) {
+        return Err(ClientError::Pagination(
+            "cursor pagination requires sorting by id".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+macro_rules! impl_cursor_paginated {
+    ($payload:ty, $method:literal) => {
+        impl CursorPaginated for $payload {
+            const METHOD: &'static str = $method;
+
+            fn limit(&self) -> Option<u32> {
+                self.limit
+            }
+
+            fn set_cursor(&mut self, cursor: Option<String>) {
+                self.cursor = cursor;
+            }
+
+            fn validate_cursor_pagination(&self) -> Result<(), ClientError> {
+                validate_cursor_pagination(&self.sort_by, self.page, &self.before, &self.after)
+            }
+        }
+    };
+}
+
+impl_cursor_paginated!(GetAssetsByOwner, "getAssetsByOwner");
+impl_cursor_paginated!(GetAssetsByGroup, "getAssetsByGroup");
+impl_cursor_paginated!(GetAssetsByCreator, "getAssetsByCreator");
+impl_cursor_paginated!(GetAssetsByAuthority, "getAssetsByAuthority");
+impl_cursor_paginated!(SearchAssets, "searchAssets");
@@ -0,0 +1,96 @@
+use {crate::error::ClientError, async_trait::async_trait, reqwest::header::CONTENT_TYPE};
+#[cfg(feature = "in_process")]
+use {
+    das_api::{api::ApiContract, builder::RpcApiBuilder},
+    jsonrpsee::RpcModule,
+};
+
+/// Moves one serialized JSON-RPC request to a server and returns the serialized response.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn send(&self, request: String) -> Result<String, ClientError>;
+}
+
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl HttpTransport {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), url)
+    }
+
+    pub fn with_client(client: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            api_key: None,
+        }
+    }
+
+    /// Sent as `x-api-key` for servers with API key auth enabled.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send(&self, request: String) -> Result<String, ClientError> {
+        let mut builder = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(request);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.header("x-api-key", api_key);
+        }
+        let response = builder.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        // rejected requests (auth, rate limits) still carry a JSON-RPC error body
+        if status.is_success() || body.trim_start().starts_with('{') {
+            Ok(body)
+        } else {
+            Err(ClientError::Status {
+                status: status.as_u16(),
+                body,
+            })
+        }
+    }
+}
+
+/// Calls the RPC module directly, without a server, e.g. for tests against a mocked database.
+#[cfg(feature = "in_process")]
+pub struct InProcessTransport {
+    module: RpcModule<Box<dyn ApiContract>>,
+}
+
+#[cfg(feature = "in_process")]
+impl InProcessTransport {
+    pub fn new(contract: Box<dyn ApiContract>) -> Result<Self, ClientError> {
+        let module = RpcApiBuilder::build(contract)
+            .map_err(|error| ClientError::Transport(error.to_string()))?;
+        Ok(Self { module })
+    }
+
+    pub const fn module(&self) -> &RpcModule<Box<dyn ApiContract>> {
+        &self.module
+    }
+}
+
+#[cfg(feature = "in_process")]
+#[async_trait]
+impl Transport for InProcessTransport {
+    async fn send(&self, request: String) -> Result<String, ClientError> {
+        let (response, _) = self
+            .module
+            .raw_json_request(&request)
+            .await
+            .map_err(|error| ClientError::Transport(error.to_string()))?;
+        Ok(response.result)
+    }
+}
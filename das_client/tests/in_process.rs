@@ -0,0 +1,160 @@
+use das_api::api::{DasApi, GetAsset, GetAssetsByOwner, GetCompressedData};
+use das_client::{ClientError, DasClient, InProcessTransport, METHODS};
+use digital_asset_types::{
+    dao::compressed_data,
+    rpc::filter::{AssetSortBy, AssetSorting},
+};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+fn client(db: DatabaseConnection) -> DasClient {
+    DasClient::in_process(DasApi::from_connection(db)).unwrap()
+}
+
+fn empty_db() -> DatabaseConnection {
+    MockDatabase::new(DatabaseBackend::Postgres).into_connection()
+}
+
+/// Legacy names the server keeps for methods the client calls by their current name.
+const ALIASES: &[&str] = &[
+    "get_asset_proof_batch",
+    "getAssetProofBatch",
+    "get_asset_batch",
+    "getAssetBatch",
+    "getSignaturesForAsset",
+];
+
+fn camel_case(method: &str) -> String {
+    let mut parts = method.split('_');
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
+#[test]
+fn every_client_method_is_registered() {
+    let transport = InProcessTransport::new(Box::new(DasApi::from_connection(empty_db()))).unwrap();
+    let registered = transport.module().method_names().collect::<Vec<_>>();
+    for method in METHODS {
+        assert!(registered.contains(method), "{method} is not registered");
+    }
+}
+
+#[test]
+fn every_registered_method_has_a_client_method() {
+    let transport = InProcessTransport::new(Box::new(DasApi::from_connection(empty_db()))).unwrap();
+    for method in transport.module().method_names() {
+        if ALIASES.contains(&method) {
+            continue;
+        }
+        assert!(
+            METHODS.contains(&camel_case(method).as_str()),
+            "{method} is not wrapped by the client"
+        );
+    }
+}
+
+#[tokio::test]
+async fn get_compressed_data() {
+    let tree = Keypair::new().pubkey();
+    let parsed_data = json!({ "owner": "pubkey:11111111111111111111111111111111" });
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![compressed_data::Model {
+            id: vec![1; 32],
+            tree_id: tree.to_bytes().to_vec(),
+            leaf_idx: 7,
+            seq: 3,
+            schema_validated: true,
+            raw_data: vec![],
+            parsed_data: parsed_data.clone(),
+            created_at: None,
+            slot_updated: 42,
+        }]])
+        .into_connection();
+
+    let data = client(db)
+        .get_compressed_data(GetCompressedData {
+            tree: tree.to_string(),
+            leaf_idx: 7,
+            options: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(data.id, vec![1; 32]);
+    assert_eq!(data.tree_id, tree.to_string());
+    assert_eq!(data.leaf_idx, 7);
+    assert!(data.schema_validated);
+    assert_eq!(data.parsed_data, parsed_data);
+    assert_eq!(data.slot_updated, 42);
+}
+
+#[tokio::test]
+async fn rpc_errors_are_returned() {
+    let error = client(empty_db())
+        .get_asset(GetAsset {
+            id: "not-a-pubkey".to_string(),
+            options: None,
+        })
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, ClientError::Rpc { message, .. } if message.contains("not-a-pubkey")),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn cursor_pagination_is_validated() {
+    let client = client(empty_db());
+    let payload = GetAssetsByOwner {
+        owner_address: Keypair::new().pubkey().to_string(),
+        sort_by: None,
+        limit: Some(10),
+        page: Some(1),
+        before: None,
+        after: None,
+        options: None,
+        cursor: None,
+    };
+
+    let error = client.collect_all(payload.clone()).await.unwrap_err();
+    assert!(matches!(error, ClientError::Pagination(_)), "{error:?}");
+
+    let error = client
+        .collect_all(GetAssetsByOwner {
+            page: None,
+            sort_by: Some(AssetSorting {
+                sort_by: AssetSortBy::Created,
+                sort_direction: None,
+            }),
+            ..payload
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Pagination(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn empty_batches_skip_requests() {
+    // the mock database has no results, any query would fail
+    let client = client(empty_db());
+    assert!(client
+        .get_assets_batched(vec![], None)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(client
+        .get_asset_proofs_batched(vec![])
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use das_client::{ClientError, DasClient, Transport};
+use digital_asset_types::rpc::request::GetAssetsByOwner;
+use futures::TryStreamExt;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// Serves `getAssetsByOwner` from a fixed list of ids, `limit` at a time after the cursor.
+struct ScriptedTransport {
+    ids: Vec<String>,
+    cursors: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+#[async_trait]
+impl Transport for ScriptedTransport {
+    async fn send(&self, request: String) -> Result<String, ClientError> {
+        let request: Value = serde_json::from_str(&request)?;
+        assert_eq!(request["method"], "getAssetsByOwner");
+        let params = &request["params"];
+        let cursor = params["cursor"].as_str().map(str::to_owned);
+        self.cursors.lock().unwrap().push(cursor.clone());
+
+        let limit = params["limit"].as_u64().unwrap() as usize;
+        let start = cursor
+            .map(|cursor| self.ids.iter().position(|id| *id == cursor).unwrap() + 1)
+            .unwrap_or_default();
+        let ids = self.ids.iter().skip(start).take(limit).collect::<Vec<_>>();
+        let items = ids.iter().map(|id| asset(id)).collect::<Vec<_>>();
+        let result = json!({
+            "total": items.len(),
+            "limit": limit,
+            "cursor": ids.last(),
+            "items": items,
+        });
+        Ok(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string())
+    }
+}
+
+fn asset(id: &str) -> Value {
+    json!({
+        "interface": "V1_NFT",
+        "id": id,
+        "ownership": {
+            "frozen": false,
+            "delegated": false,
+            "delegate": null,
+            "ownership_model": "single",
+            "owner": "11111111111111111111111111111111",
+        },
+        "supply": null,
+        "mutable": true,
+        "burnt": false,
+    })
+}
+
+fn payload(limit: u32) -> GetAssetsByOwner {
+    GetAssetsByOwner {
+        owner_address: "11111111111111111111111111111111".to_string(),
+        sort_by: None,
+        limit: Some(limit),
+        page: None,
+        before: None,
+        after: None,
+        options: None,
+        cursor: None,
+    }
+}
+
+fn client(count: usize) -> (DasClient, Vec<String>, Arc<Mutex<Vec<Option<String>>>>) {
+    let ids = (0..count).map(|i| format!("asset-{i}")).collect::<Vec<_>>();
+    let cursors = Arc::new(Mutex::new(vec![]));
+    let transport = ScriptedTransport {
+        ids: ids.clone(),
+        cursors: Arc::clone(&cursors),
+    };
+    (DasClient::with_transport(transport), ids, cursors)
+}
+
+#[tokio::test]
+async fn collect_all_follows_cursors_until_a_short_page() {
+    let (client, ids, cursors) = client(5);
+
+    let assets = client.collect_all(payload(2)).await.unwrap();
+
+    assert_eq!(
+        assets.into_iter().map(|asset| asset.id).collect::<Vec<_>>(),
+        ids
+    );
+    assert_eq!(
+        *cursors.lock().unwrap(),
+        vec![None, Some(ids[1].clone()), Some(ids[3].clone())]
+    );
+}
+
+#[tokio::test]
+async fn pages_stop_after_an_empty_page() {
+    let (client, ids, cursors) = client(4);
+
+    let pages = client
+        .pages(payload(2))
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    let sizes = pages
+        .iter()
+        .map(|page| page.items.len())
+        .collect::<Vec<_>>();
+    assert_eq!(sizes, vec![2, 2, 0]);
+    assert_eq!(pages[1].cursor.as_ref(), Some(&ids[3]));
+    assert_eq!(pages[2].cursor, None);
+    assert_eq!(cursors.lock().unwrap().len(), 3);
+}
@@ -20,6 +20,7 @@ pub struct Model {
     pub parsed_data: Json,
     pub created_at: Option<DateTimeWithTimeZone>,
     pub slot_updated: i64,
+    pub project: Option<Vec<u8>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -30,6 +31,7 @@ pub enum Column {
     ParsedData,
     CreatedAt,
     SlotUpdated,
+    Project,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -57,6 +59,7 @@ impl ColumnTrait for Column {
             Self::ParsedData => ColumnType::JsonBinary.def(),
             Self::CreatedAt => ColumnType::TimestampWithTimeZone.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
+            Self::Project => ColumnType::Binary.def().null(),
         }
     }
 }
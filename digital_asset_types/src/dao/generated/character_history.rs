@@ -20,6 +20,7 @@ pub struct Model {
     pub event_data: Json,
     pub created_at: Option<DateTimeWithTimeZone>,
     pub slot_updated: i64,
+    pub signature: Option<Vec<u8>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -30,6 +31,7 @@ pub enum Column {
     EventData,
     CreatedAt,
     SlotUpdated,
+    Signature,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -59,6 +61,7 @@ impl ColumnTrait for Column {
             Self::EventData => ColumnType::JsonBinary.def(),
             Self::CreatedAt => ColumnType::TimestampWithTimeZone.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
+            Self::Signature => ColumnType::Binary.def().null(),
         }
     }
 }
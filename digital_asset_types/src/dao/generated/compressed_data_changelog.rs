@@ -22,6 +22,8 @@ pub struct Model {
     pub seq: i64,
     pub slot: i64,
     pub created_at: Option<DateTimeWithTimeZone>,
+    pub signature: Option<Vec<u8>>,
+    pub event_type: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -34,6 +36,8 @@ pub enum Column {
     Seq,
     Slot,
     CreatedAt,
+    Signature,
+    EventType,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -63,6 +67,8 @@ impl ColumnTrait for Column {
             Self::Seq => ColumnType::BigInteger.def(),
             Self::Slot => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::TimestampWithTimeZone.def().null(),
+            Self::Signature => ColumnType::Binary.def().null(),
+            Self::EventType => ColumnType::String(Some(16u32)).def(),
         }
     }
 }
@@ -20,6 +20,7 @@ pub struct Model {
     pub data_schema: Vec<u8>,
     pub canopy_depth: i32,
     pub created_at: Option<DateTimeWithTimeZone>,
+    pub project: Option<Vec<u8>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -30,6 +31,7 @@ pub enum Column {
     DataSchema,
     CanopyDepth,
     CreatedAt,
+    Project,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -57,6 +59,7 @@ impl ColumnTrait for Column {
             Self::DataSchema => ColumnType::Binary.def(),
             Self::CanopyDepth => ColumnType::Integer.def(),
             Self::CreatedAt => ColumnType::TimestampWithTimeZone.def().null(),
+            Self::Project => ColumnType::Binary.def().null(),
         }
     }
 }
@@ -10,17 +10,26 @@ pub use full_asset::*;
 pub use generated::*;
 pub mod extensions;
 
+use crate::rpc::filter::AttributeFilter;
 use log::debug;
 use sea_orm::{
     entity::*,
     sea_query::Expr,
     sea_query::{ConditionType, IntoCondition, SimpleExpr},
-    Condition, DbErr, RelationDef,
+    Condition, DbErr, FromQueryResult, RelationDef,
 };
 use serde::{Deserialize, Serialize};
 
-pub struct GroupingSize {
-    pub size: u64,
+#[derive(FromQueryResult, Debug, Default, Clone, PartialEq, Eq)]
+pub struct GroupingStats {
+    pub verified: i64,
+    pub unverified: i64,
+    pub compressed: i64,
+    pub uncompressed: i64,
+    pub burnt: i64,
+    pub unique_holders: i64,
+    pub first_mint_slot: Option<i64>,
+    pub last_mint_slot: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -74,9 +83,21 @@ pub struct SearchAssetsQuery {
     pub burnt: Option<bool>,
     pub json_uri: Option<String>,
     pub name: Option<Vec<u8>>,
+    pub symbol: Option<String>,
+    pub attributes: Option<Vec<AttributeFilter>>,
+    /// Defaults to [ConditionType::All]
+    pub attribute_condition_type: Option<ConditionType>,
 }
 
 impl SearchAssetsQuery {
+    /// Whether the conditions filter on `asset_data`, which is then joined.
+    pub fn joins_asset_data(&self) -> bool {
+        self.json_uri.is_some()
+            || self.name.is_some()
+            || self.symbol.is_some()
+            || self.attributes.is_some()
+    }
+
     pub fn conditions(&self) -> Result<(Condition, Vec<RelationDef>), DbErr> {
         let mut conditions = match self.condition_type {
             // None --> default to all when no option is provided
@@ -225,15 +246,6 @@ impl SearchAssetsQuery {
         if let Some(ju) = self.json_uri.to_owned() {
             let cond = Condition::all().add(asset_data::Column::MetadataUrl.eq(ju));
             conditions = conditions.add(cond);
-            let rel = extensions::asset_data::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_data::Column::Id)
-                        .eq(Expr::tbl(left, asset::Column::AssetData))
-                        .into_condition()
-                });
-            joins.push(rel);
         }
 
         if let Some(n) = self.name.to_owned() {
@@ -242,20 +254,34 @@ impl SearchAssetsQuery {
                     "Could not convert raw name bytes into string for comparison".to_owned(),
                 )
             })?;
+            conditions = conditions.add(text_search_condition("name", name_as_str));
+        }
 
-            let name_expr =
-                SimpleExpr::Custom(format!("chain_data->>'name' LIKE '%{}%'", name_as_str));
+        if let Some(symbol) = self.symbol.to_owned() {
+            conditions = conditions.add(text_search_condition("symbol", &symbol));
+        }
 
-            conditions = conditions.add(name_expr);
-            let rel = extensions::asset_data::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_data::Column::Id)
-                        .eq(Expr::tbl(left, asset::Column::AssetData))
-                        .into_condition()
-                });
-            joins.push(rel);
+        if let Some(attributes) = self.attributes.to_owned() {
+            let mut cond = match self.attribute_condition_type {
+                None | Some(ConditionType::All) => Condition::all(),
+                Some(ConditionType::Any) => Condition::any(),
+            };
+            // Matches the `asset_data_attributes_idx` GIN index.
+            for attribute in attributes {
+                let attribute = serde_json::json!([{
+                    "trait_type": attribute.trait_type,
+                    "value": attribute.value,
+                }]);
+                cond = cond.add(Expr::cust_with_values(
+                    "asset_data.metadata->'attributes' @> $1::jsonb",
+                    vec![attribute.to_string()],
+                ));
+            }
+            conditions = conditions.add(cond);
+        }
+
+        if self.joins_asset_data() {
+            joins.push(asset_data_join());
         }
         conditions = match self.negate {
             None | Some(false) => conditions,
@@ -264,3 +290,29 @@ impl SearchAssetsQuery {
         Ok((conditions, joins))
     }
 }
+
+pub fn asset_data_join() -> RelationDef {
+    extensions::asset_data::Relation::Asset
+        .def()
+        .rev()
+        .on_condition(|left, right| {
+            Expr::tbl(right, asset_data::Column::Id)
+                .eq(Expr::tbl(left, asset::Column::AssetData))
+                .into_condition()
+        })
+}
+
+/// Case-insensitive substring or trigram similarity match on an on-chain `chain_data` field,
+/// both served by the `asset_data_<field>_trgm_idx` indexes.
+fn text_search_condition(field: &str, search: &str) -> SimpleExpr {
+    let substring = search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    Expr::cust_with_values(
+        &format!(
+            "(asset_data.chain_data->>'{field}' ILIKE $1 OR asset_data.chain_data->>'{field}' % $2)"
+        ),
+        vec![format!("%{}%", substring), search.to_string()],
+    )
+}
@@ -4,12 +4,15 @@ use crate::{
         asset_authority, asset_creators, asset_data, asset_grouping, cl_audits_v2,
         extensions::{self, instruction::PascalCase},
         sea_orm_active_enums::Instruction,
-        token_accounts, tokens, Cursor, FullAsset, GroupingSize, Pagination,
+        token_accounts, tokens, Cursor, FullAsset, GroupingStats, Pagination,
     },
-    rpc::filter::AssetSortDirection,
+    rpc::{filter::AssetSortDirection, options::Options},
 };
 use indexmap::IndexMap;
-use sea_orm::{entity::*, query::*, ConnectionTrait, DbErr, Order};
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, ConnectionTrait, DbBackend, DbErr, FromQueryResult,
+    Order, Statement,
+};
 use std::collections::HashMap;
 
 pub fn paginate<T, C>(
@@ -60,7 +63,7 @@ pub async fn get_by_creator(
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
     let mut condition = Condition::all()
         .add(asset_creators::Column::Creator.eq(creator.clone()))
@@ -76,32 +79,43 @@ pub async fn get_by_creator(
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
+        options,
         Some(creator),
         None
     )
     .await
 }
 
+// Counts only verified members unless noted. `mint_slot` is set when the asset is first indexed
+// and is NULL for assets indexed before it existed, which MIN/MAX skip.
+const GROUPING_STATS_QUERY: &str = "
+SELECT
+    COUNT(*) FILTER (WHERE ag.verified) AS verified,
+    COUNT(*) FILTER (WHERE NOT ag.verified) AS unverified,
+    COUNT(*) FILTER (WHERE ag.verified AND a.compressed) AS compressed,
+    COUNT(*) FILTER (WHERE ag.verified AND NOT a.compressed) AS uncompressed,
+    COUNT(*) FILTER (WHERE ag.verified AND a.burnt) AS burnt,
+    COUNT(DISTINCT a.owner) FILTER (WHERE ag.verified AND NOT a.burnt) AS unique_holders,
+    MIN(a.mint_slot) FILTER (WHERE ag.verified) AS first_mint_slot,
+    MAX(a.mint_slot) FILTER (WHERE ag.verified) AS last_mint_slot
+FROM asset_grouping ag
+JOIN asset a ON a.id = ag.asset_id
+WHERE ag.group_key = $1 AND ag.group_value = $2
+";
+
 pub async fn get_grouping(
     conn: &impl ConnectionTrait,
     group_key: String,
     group_value: String,
-) -> Result<GroupingSize, DbErr> {
-    let size = asset_grouping::Entity::find()
-        .filter(
-            Condition::all()
-                .add(asset_grouping::Column::GroupKey.eq(group_key))
-                .add(asset_grouping::Column::GroupValue.eq(group_value))
-                .add(
-                    Condition::any()
-                        .add(asset_grouping::Column::Verified.eq(true))
-                        .add(asset_grouping::Column::Verified.is_null()),
-                ),
-        )
-        .count(conn)
-        .await?;
-    Ok(GroupingSize { size })
+) -> Result<GroupingStats, DbErr> {
+    let stats = GroupingStats::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        GROUPING_STATS_QUERY,
+        vec![group_key.into(), group_value.into()],
+    ))
+    .one(conn)
+    .await?;
+    Ok(stats.unwrap_or_default())
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -113,13 +127,13 @@ pub async fn get_by_grouping(
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
     let mut condition = asset_grouping::Column::GroupKey
         .eq(group_key)
         .and(asset_grouping::Column::GroupValue.eq(group_value));
 
-    if !show_unverified_collections {
+    if !options.show_unverified_collections {
         condition = condition.and(
             asset_grouping::Column::Verified
                 .eq(true)
@@ -137,7 +151,7 @@ pub async fn get_by_grouping(
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
+        options,
         None,
         None
     )
@@ -151,20 +165,41 @@ pub async fn get_assets_by_owner(
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let cond = Condition::all()
-        .add(asset::Column::Owner.eq(owner.clone()))
+    let holdings = || {
+        token_accounts::Entity::find()
+            .select_only()
+            .column(token_accounts::Column::Mint)
+            .filter(token_accounts::Column::Owner.eq(owner.clone()))
+    };
+    let mut owned = Condition::any().add(asset::Column::Owner.eq(owner.clone()));
+    if options.show_fungible {
+        // Fungible assets have no single owner, their holders are found through token accounts.
+        owned = owned.add(asset::Column::Id.in_subquery(holdings().into_query()));
+    }
+    let mut cond = Condition::all()
+        .add(owned)
         .add(asset::Column::Supply.gt(0));
+    if !options.show_zero_balance {
+        // Assets without token accounts, like compressed ones, are held whatever the balance.
+        let funded = holdings().filter(token_accounts::Column::Amount.ne(0));
+        cond = cond.add(
+            Condition::any()
+                .add(asset::Column::Id.not_in_subquery(holdings().into_query()))
+                .add(asset::Column::Id.in_subquery(funded.into_query())),
+        );
+    }
     get_assets_by_condition(
         conn,
         cond,
         vec![],
         sort_by,
         sort_direction,
+        false,
         pagination,
         limit,
-        show_unverified_collections,
+        options,
         Some(owner)
     )
     .await
@@ -186,9 +221,10 @@ pub async fn get_assets(
         // Default values provided. The args below are not used for batch requests
         None,
         Order::Asc,
+        false,
         pagination,
         limit,
-        false,
+        &Options::default(),
         None
     )
     .await
@@ -201,7 +237,7 @@ pub async fn get_by_authority(
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
     let cond = Condition::all()
         .add(asset_authority::Column::Authority.eq(authority))
@@ -214,7 +250,7 @@ pub async fn get_by_authority(
         sort_direction,
         pagination,
         limit,
-        show_unverified_collections,
+        options,
         None,
         None
     )
@@ -230,7 +266,7 @@ async fn get_by_related_condition<E>(
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
     required_creator: Option<Vec<u8>>,
     include_owner: Option<Vec<u8>>,
 ) -> Result<Vec<FullAsset>, DbErr>
@@ -250,13 +286,13 @@ where
     let assets = paginate(pagination, limit, stmt, sort_direction, asset::Column::Id)
         .all(conn)
         .await?;
-    get_related_for_assets(conn, assets, show_unverified_collections, required_creator, include_owner).await
+    get_related_for_assets(conn, assets, options, required_creator, include_owner).await
 }
 
 pub async fn get_related_for_assets(
     conn: &impl ConnectionTrait,
     assets: Vec<asset::Model>,
-    show_unverified_collections: bool,
+    options: &Options,
     required_creator: Option<Vec<u8>>,
     include_owner: Option<Vec<u8>>,
 ) -> Result<Vec<FullAsset>, DbErr> {
@@ -324,12 +360,15 @@ pub async fn get_related_for_assets(
     }
     if let Some(include_owner) = include_owner {
         // Get all creators for all assets in `assets_map``.
+        let mut token_account_condition = Condition::all()
+            .add(token_accounts::Column::Mint.is_in(ids.clone()))
+            .add(token_accounts::Column::Owner.eq(include_owner));
+        if !options.show_zero_balance {
+            token_account_condition =
+                token_account_condition.add(token_accounts::Column::Amount.ne(0));
+        }
         let token_accounts = token_accounts::Entity::find()
-            .filter(
-                Condition::all()
-                    .add(token_accounts::Column::Mint.is_in(ids.clone()))
-                    .add(token_accounts::Column::Owner.eq(include_owner)),
-            )
+            .filter(token_account_condition)
             .order_by_asc(token_accounts::Column::Mint)
             .all(conn)
             .await?;
@@ -367,7 +406,7 @@ pub async fn get_related_for_assets(
         }
     }
 
-    let cond = if show_unverified_collections {
+    let cond = if options.show_unverified_collections {
         Condition::all()
     } else {
         Condition::any()
@@ -400,9 +439,10 @@ pub async fn get_assets_by_condition(
     joins: Vec<RelationDef>,
     sort_by: Option<asset::Column>,
     sort_direction: Order,
+    order_by_name: bool,
     pagination: &Pagination,
     limit: u64,
-    show_unverified_collections: bool,
+    options: &Options,
     include_owner: Option<Vec<u8>>,
 
 ) -> Result<Vec<FullAsset>, DbErr> {
@@ -411,7 +451,15 @@ pub async fn get_assets_by_condition(
         stmt = stmt.join(JoinType::LeftJoin, def);
     }
     stmt = stmt.filter(condition);
-    if let Some(col) = sort_by {
+    // The name is only available when the caller joined `asset_data`.
+    if order_by_name {
+        QueryOrder::query(&mut stmt)
+            .order_by_expr(
+                Expr::cust("asset_data.chain_data->>'name'"),
+                sort_direction.clone(),
+            )
+            .order_by(asset::Column::Id, sort_direction.clone());
+    } else if let Some(col) = sort_by {
         stmt = stmt
             .order_by(col, sort_direction.clone())
             .order_by(asset::Column::Id, sort_direction.clone());
@@ -421,7 +469,7 @@ pub async fn get_assets_by_condition(
         .all(conn)
         .await?;
     let full_assets =
-        get_related_for_assets(conn, assets, show_unverified_collections, None, include_owner).await?;
+        get_related_for_assets(conn, assets, options, None, include_owner).await?;
     Ok(full_assets)
 }
 
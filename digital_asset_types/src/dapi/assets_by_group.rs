@@ -6,7 +6,10 @@ use crate::rpc::response::AssetList;
 use sea_orm::DatabaseConnection;
 use sea_orm::DbErr;
 
-use super::common::{build_asset_response, create_pagination, create_sorting};
+use super::{
+    apply_display_options,
+    common::{build_asset_response, create_pagination, create_sorting},
+};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn get_assets_by_group(
@@ -27,13 +30,10 @@ pub async fn get_assets_by_group(
         sort_direction,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
+        options,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let mut asset_list = build_asset_response(assets, page_options.limit, &pagination, options);
+    apply_display_options(db, &mut asset_list.items, options).await?;
+    Ok(asset_list)
 }
@@ -0,0 +1,124 @@
+use crate::{
+    dao::compressed_data,
+    dapi::{
+        common::{json_enum_params, json_pubkey},
+        get_assets,
+    },
+    rpc::{options::Options, Asset, CompressedData},
+};
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr};
+use std::collections::HashMap;
+
+/// Returns the character wrapping `asset_id` along with its source asset.
+pub async fn get_character_by_asset(
+    db: &DatabaseConnection,
+    asset_id: Vec<u8>,
+    options: &Options,
+) -> Result<Option<CompressedData>, DbErr> {
+    let asset_id = bs58::encode(asset_id).into_string();
+    let mut characters = characters_by_source(db, &[asset_id.clone()])
+        .await?
+        .remove(&asset_id)
+        .into_iter()
+        .collect::<Vec<_>>();
+    attach_source_assets(db, &mut characters, options).await?;
+    Ok(characters.pop())
+}
+
+/// Sets the wrapping character of the assets that have one.
+pub async fn attach_characters(db: &DatabaseConnection, assets: &mut [Asset]) -> Result<(), DbErr> {
+    let ids = assets
+        .iter()
+        .map(|asset| asset.id.clone())
+        .collect::<Vec<_>>();
+    let mut characters = characters_by_source(db, &ids).await?;
+    for asset in assets {
+        asset.character = characters.remove(&asset.id);
+    }
+    Ok(())
+}
+
+/// Sets the source asset of the characters when `showCharacter` is set.
+pub async fn apply_character_options(
+    db: &DatabaseConnection,
+    characters: &mut [CompressedData],
+    options: &Options,
+) -> Result<(), DbErr> {
+    if options.show_character {
+        attach_source_assets(db, characters, options).await?;
+    }
+    Ok(())
+}
+
+/// Sets the source asset of the characters wrapping an indexed NFT or cNFT.
+pub async fn attach_source_assets(
+    db: &DatabaseConnection,
+    characters: &mut [CompressedData],
+    options: &Options,
+) -> Result<(), DbErr> {
+    let ids = characters
+        .iter()
+        .filter_map(|character| source_mint(&character.parsed_data))
+        .filter_map(|mint| bs58::decode(mint).into_vec().ok())
+        .collect::<Vec<_>>();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let limit = ids.len() as u64;
+    // the source assets already hang off their character
+    let options = Options {
+        show_character: false,
+        ..options.clone()
+    };
+    let mut assets = get_assets(db, ids, limit, &options).await?;
+    for character in characters {
+        character.source_asset = source_mint(&character.parsed_data)
+            .and_then(|mint| assets.remove(&mint))
+            .map(Box::new);
+    }
+    Ok(())
+}
+
+/// Wrapped and assembled characters keep the mint of their source in `source.params.mint`.
+pub fn source_mint(parsed_data: &serde_json::Value) -> Option<String> {
+    json_pubkey(json_enum_params(parsed_data.get("source"))?.get("mint"))
+}
+
+async fn characters_by_source(
+    db: &DatabaseConnection,
+    asset_ids: &[String],
+) -> Result<HashMap<String, CompressedData>, DbErr> {
+    if asset_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    // matches the expression of `compressed_data_source_mint_idx`
+    let condition = asset_ids.iter().fold(Condition::any(), |condition, id| {
+        condition.add(Expr::cust_with_values(
+            "(compressed_data.parsed_data->'source'->'params'->>'mint') = $1",
+            vec![format!("pubkey:{}", id)],
+        ))
+    });
+    let models = compressed_data::Entity::find()
+        .filter(condition)
+        .all(db)
+        .await?;
+
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            Some((
+                source_mint(&model.parsed_data)?,
+                CompressedData {
+                    id: model.id,
+                    tree_id: bs58::encode(model.tree_id).into_string(),
+                    leaf_idx: model.leaf_idx,
+                    schema_validated: model.schema_validated,
+                    parsed_data: model.parsed_data,
+                    slot_updated: model.slot_updated,
+                    source_asset: None,
+                },
+            ))
+        })
+        .collect())
+}
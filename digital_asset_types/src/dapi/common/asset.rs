@@ -141,7 +141,8 @@ pub fn create_sorting(sorting: AssetSorting) -> (sea_orm::query::Order, Option<a
         AssetSortBy::Created => Some(asset::Column::CreatedAt),
         AssetSortBy::Updated => Some(asset::Column::SlotUpdated),
         AssetSortBy::RecentAction => Some(asset::Column::SlotUpdated),
-        AssetSortBy::None => None,
+        // The name lives in `asset_data`, `searchAssets` orders by it itself.
+        AssetSortBy::Name | AssetSortBy::None => None,
     };
     let sort_direction = match sorting.sort_direction.unwrap_or_default() {
         AssetSortDirection::Desc => sea_orm::query::Order::Desc,
@@ -352,6 +353,7 @@ pub fn to_grouping(
                 group_key: model.group_key.clone(),
                 group_value: Some(group_value),
                 verified,
+                collection_metadata: None,
             })
         })
         .collect();
@@ -405,6 +407,11 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
         }),
         _ => None,
     };
+    let (chain_data, raw_json) = if options.show_raw_data {
+        (Some(data.chain_data.clone()), Some(data.metadata.clone()))
+    } else {
+        (None, None)
+    };
     let token_info = match (token, token_account) {
         (Some(token), Some(token_account)) => Some(TokenInfo {
             decimals: token.decimals.to_u8(),
@@ -515,6 +522,9 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
         mpl_core_info,
         mint_extensions: mint_ext,
         token_info,
+        character: None,
+        chain_data,
+        raw_json,
     })
 }
 
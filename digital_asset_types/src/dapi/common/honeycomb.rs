@@ -0,0 +1,125 @@
+use crate::dao::{compressed_data, merkle_tree};
+use sea_orm::{
+    sea_query::SimpleExpr, ColumnTrait, EntityTrait, QueryFilter, QuerySelect, QueryTrait,
+};
+use serde_json::{json, Value};
+
+/// Honeycomb accounts are indexed with pubkeys stored as `pubkey:<base58>`.
+pub fn json_pubkey(value: Option<&Value>) -> Option<String> {
+    let value = value?.as_str()?;
+    Some(value.strip_prefix("pubkey:").unwrap_or(value).to_string())
+}
+
+pub fn json_pubkey_bytes(value: Option<&Value>) -> Option<Vec<u8>> {
+    bs58::decode(json_pubkey(value)?).into_vec().ok()
+}
+
+/// Returns the variant name of an indexed enum, whether it was stored as a plain string, as an
+/// object with a `__kind` tag or as a single key object.
+pub fn json_enum_kind(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(kind) => Some(kind.clone()),
+        Value::Object(object) => object
+            .get("__kind")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| match object.len() {
+                1 => object.keys().next().cloned(),
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+/// Returns the params of an indexed enum variant, stored next to its kind or as the value of a
+/// single key object.
+pub fn json_enum_params(value: Option<&Value>) -> Option<&Value> {
+    let object = value?.as_object()?;
+    object.get("params").or_else(|| match object.len() {
+        1 => object.values().next().filter(|params| params.is_object()),
+        _ => None,
+    })
+}
+
+/// Resolves a reward collected on a mission, `event_reward` being an entry of the character's
+/// `used_by` rewards and `mission_rewards` the rewards of the `Mission` account.
+pub fn calculate_reward(event_reward: &Value, mission_rewards: &[Value]) -> Option<Value> {
+    if !event_reward.get("collected")?.as_bool()? {
+        return None;
+    }
+    earned_reward(event_reward, mission_rewards)
+}
+
+/// Same as [`calculate_reward`] without requiring the reward to be collected yet.
+pub fn earned_reward(event_reward: &Value, mission_rewards: &[Value]) -> Option<Value> {
+    let delta = event_reward.get("delta")?.as_u64()?;
+    let reward_idx = event_reward.get("reward_idx")?.as_u64()? as usize;
+
+    let reward = mission_rewards.get(reward_idx)?.as_object()?;
+    let min = reward.get("min")?.as_u64()?;
+    let max = reward.get("max")?.as_u64()?;
+    let reward_type = reward.get("reward_type")?.clone();
+
+    Some(json!({
+        "reward": get_result_from_delta(min, max, delta)?,
+        "reward_type": reward_type
+    }))
+}
+
+/// `None` when the reward bounds are inverted or the reward overflows.
+const fn get_result_from_delta(min: u64, max: u64, delta: u64) -> Option<u64> {
+    let Some(range) = max.checked_sub(min) else {
+        return None;
+    };
+    let Some(scaled) = delta.checked_mul(range) else {
+        return None;
+    };
+    min.checked_add(scaled / 100)
+}
+
+/// Integers wider than 53 bits may be indexed as strings.
+pub fn json_i64(value: Option<&Value>) -> Option<i64> {
+    let value = value?;
+    value.as_i64().or_else(|| value.as_str()?.parse().ok())
+}
+
+pub fn json_u64(value: Option<&Value>) -> Option<u64> {
+    let value = value?;
+    value.as_u64().or_else(|| value.as_str()?.parse().ok())
+}
+
+/// Returns the trees listed in a `ControlledMerkleTrees`.
+pub fn json_controlled_trees(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|trees| trees.get("merkle_trees"))
+        .and_then(Value::as_array)
+        .map(|trees| {
+            trees
+                .iter()
+                .filter_map(|tree| json_pubkey(Some(tree)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns every tree an account lists in its `ControlledMerkleTrees`, whether under
+/// `merkle_trees`, `profile_trees` or the ledger `storage` of a resource.
+pub fn json_account_trees(data: &Value) -> Vec<String> {
+    let storage =
+        json_enum_params(data.get("storage")).and_then(|params| params.get("merkle_trees"));
+    [data.get("merkle_trees"), data.get("profile_trees"), storage]
+        .into_iter()
+        .flat_map(json_controlled_trees)
+        .collect()
+}
+
+/// Restricts compressed data to the leaves of the trees indexed under `project`.
+pub fn compressed_data_in_project(project: Vec<u8>) -> SimpleExpr {
+    compressed_data::Column::TreeId.in_subquery(
+        merkle_tree::Entity::find()
+            .select_only()
+            .column(merkle_tree::Column::Id)
+            .filter(merkle_tree::Column::Project.eq(project))
+            .into_query(),
+    )
+}
@@ -1,2 +1,4 @@
 mod asset;
+mod honeycomb;
 pub use asset::*;
+pub use honeycomb::*;
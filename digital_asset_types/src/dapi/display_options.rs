@@ -0,0 +1,85 @@
+use super::attach_characters;
+use crate::{
+    dao::asset_data,
+    rpc::{options::Options, Asset, CollectionMetadata},
+};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// Applies the display options that need more than the asset rows themselves.
+pub async fn apply_display_options(
+    db: &DatabaseConnection,
+    assets: &mut [Asset],
+    options: &Options,
+) -> Result<(), DbErr> {
+    if options.show_character {
+        attach_characters(db, assets).await?;
+    }
+    if options.show_collection_metadata {
+        attach_collection_metadata(db, assets).await?;
+    }
+    Ok(())
+}
+
+/// Inlines the name, image and JSON of the collection asset into each `collection` group.
+pub async fn attach_collection_metadata(
+    db: &DatabaseConnection,
+    assets: &mut [Asset],
+) -> Result<(), DbErr> {
+    let ids = assets
+        .iter()
+        .flat_map(|asset| asset.grouping.iter().flatten())
+        .filter(|group| group.group_key == "collection")
+        .filter_map(|group| group.group_value.as_deref())
+        .filter_map(|collection| bs58::decode(collection).into_vec().ok())
+        .collect::<BTreeSet<_>>();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let metadata = asset_data::Entity::find()
+        .filter(asset_data::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|data| {
+            (
+                bs58::encode(&data.id).into_string(),
+                collection_metadata(data),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    for group in assets
+        .iter_mut()
+        .flat_map(|asset| asset.grouping.iter_mut().flatten())
+        .filter(|group| group.group_key == "collection")
+    {
+        group.collection_metadata = group
+            .group_value
+            .as_ref()
+            .and_then(|collection| metadata.get(collection))
+            .cloned();
+    }
+    Ok(())
+}
+
+// The off-chain JSON is preferred, the on-chain name and symbol are used while it is not
+// fetched yet.
+fn collection_metadata(data: asset_data::Model) -> CollectionMetadata {
+    let string = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(|value| value.trim_matches(char::from(0)).to_string())
+            .filter(|value| !value.is_empty())
+    };
+    CollectionMetadata {
+        name: string(&data.metadata, "name").or_else(|| string(&data.chain_data, "name")),
+        symbol: string(&data.metadata, "symbol").or_else(|| string(&data.chain_data, "symbol")),
+        image: string(&data.metadata, "image"),
+        description: string(&data.metadata, "description"),
+        external_url: string(&data.metadata, "external_url"),
+        metadata: Some(data.metadata).filter(|metadata| metadata.is_object()),
+    }
+}
@@ -1,4 +1,7 @@
-use super::common::{asset_to_rpc, build_asset_response};
+use super::{
+    apply_display_options,
+    common::{asset_to_rpc, build_asset_response},
+};
 use crate::{
     dao::{scopes, Pagination},
     rpc::{options::Options, Asset},
@@ -12,7 +15,9 @@ pub async fn get_asset(
     options: &Options,
 ) -> Result<Asset, DbErr> {
     let asset = scopes::asset::get_by_id(db, id, false).await?;
-    asset_to_rpc(asset, options)
+    let mut asset = asset_to_rpc(asset, options)?;
+    apply_display_options(db, std::slice::from_mut(&mut asset), options).await?;
+    Ok(asset)
 }
 
 pub async fn get_assets(
@@ -23,7 +28,8 @@ pub async fn get_assets(
 ) -> Result<HashMap<String, Asset>, DbErr> {
     let pagination = Pagination::Page { page: 1 };
     let assets = scopes::asset::get_assets(db, ids, &pagination, limit).await?;
-    let asset_list = build_asset_response(assets, limit, &pagination, options);
+    let mut asset_list = build_asset_response(assets, limit, &pagination, options);
+    apply_display_options(db, &mut asset_list.items, options).await?;
     let asset_map = asset_list
         .items
         .into_iter()
@@ -1,4 +1,8 @@
-use crate::{dao::compressed_data, rpc::CompressedData};
+use super::{apply_character_options, common::compressed_data_in_project};
+use crate::{
+    dao::compressed_data,
+    rpc::{options::Options, CompressedData},
+};
 use sea_orm::{entity::*, query::*, DbErr};
 use sea_orm::{DatabaseConnection, DbBackend};
 
@@ -6,20 +10,24 @@ pub async fn get_characters(
     db: &DatabaseConnection,
     wallet: String,
     merkle_tree: Option<Vec<u8>>,
+    project: Option<Vec<u8>>,
+    options: &Options,
 ) -> Result<Vec<CompressedData>, DbErr> {
     let mut query_builder = compressed_data::Entity::find();
 
-    let merkle_tree_is_some = merkle_tree.is_some();
-    if merkle_tree_is_some {
-        query_builder =
-            query_builder.filter(compressed_data::Column::TreeId.eq(merkle_tree.unwrap()));
+    let filtered = merkle_tree.is_some() || project.is_some();
+    if let Some(merkle_tree) = merkle_tree {
+        query_builder = query_builder.filter(compressed_data::Column::TreeId.eq(merkle_tree));
+    }
+    if let Some(project) = project {
+        query_builder = query_builder.filter(compressed_data_in_project(project));
     }
 
     let mut query = query_builder.build(DbBackend::Postgres);
     query.sql = format!(
         "{} {} parsed_data->>'owner' = 'pubkey:{}'",
         query.sql,
-        if !merkle_tree_is_some { "WHERE" } else { "AND" },
+        if !filtered { "WHERE" } else { "AND" },
         wallet
     );
 
@@ -38,8 +46,10 @@ pub async fn get_characters(
             schema_validated: model.schema_validated,
             parsed_data: model.parsed_data,
             slot_updated: model.slot_updated,
+            source_asset: None,
         })
     }
+    apply_character_options(db, &mut characters, options).await?;
 
     Ok(characters)
 }
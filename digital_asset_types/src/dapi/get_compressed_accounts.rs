@@ -8,20 +8,22 @@ use sea_orm::{DatabaseConnection, DbBackend};
 pub async fn get_compressed_accounts(
     db: &DatabaseConnection,
     discriminator: Vec<u8>,
+    project: Option<Vec<u8>>,
 ) -> Result<Vec<CompressedData>, DbErr> {
     // let discriminator_trees_query = format!(
     //     "SELECT id from merkle_tree WHERE encode(discriminator, 'base64') = '{}'",
     //     "kcvQ4LSL08LVfZ12sfjlGZjXLQR5h9dVRPQ2XhwbnAA="
     // );
 
+    let mut trees = merkle_tree::Entity::find()
+        .select_only()
+        .column(merkle_tree::Column::Id)
+        .filter(merkle_tree::Column::Discriminator.eq(discriminator));
+    if let Some(project) = project {
+        trees = trees.filter(merkle_tree::Column::Project.eq(project));
+    }
     let query = compressed_data::Entity::find()
-        .filter(
-            compressed_data::Column::TreeId.in_subquery(
-                merkle_tree::Entity::find()
-                    .filter(merkle_tree::Column::Discriminator.eq(discriminator))
-                    .into_query(),
-            ),
-        )
+        .filter(compressed_data::Column::TreeId.in_subquery(trees.into_query()))
         .build(DbBackend::Postgres);
 
     let models = compressed_data::Entity::find()
@@ -39,6 +41,7 @@ pub async fn get_compressed_accounts(
             schema_validated: model.schema_validated,
             parsed_data: model.parsed_data,
             slot_updated: model.slot_updated,
+            source_asset: None,
         })
     }
 
@@ -1,4 +1,8 @@
-use crate::{dao::compressed_data, rpc::CompressedData};
+use super::apply_character_options;
+use crate::{
+    dao::compressed_data,
+    rpc::{options::Options, CompressedData},
+};
 use sea_orm::DatabaseConnection;
 use sea_orm::{entity::*, query::*, DbErr};
 
@@ -6,6 +10,7 @@ pub async fn get_compressed_data(
     db: &DatabaseConnection,
     tree_id: Vec<u8>,
     leaf_idx: u32,
+    options: &Options,
 ) -> Result<CompressedData, DbErr> {
     // let sel = compressed_data::Entity::find()
     //     .filter(Expr::cust_with_values(
@@ -38,12 +43,15 @@ pub async fn get_compressed_data(
 
     let db_data = found.unwrap();
 
-    Ok(CompressedData {
+    let mut data = CompressedData {
         id: db_data.id,
         tree_id: bs58::encode(db_data.tree_id).into_string(),
         leaf_idx: db_data.leaf_idx,
         schema_validated: db_data.schema_validated,
         parsed_data: db_data.parsed_data,
         slot_updated: db_data.slot_updated,
-    })
+        source_asset: None,
+    };
+    apply_character_options(db, std::slice::from_mut(&mut data), options).await?;
+    Ok(data)
 }
@@ -0,0 +1,61 @@
+use crate::{
+    dao::compressed_data_changelog,
+    rpc::{
+        filter::AssetSortDirection, response::CompressedDataSignatureList, CompressedDataSignature,
+    },
+};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+
+/// Lists the changelog entries of a leaf, newest first unless `sort_direction` is `asc`. The
+/// cursor is the id of the last entry returned, base58 encoded big endian in the response.
+pub async fn get_compressed_data_signatures(
+    db: &DatabaseConnection,
+    tree: Vec<u8>,
+    leaf_idx: i64,
+    limit: u64,
+    cursor: Option<i64>,
+    sort_direction: Option<AssetSortDirection>,
+) -> Result<CompressedDataSignatureList, DbErr> {
+    let sort_direction = sort_direction.unwrap_or(AssetSortDirection::Desc);
+    let mut query = compressed_data_changelog::Entity::find()
+        .filter(compressed_data_changelog::Column::TreeId.eq(tree))
+        .filter(compressed_data_changelog::Column::LeafIdx.eq(leaf_idx));
+    if let Some(cursor) = cursor {
+        query = match sort_direction {
+            AssetSortDirection::Asc => {
+                query.filter(compressed_data_changelog::Column::Id.gt(cursor))
+            }
+            AssetSortDirection::Desc => {
+                query.filter(compressed_data_changelog::Column::Id.lt(cursor))
+            }
+        };
+    }
+    let query = match sort_direction {
+        AssetSortDirection::Asc => query.order_by_asc(compressed_data_changelog::Column::Id),
+        AssetSortDirection::Desc => query.order_by_desc(compressed_data_changelog::Column::Id),
+    };
+    let entries = query.limit(limit).all(db).await?;
+
+    let cursor = entries
+        .last()
+        .map(|entry| bs58::encode((entry.id as i64).to_be_bytes()).into_string());
+    let items = entries
+        .into_iter()
+        .map(|entry| CompressedDataSignature {
+            signature: entry
+                .signature
+                .map(|signature| bs58::encode(signature).into_string()),
+            event_type: entry.event_type,
+            key: entry.key,
+            seq: entry.seq,
+            slot: entry.slot,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(CompressedDataSignatureList {
+        total: items.len() as u32,
+        limit: limit as u32,
+        cursor,
+        items,
+    })
+}
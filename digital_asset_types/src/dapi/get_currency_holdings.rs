@@ -0,0 +1,113 @@
+use crate::{
+    dao::{accounts, token_accounts, tokens},
+    dapi::common::{json_enum_kind, json_pubkey, json_pubkey_bytes},
+    rpc::{ui_amount_string, CurrencyHolding},
+};
+use blockbuster::programs::hpl_currency_manager::{Currency, HolderAccount};
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr};
+use std::collections::HashMap;
+
+pub async fn get_currency_holdings(
+    db: &DatabaseConnection,
+    owner: String,
+    project: Option<String>,
+) -> Result<Vec<CurrencyHolding>, DbErr> {
+    // the discriminator is inlined, not bound, so the partial owner index on holder accounts
+    // matches the query plan
+    let discriminator = HolderAccount::DISCRIMINATOR
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let holders = accounts::Entity::find()
+        .filter(Expr::cust(&format!(
+            "accounts.discriminator = '\\x{discriminator}'::bytea"
+        )))
+        .filter(Expr::cust_with_values(
+            "accounts.parsed_data->>'owner' = $1",
+            vec![format!("pubkey:{}", owner)],
+        ))
+        .all(db)
+        .await?;
+    if holders.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let currency_ids = holders
+        .iter()
+        .filter_map(|holder| json_pubkey_bytes(holder.parsed_data.get("currency")))
+        .collect::<Vec<_>>();
+    let currencies = accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(currency_ids))
+        .filter(accounts::Column::Discriminator.eq(Currency::DISCRIMINATOR.to_vec()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|currency| (bs58::encode(&currency.id).into_string(), currency))
+        .collect::<HashMap<_, _>>();
+
+    let token_account_ids = holders
+        .iter()
+        .filter_map(|holder| json_pubkey_bytes(holder.parsed_data.get("token_account")))
+        .collect::<Vec<_>>();
+    let token_accounts = token_accounts::Entity::find()
+        .filter(token_accounts::Column::Pubkey.is_in(token_account_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|account| (bs58::encode(&account.pubkey).into_string(), account))
+        .collect::<HashMap<_, _>>();
+
+    let mints = token_accounts
+        .values()
+        .map(|account| account.mint.clone())
+        .collect::<Vec<_>>();
+    let decimals = tokens::Entity::find()
+        .filter(tokens::Column::Mint.is_in(mints))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|token| Some((token.mint, u8::try_from(token.decimals).ok()?)))
+        .collect::<HashMap<_, _>>();
+
+    let mut holdings = Vec::with_capacity(holders.len());
+    for holder in holders {
+        let data = &holder.parsed_data;
+        let currency_address = json_pubkey(data.get("currency")).unwrap_or_default();
+        let currency = currencies
+            .get(&currency_address)
+            .map(|currency| &currency.parsed_data);
+        let currency_project = currency.and_then(|currency| json_pubkey(currency.get("project")));
+        if project.is_some() && currency_project != project {
+            continue;
+        }
+
+        let token_account_address = json_pubkey(data.get("token_account")).unwrap_or_default();
+        let token_account = token_accounts.get(&token_account_address);
+        let amount = token_account.map(|account| u64::try_from(account.amount).unwrap_or_default());
+        let token_decimals = token_account.and_then(|account| decimals.get(&account.mint).copied());
+        let status = json_enum_kind(data.get("status"));
+
+        holdings.push(CurrencyHolding {
+            holder_account: bs58::encode(&holder.id).into_string(),
+            currency: currency_address,
+            owner: owner.clone(),
+            project: currency_project,
+            mint: currency.and_then(|currency| json_pubkey(currency.get("mint"))),
+            kind: currency.and_then(|currency| currency.get("kind").cloned()),
+            tx_hook: currency.and_then(|currency| currency.get("tx_hook").cloned()),
+            token_account: token_account_address,
+            amount,
+            decimals: token_decimals,
+            ui_amount_string: amount
+                .zip(token_decimals)
+                .map(|(amount, decimals)| ui_amount_string(amount, decimals)),
+            inactive: status.as_deref() == Some("Inactive"),
+            status,
+            created_at: data
+                .get("created_at")
+                .and_then(|value| value.as_i64().or_else(|| value.as_str()?.parse().ok())),
+            slot_updated: holder.slot_updated,
+        });
+    }
+    Ok(holdings)
+}
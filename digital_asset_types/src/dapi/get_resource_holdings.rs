@@ -0,0 +1,243 @@
+use crate::{
+    dao::{accounts, compressed_data},
+    dapi::common::{
+        json_controlled_trees, json_enum_kind, json_enum_params, json_i64, json_pubkey, json_u64,
+    },
+    rpc::{
+        ui_amount_string, CraftableRecipe, RecipeIngredient, ResourceFaucet, ResourceHolding,
+        ResourceHoldings, ResourceLeaf,
+    },
+};
+use blockbuster::programs::hpl_resource_manager::{Faucet, Recipe, Resource};
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+pub async fn get_resource_holdings(
+    db: &DatabaseConnection,
+    wallet: String,
+    project: Option<String>,
+) -> Result<ResourceHoldings, DbErr> {
+    let mut resources = accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(Resource::DISCRIMINATOR.to_vec()));
+    if let Some(project) = &project {
+        resources = resources.filter(project_condition(std::slice::from_ref(project)));
+    }
+    let resources = resources.all(db).await?;
+
+    // only ledger-state resources keep their balances in compressed leaves
+    let resource_by_tree = resources
+        .iter()
+        .flat_map(|resource| {
+            ledger_trees(&resource.parsed_data)
+                .into_iter()
+                .map(move |tree| (tree, bs58::encode(&resource.id).into_string()))
+        })
+        .collect::<HashMap<_, _>>();
+    let tree_ids = resource_by_tree
+        .keys()
+        .filter_map(|tree| bs58::decode(tree).into_vec().ok())
+        .collect::<Vec<_>>();
+
+    let leaves = if tree_ids.is_empty() {
+        Vec::new()
+    } else {
+        compressed_data::Entity::find()
+            .filter(compressed_data::Column::TreeId.is_in(tree_ids))
+            .filter(
+                Condition::any()
+                    .add(Expr::cust_with_values(
+                        "compressed_data.parsed_data->>'holder' = $1",
+                        vec![format!("pubkey:{}", wallet)],
+                    ))
+                    .add(Expr::cust_with_values(
+                        "compressed_data.parsed_data->>'owner' = $1",
+                        vec![format!("pubkey:{}", wallet)],
+                    )),
+            )
+            .order_by_asc(compressed_data::Column::TreeId)
+            .order_by_asc(compressed_data::Column::LeafIdx)
+            .all(db)
+            .await?
+    };
+
+    let mut leaves_by_resource: HashMap<String, Vec<ResourceLeaf>> = HashMap::new();
+    for leaf in leaves {
+        let tree_id = bs58::encode(&leaf.tree_id).into_string();
+        let Some(resource) = resource_by_tree.get(&tree_id) else {
+            continue;
+        };
+        let data = &leaf.parsed_data;
+        leaves_by_resource
+            .entry(resource.clone())
+            .or_default()
+            .push(ResourceLeaf {
+                tree_id,
+                leaf_idx: leaf.leaf_idx,
+                balance: json_u64(data.get("balance")).or_else(|| json_u64(data.get("amount"))),
+                characteristics: data.get("characteristics").cloned(),
+                slot_updated: leaf.slot_updated,
+            });
+    }
+
+    let mut holdings = Vec::with_capacity(leaves_by_resource.len());
+    for resource in &resources {
+        let id = bs58::encode(&resource.id).into_string();
+        let Some(leaves) = leaves_by_resource.remove(&id) else {
+            continue;
+        };
+        let data = &resource.parsed_data;
+        let kind = data.get("kind");
+        let decimals = json_enum_params(kind)
+            .and_then(|params| params.get("decimals"))
+            .and_then(Value::as_u64)
+            .and_then(|decimals| u8::try_from(decimals).ok());
+        let balance = leaves
+            .iter()
+            .try_fold(0u64, |total, leaf| {
+                Some(total.saturating_add(leaf.balance?))
+            })
+            .filter(|_| is_fungible(kind));
+
+        holdings.push(ResourceHolding {
+            resource: id,
+            project: json_pubkey(data.get("project")),
+            mint: json_pubkey(data.get("mint")),
+            kind: json_enum_kind(kind),
+            decimals,
+            balance,
+            ui_amount_string: balance
+                .zip(decimals)
+                .map(|(balance, decimals)| ui_amount_string(balance, decimals)),
+            leaves,
+        });
+    }
+
+    // recipes and faucets of the requested project, or of every project the wallet holds
+    // resources of
+    let projects = match project {
+        Some(project) => vec![project],
+        None => holdings
+            .iter()
+            .filter_map(|holding| holding.project.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+    };
+    if projects.is_empty() {
+        return Ok(ResourceHoldings {
+            holdings,
+            recipes: Vec::new(),
+            faucets: Vec::new(),
+        });
+    }
+
+    let held = holdings
+        .iter()
+        .map(|holding| {
+            let amount = holding.balance.unwrap_or(holding.leaves.len() as u64);
+            (holding.resource.clone(), amount)
+        })
+        .collect::<HashMap<_, _>>();
+    let recipes = accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(Recipe::DISCRIMINATOR.to_vec()))
+        .filter(project_condition(&projects))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|recipe| recipe_to_rpc(recipe, &held))
+        .collect();
+
+    let faucets = accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(Faucet::DISCRIMINATOR.to_vec()))
+        .filter(project_condition(&projects))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|faucet| {
+            let data = &faucet.parsed_data;
+            let repeat_interval = json_i64(data.get("repeat_interval"));
+            let last_claimed = json_i64(data.get("last_claimed"));
+            ResourceFaucet {
+                faucet: bs58::encode(&faucet.id).into_string(),
+                project: json_pubkey(data.get("project")),
+                resource: json_pubkey(data.get("resource")),
+                amount: json_u64(data.get("amount")),
+                repeat_interval,
+                last_claimed,
+                next_claim_at: last_claimed
+                    .zip(repeat_interval)
+                    .map(|(last_claimed, interval)| last_claimed.saturating_add(interval)),
+            }
+        })
+        .collect();
+
+    Ok(ResourceHoldings {
+        holdings,
+        recipes,
+        faucets,
+    })
+}
+
+/// Returns the trees listed in the `ControlledMerkleTrees` of a `LedgerState` resource.
+pub fn ledger_trees(resource: &Value) -> Vec<String> {
+    let storage = resource.get("storage");
+    if json_enum_kind(storage).as_deref() != Some("LedgerState") {
+        return Vec::new();
+    }
+    json_controlled_trees(json_enum_params(storage).and_then(|params| params.get("merkle_trees")))
+}
+
+fn is_fungible(kind: Option<&Value>) -> bool {
+    matches!(
+        json_enum_kind(kind).as_deref(),
+        Some("HplFungible" | "WrappedFungible")
+    )
+}
+
+fn recipe_to_rpc(recipe: accounts::Model, held: &HashMap<String, u64>) -> CraftableRecipe {
+    let data = &recipe.parsed_data;
+    let ingredients = data
+        .get("ingredients")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|ingredient| {
+            let resource = json_pubkey(ingredient.get("resource_address"))?;
+            Some(RecipeIngredient {
+                held: held.get(&resource).copied().unwrap_or_default(),
+                amount: json_u64(ingredient.get("amount"))?,
+                resource,
+            })
+        })
+        .collect::<Vec<_>>();
+    let craftable = ingredients
+        .iter()
+        .filter(|ingredient| ingredient.amount > 0)
+        .map(|ingredient| ingredient.held / ingredient.amount)
+        .min()
+        .unwrap_or_default();
+    let meal = data.get("meal");
+
+    CraftableRecipe {
+        recipe: bs58::encode(&recipe.id).into_string(),
+        project: json_pubkey(data.get("project")),
+        xp: json_u64(data.get("xp")),
+        meal_resource: meal.and_then(|meal| json_pubkey(meal.get("resource_address"))),
+        meal_amount: meal.and_then(|meal| json_u64(meal.get("amount"))),
+        ingredients,
+        craftable,
+    }
+}
+
+fn project_condition(projects: &[String]) -> Condition {
+    projects
+        .iter()
+        .fold(Condition::any(), |condition, project| {
+            condition.add(Expr::cust_with_values(
+                "accounts.parsed_data->>'project' = $1",
+                vec![format!("pubkey:{}", project)],
+            ))
+        })
+}
@@ -0,0 +1,308 @@
+use crate::{
+    dao::{accounts, compressed_data},
+    dapi::common::{
+        json_controlled_trees, json_enum_kind, json_enum_params, json_pubkey, json_u64,
+    },
+    rpc::{response::ProfileList, DelegateAuthority, Profile, Project, ServiceDelegation},
+};
+use blockbuster::programs::hpl_hive_control as hive_control;
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub async fn get_project(db: &DatabaseConnection, id: Vec<u8>) -> Result<Project, DbErr> {
+    accounts::Entity::find_by_id(id)
+        .filter(accounts::Column::Discriminator.eq(hive_control::Project::DISCRIMINATOR.to_vec()))
+        .one(db)
+        .await?
+        .map(project_to_rpc)
+        .ok_or(DbErr::RecordNotFound("Project Not Found".to_string()))
+}
+
+pub async fn get_projects(
+    db: &DatabaseConnection,
+    authority: Option<String>,
+    driver: Option<String>,
+) -> Result<Vec<Project>, DbErr> {
+    let mut query = accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(hive_control::Project::DISCRIMINATOR.to_vec()));
+    if let Some(authority) = authority {
+        query = query.filter(Expr::cust_with_values(
+            "accounts.parsed_data->>'authority' = $1",
+            vec![format!("pubkey:{}", authority)],
+        ));
+    }
+    if let Some(driver) = driver {
+        query = query.filter(Expr::cust_with_values(
+            "accounts.parsed_data->>'driver' = $1",
+            vec![format!("pubkey:{}", driver)],
+        ));
+    }
+    Ok(query
+        .order_by_asc(accounts::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(project_to_rpc)
+        .collect())
+}
+
+/// Lists the profiles stored in the project's `profile_trees`, only those of the user owning
+/// `wallet` when it is set.
+pub async fn get_profiles(
+    db: &DatabaseConnection,
+    project: Vec<u8>,
+    wallet: Option<String>,
+    limit: u64,
+    cursor: Option<Vec<u8>>,
+) -> Result<ProfileList, DbErr> {
+    let project = get_project(db, project).await?;
+    let tree_ids = project
+        .profile_trees
+        .iter()
+        .filter_map(|tree| bs58::decode(tree).into_vec().ok())
+        .collect::<Vec<_>>();
+    let config = &project.profile_data_config;
+    let achievement_names = json_strings(config.get("achievements"));
+    let custom_data_fields = json_strings(config.get("custom_data_fields"));
+
+    let empty = ProfileList {
+        limit: limit as u32,
+        ..Default::default()
+    };
+    if tree_ids.is_empty() {
+        return Ok(empty);
+    }
+
+    let mut query =
+        compressed_data::Entity::find().filter(compressed_data::Column::TreeId.is_in(tree_ids));
+    if let Some(wallet) = wallet {
+        let user_ids = get_user_ids(db, &wallet).await?;
+        if user_ids.is_empty() {
+            return Ok(empty);
+        }
+        let condition = user_ids
+            .iter()
+            .fold(Condition::any(), |condition, user_id| {
+                condition.add(Expr::cust_with_values(
+                    "compressed_data.parsed_data->>'user_id' = $1",
+                    vec![user_id.to_string()],
+                ))
+            });
+        query = query.filter(condition);
+    }
+    if let Some(cursor) = cursor {
+        query = query.filter(compressed_data::Column::Id.gt(cursor));
+    }
+    let models = query
+        .order_by_asc(compressed_data::Column::Id)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    let cursor = models
+        .last()
+        .map(|model| bs58::encode(&model.id).into_string());
+    let items = models
+        .into_iter()
+        .map(|model| {
+            let data = &model.parsed_data;
+            let platform_data = data.get("platform_data");
+            let achievements = platform_data
+                .and_then(|platform_data| platform_data.get("achievements"))
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|achievement| {
+                    let index = usize::try_from(achievement.as_u64()?).ok()?;
+                    Some(
+                        achievement_names
+                            .get(index)
+                            .cloned()
+                            .unwrap_or_else(|| index.to_string()),
+                    )
+                })
+                .collect();
+
+            Profile {
+                id: bs58::encode(&model.id).into_string(),
+                tree_id: bs58::encode(&model.tree_id).into_string(),
+                leaf_idx: model.leaf_idx,
+                project: project.address.clone(),
+                user_id: json_u64(data.get("user_id")),
+                identity: data.get("identity").cloned(),
+                info: data.get("info").cloned(),
+                xp: json_u64(platform_data.and_then(|platform_data| platform_data.get("xp"))),
+                achievements,
+                custom_data: named_custom_data(data.get("custom_data"), &custom_data_fields),
+                slot_updated: model.slot_updated,
+                parsed_data: model.parsed_data,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ProfileList {
+        total: items.len() as u32,
+        limit: limit as u32,
+        cursor,
+        items,
+    })
+}
+
+pub async fn get_delegate_authorities(
+    db: &DatabaseConnection,
+    project: String,
+) -> Result<Vec<DelegateAuthority>, DbErr> {
+    Ok(accounts::Entity::find()
+        .filter(
+            accounts::Column::Discriminator
+                .eq(hive_control::DelegateAuthority::DISCRIMINATOR.to_vec()),
+        )
+        .filter(Expr::cust_with_values(
+            "accounts.parsed_data->>'project' = $1",
+            vec![format!("pubkey:{}", project)],
+        ))
+        .order_by_asc(accounts::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|account| {
+            let data = &account.parsed_data;
+            let delegations = data
+                .get("delegations")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|delegation| {
+                    let service = json_enum_kind(Some(delegation))?;
+                    let params = json_enum_params(Some(delegation));
+                    Some(ServiceDelegation {
+                        service,
+                        index: params
+                            .and_then(|params| params.get("index"))
+                            .and_then(Value::as_u64)
+                            .and_then(|index| u8::try_from(index).ok()),
+                        permission: json_enum_kind(
+                            params.and_then(|params| params.get("permission")),
+                        ),
+                    })
+                })
+                .collect();
+
+            DelegateAuthority {
+                address: bs58::encode(&account.id).into_string(),
+                project: json_pubkey(data.get("project")),
+                authority: json_pubkey(data.get("authority")),
+                delegations,
+                slot_updated: account.slot_updated,
+            }
+        })
+        .collect())
+}
+
+fn project_to_rpc(account: accounts::Model) -> Project {
+    let data = &account.parsed_data;
+    Project {
+        address: bs58::encode(&account.id).into_string(),
+        key: json_pubkey(data.get("key")),
+        authority: json_pubkey(data.get("authority")),
+        driver: json_pubkey(data.get("driver")),
+        name: data.get("name").and_then(Value::as_str).map(str::to_string),
+        services: data.get("services").cloned().unwrap_or_default(),
+        associated_programs: data.get("associated_programs").cloned().unwrap_or_default(),
+        profile_data_config: data.get("profile_data_config").cloned().unwrap_or_default(),
+        profile_trees: json_controlled_trees(data.get("profile_trees")),
+        badge_criteria: data
+            .get("badge_criteria")
+            .filter(|criteria| !criteria.is_null())
+            .cloned(),
+        subsidize_fees: data
+            .get("subsidize_fees")
+            .and_then(Value::as_bool)
+            .unwrap_or_default(),
+        slot_updated: account.slot_updated,
+    }
+}
+
+/// Returns the ids of the users, stored in the `user_trees` of the Hive Control `Global`
+/// account, that own `wallet`.
+async fn get_user_ids(db: &DatabaseConnection, wallet: &str) -> Result<Vec<u64>, DbErr> {
+    let tree_ids = accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(hive_control::Global::DISCRIMINATOR.to_vec()))
+        .all(db)
+        .await?
+        .iter()
+        .flat_map(|global| json_controlled_trees(global.parsed_data.get("user_trees")))
+        .filter_map(|tree| bs58::decode(tree).into_vec().ok())
+        .collect::<Vec<_>>();
+    if tree_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let wallet = format!("pubkey:{}", wallet);
+    Ok(compressed_data::Entity::find()
+        .filter(compressed_data::Column::TreeId.is_in(tree_ids))
+        .filter(
+            Condition::any()
+                .add(Expr::cust_with_values(
+                    "compressed_data.parsed_data->'wallets'->'wallets' @> jsonb_build_array($1::text)",
+                    vec![wallet.clone()],
+                ))
+                .add(Expr::cust_with_values(
+                    "compressed_data.parsed_data->'wallets'->>'shadow' = $1",
+                    vec![wallet],
+                )),
+        )
+        .all(db)
+        .await?
+        .iter()
+        .filter_map(|user| json_u64(user.parsed_data.get("id")))
+        .collect())
+}
+
+fn json_strings(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Custom data is keyed by field name or by its index in `custom_data_fields`, and is stored
+/// either as an object or as a list of key/value pairs.
+fn named_custom_data(value: Option<&Value>, fields: &[String]) -> BTreeMap<String, Value> {
+    let entries: Vec<(String, Value)> = match value {
+        Some(Value::Object(object)) => object
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        Some(Value::Array(pairs)) => pairs
+            .iter()
+            .filter_map(|pair| match pair.as_array()?.as_slice() {
+                [Value::String(key), value] => Some((key.clone(), value.clone())),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .map(|(key, value)| {
+            let name = key
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| fields.get(index).cloned())
+                .unwrap_or(key);
+            (name, value)
+        })
+        .collect()
+}
@@ -3,12 +3,21 @@ mod assets_by_creator;
 mod assets_by_group;
 mod assets_by_owner;
 mod change_logs;
+mod character_assets;
+mod display_options;
 mod get_asset;
 mod get_asset_signatures;
 mod get_characters;
 mod get_compressed_accounts;
 mod get_compressed_data;
+mod get_compressed_data_signatures;
+mod get_currency_holdings;
+mod get_resource_holdings;
+mod hive_control;
+mod nectar_positions;
+mod notifications;
 mod search_assets;
+mod token_accounts;
 
 pub mod common;
 
@@ -17,9 +26,18 @@ pub use assets_by_creator::*;
 pub use assets_by_group::*;
 pub use assets_by_owner::*;
 pub use change_logs::*;
+pub use character_assets::*;
+pub use display_options::*;
 pub use get_asset::*;
 pub use get_asset_signatures::*;
 pub use get_characters::*;
 pub use get_compressed_accounts::*;
 pub use get_compressed_data::*;
+pub use get_compressed_data_signatures::*;
+pub use get_currency_holdings::*;
+pub use get_resource_holdings::*;
+pub use hive_control::*;
+pub use nectar_positions::*;
+pub use notifications::*;
 pub use search_assets::*;
+pub use token_accounts::*;
@@ -0,0 +1,278 @@
+use crate::{
+    dao::{accounts, character_history, compressed_data},
+    dapi::common::{
+        compressed_data_in_project, earned_reward, json_enum_kind, json_enum_params, json_i64,
+        json_pubkey, json_u64,
+    },
+    rpc::{MissionParticipation, MissionReward, StakingPosition},
+};
+use blockbuster::programs::{
+    hpl_nectar_missions::{Mission, MissionPool},
+    hpl_nectar_staking::{Multipliers, Staker, StakingPool},
+};
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub async fn get_staking_positions(
+    db: &DatabaseConnection,
+    wallet: String,
+    project: Option<Vec<u8>>,
+) -> Result<Vec<StakingPosition>, DbErr> {
+    let characters = characters_used_by(db, &wallet, "Staking", project).await?;
+    if characters.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let pool_addresses = characters
+        .iter()
+        .filter_map(|(_, params)| json_pubkey(params.get("pool")))
+        .collect::<Vec<_>>();
+    let pools = accounts_by_id(db, &pool_addresses, StakingPool::DISCRIMINATOR).await?;
+    let multipliers = accounts_by_field(
+        db,
+        Multipliers::DISCRIMINATOR,
+        "staking_pool",
+        &pool_addresses,
+    )
+    .await?;
+    let stakers = accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(Staker::DISCRIMINATOR.to_vec()))
+        .filter(Expr::cust_with_values(
+            "accounts.parsed_data->>'wallet' = $1",
+            vec![format!("pubkey:{}", wallet)],
+        ))
+        .all(db)
+        .await?;
+    let entered = entered_slots(db, &characters, "Staked").await?;
+
+    let mut positions = Vec::with_capacity(characters.len());
+    for (character, params) in characters {
+        let staking_pool = json_pubkey(params.get("pool")).unwrap_or_default();
+        let pool = pools.get(&staking_pool).map(|pool| &pool.parsed_data);
+        // prefer the staker referenced by the character, a wallet has one staker per pool
+        let staker = json_pubkey(params.get("staker"))
+            .and_then(|staker| {
+                stakers
+                    .iter()
+                    .find(|account| bs58::encode(&account.id).into_string() == staker)
+            })
+            .or_else(|| {
+                stakers.iter().find(|account| {
+                    json_pubkey(account.parsed_data.get("staking_pool")).as_ref()
+                        == Some(&staking_pool)
+                })
+            });
+
+        positions.push(StakingPosition {
+            character: bs58::encode(&character.id).into_string(),
+            tree_id: bs58::encode(&character.tree_id).into_string(),
+            leaf_idx: character.leaf_idx,
+            wallet: wallet.clone(),
+            staker: staker
+                .map(|staker| bs58::encode(&staker.id).into_string())
+                .or_else(|| json_pubkey(params.get("staker"))),
+            staked_at: json_i64(params.get("staked_at")),
+            claimed_at: json_i64(params.get("claimed_at")),
+            entered_slot: entered.get(&character.id).copied(),
+            lock_type: pool.and_then(|pool| json_enum_kind(pool.get("lock_type"))),
+            pool: pool.cloned(),
+            multipliers: multipliers
+                .get(&staking_pool)
+                .map(|multipliers| multipliers.parsed_data.clone()),
+            total_staked: staker
+                .and_then(|staker| json_u64(staker.parsed_data.get("total_staked"))),
+            staking_pool,
+        });
+    }
+    Ok(positions)
+}
+
+pub async fn get_mission_participations(
+    db: &DatabaseConnection,
+    wallet: String,
+    project: Option<Vec<u8>>,
+    now: i64,
+) -> Result<Vec<MissionParticipation>, DbErr> {
+    let characters = characters_used_by(db, &wallet, "Mission", project).await?;
+    if characters.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mission_addresses = characters
+        .iter()
+        .filter_map(|(_, params)| mission_address(params))
+        .collect::<Vec<_>>();
+    let missions = accounts_by_id(db, &mission_addresses, Mission::DISCRIMINATOR).await?;
+    let pool_addresses = missions
+        .values()
+        .filter_map(|mission| json_pubkey(mission.parsed_data.get("mission_pool")))
+        .collect::<Vec<_>>();
+    let pools = accounts_by_id(db, &pool_addresses, MissionPool::DISCRIMINATOR).await?;
+    let entered = entered_slots(db, &characters, "MissionParticipation").await?;
+
+    let mut participations = Vec::with_capacity(characters.len());
+    for (character, params) in characters {
+        let mission_address = mission_address(&params).unwrap_or_default();
+        let mission = missions
+            .get(&mission_address)
+            .map(|mission| &mission.parsed_data);
+        let mission_pool = mission.and_then(|mission| json_pubkey(mission.get("mission_pool")));
+        let end_time = json_i64(params.get("end_time"));
+        let ended = end_time.is_some_and(|end_time| end_time <= now);
+        let mission_rewards = mission
+            .and_then(|mission| mission.get("rewards"))
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let rewards = params
+            .get("rewards")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|reward| {
+                let collected = reward.get("collected")?.as_bool()?;
+                let earned = earned_reward(reward, mission_rewards);
+                Some(MissionReward {
+                    reward_idx: reward.get("reward_idx")?.as_u64()?,
+                    delta: reward.get("delta")?.as_u64()?,
+                    collected,
+                    claimable: ended && !collected,
+                    amount: earned
+                        .as_ref()
+                        .and_then(|earned| earned.get("reward")?.as_u64()),
+                    reward_type: earned.and_then(|earned| earned.get("reward_type").cloned()),
+                })
+            })
+            .collect();
+
+        participations.push(MissionParticipation {
+            character: bs58::encode(&character.id).into_string(),
+            tree_id: bs58::encode(&character.tree_id).into_string(),
+            leaf_idx: character.leaf_idx,
+            wallet: wallet.clone(),
+            mission: mission_address,
+            mission_pool_config: mission_pool
+                .as_ref()
+                .and_then(|pool| pools.get(pool))
+                .map(|pool| pool.parsed_data.clone()),
+            mission_pool,
+            end_time,
+            entered_slot: entered.get(&character.id).copied(),
+            mission_config: mission.cloned(),
+            rewards,
+        });
+    }
+    Ok(participations)
+}
+
+// The transformer records the mission as `mission_id` in the character history, the character
+// manager stores it as `id`.
+fn mission_address(params: &Value) -> Option<String> {
+    json_pubkey(params.get("mission_id")).or_else(|| json_pubkey(params.get("id")))
+}
+
+/// Returns the wallet's characters whose `used_by` is of `kind`, along with its params, only
+/// those stored in the trees of `project` when it is set.
+async fn characters_used_by(
+    db: &DatabaseConnection,
+    wallet: &str,
+    kind: &str,
+    project: Option<Vec<u8>>,
+) -> Result<Vec<(compressed_data::Model, Value)>, DbErr> {
+    let mut query = compressed_data::Entity::find().filter(Expr::cust_with_values(
+        "compressed_data.parsed_data->>'owner' = $1",
+        vec![format!("pubkey:{}", wallet)],
+    ));
+    if let Some(project) = project {
+        query = query.filter(compressed_data_in_project(project));
+    }
+    let characters = query.all(db).await?;
+
+    Ok(characters
+        .into_iter()
+        .filter_map(|character| {
+            let used_by = character.parsed_data.get("used_by");
+            if json_enum_kind(used_by).as_deref() != Some(kind) {
+                return None;
+            }
+            let params = json_enum_params(used_by).cloned().unwrap_or_default();
+            Some((character, params))
+        })
+        .collect())
+}
+
+async fn accounts_by_id(
+    db: &DatabaseConnection,
+    addresses: &[String],
+    discriminator: [u8; 8],
+) -> Result<HashMap<String, accounts::Model>, DbErr> {
+    let ids = addresses
+        .iter()
+        .filter_map(|address| bs58::decode(address).into_vec().ok())
+        .collect::<Vec<_>>();
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(ids))
+        .filter(accounts::Column::Discriminator.eq(discriminator.to_vec()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|account| (bs58::encode(&account.id).into_string(), account))
+        .collect())
+}
+
+/// Finds the accounts whose pubkey `field` references one of `addresses`, keyed by that field.
+async fn accounts_by_field(
+    db: &DatabaseConnection,
+    discriminator: [u8; 8],
+    field: &str,
+    addresses: &[String],
+) -> Result<HashMap<String, accounts::Model>, DbErr> {
+    if addresses.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let condition = addresses
+        .iter()
+        .fold(Condition::any(), |condition, address| {
+            condition.add(Expr::cust_with_values(
+                &format!("accounts.parsed_data->>'{}' = $1", field),
+                vec![format!("pubkey:{}", address)],
+            ))
+        });
+    Ok(accounts::Entity::find()
+        .filter(accounts::Column::Discriminator.eq(discriminator.to_vec()))
+        .filter(condition)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|account| Some((json_pubkey(account.parsed_data.get(field))?, account)))
+        .collect())
+}
+
+/// Slot of the latest `event` recorded for each character.
+async fn entered_slots(
+    db: &DatabaseConnection,
+    characters: &[(compressed_data::Model, Value)],
+    event: &str,
+) -> Result<HashMap<Vec<u8>, i64>, DbErr> {
+    let ids = characters
+        .iter()
+        .map(|(character, _)| character.id.clone())
+        .collect::<Vec<_>>();
+    let history = character_history::Entity::find()
+        .filter(character_history::Column::CharacterId.is_in(ids))
+        .filter(character_history::Column::Event.eq(event))
+        .order_by_asc(character_history::Column::SlotUpdated)
+        .all(db)
+        .await?;
+    // later slots overwrite earlier ones
+    Ok(history
+        .into_iter()
+        .map(|history| (history.character_id, history.slot_updated))
+        .collect())
+}
@@ -0,0 +1,89 @@
+use crate::{
+    dao::{character_history, compressed_data, merkle_tree},
+    rpc::{
+        notification::{ChangeOperation, CharacterEventNotification, CompressedDataNotification},
+        CompressedData,
+    },
+};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+
+fn strip_pubkey_prefix(value: &str) -> String {
+    value.strip_prefix("pubkey:").unwrap_or(value).to_string()
+}
+
+fn owner_of(parsed_data: &serde_json::Value) -> Option<String> {
+    parsed_data
+        .get("owner")
+        .and_then(serde_json::Value::as_str)
+        .map(strip_pubkey_prefix)
+}
+
+pub async fn get_compressed_data_notification(
+    db: &DatabaseConnection,
+    operation: ChangeOperation,
+    id: Vec<u8>,
+    tree_id: Vec<u8>,
+    leaf_idx: i64,
+    owner: Option<String>,
+) -> Result<CompressedDataNotification, DbErr> {
+    let program = merkle_tree::Entity::find_by_id(tree_id.clone())
+        .one(db)
+        .await?
+        .and_then(|tree| tree.program)
+        .map(|program| bs58::encode(program).into_string());
+
+    let data = match operation {
+        ChangeOperation::Delete => None,
+        _ => compressed_data::Entity::find()
+            .filter(compressed_data::Column::Id.eq(id))
+            .one(db)
+            .await?
+            .map(|model| CompressedData {
+                id: model.id,
+                tree_id: bs58::encode(model.tree_id).into_string(),
+                leaf_idx: model.leaf_idx,
+                schema_validated: model.schema_validated,
+                parsed_data: model.parsed_data,
+                slot_updated: model.slot_updated,
+                source_asset: None,
+            }),
+    };
+
+    Ok(CompressedDataNotification {
+        operation,
+        tree_id: bs58::encode(tree_id).into_string(),
+        leaf_idx,
+        program,
+        owner: owner.as_deref().map(strip_pubkey_prefix),
+        data,
+    })
+}
+
+pub async fn get_character_event_notification(
+    db: &DatabaseConnection,
+    id: i64,
+) -> Result<CharacterEventNotification, DbErr> {
+    let history = character_history::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("character_history Not Found".to_string()))?;
+
+    let character = compressed_data::Entity::find()
+        .filter(compressed_data::Column::Id.eq(history.character_id.clone()))
+        .one(db)
+        .await?;
+
+    Ok(CharacterEventNotification {
+        id: history.id,
+        character_id: bs58::encode(history.character_id).into_string(),
+        event: history.event,
+        event_data: history.event_data,
+        slot_updated: history.slot_updated,
+        tree_id: character
+            .as_ref()
+            .map(|character| bs58::encode(&character.tree_id).into_string()),
+        owner: character
+            .as_ref()
+            .and_then(|character| owner_of(&character.parsed_data)),
+    })
+}
@@ -1,7 +1,14 @@
-use super::common::{build_asset_response, create_pagination, create_sorting};
+use super::{
+    apply_display_options,
+    common::{build_asset_response, create_pagination, create_sorting},
+};
 use crate::{
-    dao::{scopes, PageOptions, SearchAssetsQuery},
-    rpc::{filter::AssetSorting, options::Options, response::AssetList},
+    dao::{asset_data_join, scopes, PageOptions, Pagination, SearchAssetsQuery},
+    rpc::{
+        filter::{AssetSortBy, AssetSorting},
+        options::Options,
+        response::AssetList,
+    },
 };
 use sea_orm::{DatabaseConnection, DbErr};
 
@@ -13,24 +20,41 @@ pub async fn search_assets(
     options: &Options,
 ) -> Result<AssetList, DbErr> {
     let pagination = create_pagination(page_options)?;
+    let order_by_name = sorting.sort_by == AssetSortBy::Name;
+    // Cursors and keysets are asset ids, they can't resume a listing ordered by name.
+    if order_by_name
+        && !matches!(
+            pagination,
+            Pagination::Page { .. }
+                | Pagination::Keyset {
+                    before: None,
+                    after: None
+                }
+        )
+    {
+        return Err(DbErr::Custom(
+            "Sorting by name requires page pagination".to_string(),
+        ));
+    }
     let (sort_direction, sort_column) = create_sorting(sorting);
-    let (condition, joins) = search_assets_query.conditions()?;
+    let (condition, mut joins) = search_assets_query.conditions()?;
+    if order_by_name && !search_assets_query.joins_asset_data() {
+        joins.push(asset_data_join());
+    }
     let assets = scopes::asset::get_assets_by_condition(
         db,
         condition,
         joins,
         sort_column,
         sort_direction,
+        order_by_name,
         &pagination,
         page_options.limit,
-        options.show_unverified_collections,
-        search_assets_query.owner_address
+        options,
+        search_assets_query.owner_address,
     )
     .await?;
-    Ok(build_asset_response(
-        assets,
-        page_options.limit,
-        &pagination,
-        options,
-    ))
+    let mut asset_list = build_asset_response(assets, page_options.limit, &pagination, options);
+    apply_display_options(db, &mut asset_list.items, options).await?;
+    Ok(asset_list)
 }
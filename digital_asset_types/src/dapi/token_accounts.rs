@@ -0,0 +1,146 @@
+use crate::{
+    dao::token_accounts,
+    rpc::{
+        response::{TokenAccountList, TokenBalanceList},
+        ui_amount_string_u128, TokenAccount, TokenBalance,
+    },
+};
+use sea_orm::{
+    entity::*, query::*, DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement,
+};
+
+#[derive(FromQueryResult, Debug, Clone, PartialEq, Eq)]
+struct TokenBalanceRow {
+    mint: Vec<u8>,
+    /// A `NUMERIC` sum as text, it doesn't fit `BIGINT`.
+    amount: String,
+    token_accounts: i64,
+    decimals: Option<i32>,
+    token_program: Vec<u8>,
+}
+
+fn encode(bytes: &[u8]) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+fn to_u64(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or_default()
+}
+
+/// Token amounts are `u64` written to a `BIGINT` as is, read them back the same way.
+const fn unsigned_amount(value: i64) -> u64 {
+    u64::from_ne_bytes(value.to_ne_bytes())
+}
+
+// Amounts are `u64` stored in a `BIGINT`, the ones above `i64::MAX` read back negative.
+const UNSIGNED_AMOUNT: &str =
+    "CASE WHEN ta.amount < 0 THEN ta.amount::NUMERIC + 18446744073709551616 ELSE ta.amount END";
+
+pub async fn get_token_accounts(
+    db: &DatabaseConnection,
+    owner: Option<Vec<u8>>,
+    mint: Option<Vec<u8>>,
+    limit: u64,
+    cursor: Option<Vec<u8>>,
+) -> Result<TokenAccountList, DbErr> {
+    let mut query = token_accounts::Entity::find();
+    if let Some(owner) = owner {
+        query = query.filter(token_accounts::Column::Owner.eq(owner));
+    }
+    if let Some(mint) = mint {
+        query = query.filter(token_accounts::Column::Mint.eq(mint));
+    }
+    if let Some(cursor) = cursor {
+        query = query.filter(token_accounts::Column::Pubkey.gt(cursor));
+    }
+    let models = query
+        .order_by_asc(token_accounts::Column::Pubkey)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    let cursor = models.last().map(|model| encode(&model.pubkey));
+    let items = models
+        .into_iter()
+        .map(|model| TokenAccount {
+            address: encode(&model.pubkey),
+            mint: encode(&model.mint),
+            owner: encode(&model.owner),
+            amount: unsigned_amount(model.amount),
+            delegate: model.delegate.as_deref().map(encode),
+            delegated_amount: unsigned_amount(model.delegated_amount),
+            frozen: model.frozen,
+            close_authority: model.close_authority.as_deref().map(encode),
+            token_program: encode(&model.token_program),
+            slot_updated: model.slot_updated,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(TokenAccountList {
+        total: items.len() as u32,
+        limit: limit as u32,
+        cursor,
+        items,
+    })
+}
+
+pub async fn get_token_balances(
+    db: &DatabaseConnection,
+    owner: Vec<u8>,
+    limit: u64,
+    cursor: Option<Vec<u8>>,
+    show_zero_balance: bool,
+) -> Result<TokenBalanceList, DbErr> {
+    let mut values = vec![owner.into()];
+    let mut sql = format!(
+        "SELECT ta.mint, SUM({UNSIGNED_AMOUNT})::TEXT AS amount, COUNT(*) AS token_accounts, \
+         t.decimals, ta.token_program \
+         FROM token_accounts ta LEFT JOIN tokens t ON t.mint = ta.mint \
+         WHERE ta.owner = $1"
+    );
+    if let Some(cursor) = cursor {
+        values.push(cursor.into());
+        sql.push_str(" AND ta.mint > $2");
+    }
+    sql.push_str(" GROUP BY ta.mint, t.decimals, ta.token_program");
+    if !show_zero_balance {
+        sql.push_str(" HAVING bool_or(ta.amount <> 0)");
+    }
+    sql.push_str(&format!(" ORDER BY ta.mint LIMIT {limit}"));
+
+    let rows = TokenBalanceRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &sql,
+        values,
+    ))
+    .all(db)
+    .await?;
+
+    let cursor = rows.last().map(|row| encode(&row.mint));
+    let items = rows
+        .into_iter()
+        .map(|row| {
+            let amount = row.amount.parse::<u128>().unwrap_or_default();
+            let decimals = row
+                .decimals
+                .and_then(|decimals| u8::try_from(decimals).ok());
+            TokenBalance {
+                mint: encode(&row.mint),
+                amount,
+                decimals,
+                ui_amount: decimals
+                    .map(|decimals| amount as f64 / 10_f64.powi(i32::from(decimals))),
+                ui_amount_string: decimals.map(|decimals| ui_amount_string_u128(amount, decimals)),
+                token_accounts: to_u64(row.token_accounts),
+                token_program: encode(&row.token_program),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(TokenBalanceList {
+        total: items.len() as u32,
+        limit: limit as u32,
+        cursor,
+        items,
+    })
+}
@@ -5,6 +5,7 @@ use crate::dao::sea_orm_active_enums::{
 use std::collections::BTreeMap;
 
 use crate::dao::sea_orm_active_enums::ChainMutability;
+use crate::rpc::CompressedData;
 use schemars::JsonSchema;
 use serde_json::Value;
 use {
@@ -221,6 +222,25 @@ pub struct Group {
     pub group_value: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verified: Option<bool>,
+    /// The collection asset's metadata, set with the `showCollectionMetadata` option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection_metadata: Option<CollectionMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct CollectionMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
@@ -422,4 +442,13 @@ pub struct Asset {
     pub mint_extensions: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_info: Option<TokenInfo>,
+    /// The character wrapping this asset, set with the `showCharacter` option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub character: Option<CompressedData>,
+    /// The indexed on-chain data, set with the `showRawData` option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_data: Option<Value>,
+    /// The off-chain JSON as fetched from `json_uri`, set with the `showRawData` option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_json: Option<Value>,
 }
@@ -1,3 +1,4 @@
+use super::Asset;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,4 +10,7 @@ pub struct CompressedData {
     pub schema_validated: bool,
     pub parsed_data: serde_json::Value,
     pub slot_updated: i64,
+    /// The NFT or cNFT wrapped by a character.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_asset: Option<Box<Asset>>,
 }
@@ -1,5 +1,6 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -27,6 +28,9 @@ pub enum AssetSortBy {
     Updated,
     #[serde(rename = "recent_action")]
     RecentAction,
+    /// Sorts by on-chain name, only accepted by `searchAssets`.
+    #[serde(rename = "name")]
+    Name,
     #[serde(rename = "none")]
     None,
 }
@@ -47,3 +51,10 @@ pub enum SearchConditionType {
     #[serde(rename = "any")]
     Any,
 }
+
+/// Matches assets whose off-chain JSON `attributes` hold a `trait_type` with `value`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AttributeFilter {
+    pub trait_type: String,
+    pub value: Value,
+}
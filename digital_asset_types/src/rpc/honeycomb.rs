@@ -0,0 +1,201 @@
+use {
+    schemars::JsonSchema,
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::collections::BTreeMap,
+};
+
+/// A wallet's `HolderAccount` joined with its `Currency` and the balance of its token account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurrencyHolding {
+    pub holder_account: String,
+    pub currency: String,
+    pub owner: String,
+    /// `None` while the currency account hasn't been indexed yet.
+    pub project: Option<String>,
+    pub mint: Option<String>,
+    pub kind: Option<Value>,
+    pub tx_hook: Option<Value>,
+    pub token_account: String,
+    /// `None` while the token account hasn't been indexed yet.
+    pub amount: Option<u64>,
+    pub decimals: Option<u8>,
+    pub ui_amount_string: Option<String>,
+    pub status: Option<String>,
+    pub inactive: bool,
+    pub created_at: Option<i64>,
+    pub slot_updated: i64,
+}
+
+/// A character staked in a Nectar `StakingPool`, joined with the pool configuration and the
+/// wallet's `Staker` account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingPosition {
+    pub character: String,
+    pub tree_id: String,
+    pub leaf_idx: i64,
+    pub wallet: String,
+    pub staking_pool: String,
+    pub staker: Option<String>,
+    pub staked_at: Option<i64>,
+    pub claimed_at: Option<i64>,
+    /// Slot of the `Staked` character history event.
+    pub entered_slot: Option<i64>,
+    /// `None` while the pool account hasn't been indexed yet.
+    pub pool: Option<Value>,
+    pub lock_type: Option<String>,
+    pub multipliers: Option<Value>,
+    pub total_staked: Option<u64>,
+}
+
+/// A character sent on a Nectar `Mission`, joined with the mission and its pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MissionParticipation {
+    pub character: String,
+    pub tree_id: String,
+    pub leaf_idx: i64,
+    pub wallet: String,
+    pub mission: String,
+    pub mission_pool: Option<String>,
+    pub end_time: Option<i64>,
+    /// Slot of the `MissionParticipation` character history event.
+    pub entered_slot: Option<i64>,
+    /// `None` while the mission account hasn't been indexed yet.
+    pub mission_config: Option<Value>,
+    pub mission_pool_config: Option<Value>,
+    pub rewards: Vec<MissionReward>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MissionReward {
+    pub reward_idx: u64,
+    pub delta: u64,
+    pub collected: bool,
+    /// Set once the mission has ended and the reward is not collected yet.
+    pub claimable: bool,
+    pub amount: Option<u64>,
+    pub reward_type: Option<Value>,
+}
+
+/// A wallet's leaves in the ledger-state trees of an HPL `Resource`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResourceHolding {
+    pub resource: String,
+    pub project: Option<String>,
+    pub mint: Option<String>,
+    pub kind: Option<String>,
+    pub decimals: Option<u8>,
+    /// Sum of the fungible leaves, `None` for non-fungible resources.
+    pub balance: Option<u64>,
+    pub ui_amount_string: Option<String>,
+    pub leaves: Vec<ResourceLeaf>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResourceLeaf {
+    pub tree_id: String,
+    pub leaf_idx: i64,
+    pub balance: Option<u64>,
+    pub characteristics: Option<Value>,
+    pub slot_updated: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecipeIngredient {
+    pub resource: String,
+    pub amount: u64,
+    pub held: u64,
+}
+
+/// An HPL `Recipe` with how many times the wallet can craft it from its holdings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CraftableRecipe {
+    pub recipe: String,
+    pub project: Option<String>,
+    pub xp: Option<u64>,
+    pub ingredients: Vec<RecipeIngredient>,
+    pub meal_resource: Option<String>,
+    pub meal_amount: Option<u64>,
+    pub craftable: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResourceFaucet {
+    pub faucet: String,
+    pub project: Option<String>,
+    pub resource: Option<String>,
+    pub amount: Option<u64>,
+    pub repeat_interval: Option<i64>,
+    pub last_claimed: Option<i64>,
+    pub next_claim_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResourceHoldings {
+    pub holdings: Vec<ResourceHolding>,
+    pub recipes: Vec<CraftableRecipe>,
+    pub faucets: Vec<ResourceFaucet>,
+}
+
+/// An HPL Hive Control `Project`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Project {
+    pub address: String,
+    pub key: Option<String>,
+    pub authority: Option<String>,
+    pub driver: Option<String>,
+    pub name: Option<String>,
+    pub services: Value,
+    pub associated_programs: Value,
+    pub profile_data_config: Value,
+    pub profile_trees: Vec<String>,
+    pub badge_criteria: Option<Value>,
+    pub subsidize_fees: bool,
+    pub slot_updated: i64,
+}
+
+/// A profile leaf of a project's `profile_trees`, with its achievements and custom data fields
+/// named after the project's `ProfileDataConfig`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Profile {
+    pub id: String,
+    pub tree_id: String,
+    pub leaf_idx: i64,
+    pub project: String,
+    pub user_id: Option<u64>,
+    pub identity: Option<Value>,
+    pub info: Option<Value>,
+    pub xp: Option<u64>,
+    pub achievements: Vec<String>,
+    pub custom_data: BTreeMap<String, Value>,
+    pub parsed_data: Value,
+    pub slot_updated: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ServiceDelegation {
+    pub service: String,
+    pub index: Option<u8>,
+    pub permission: Option<String>,
+}
+
+/// An HPL Hive Control `DelegateAuthority`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegateAuthority {
+    pub address: String,
+    pub project: Option<String>,
+    pub authority: Option<String>,
+    pub delegations: Vec<ServiceDelegation>,
+    pub slot_updated: i64,
+}
+
+/// A `compressed_data_changelog` entry of a leaf with the signature of the transaction that
+/// wrote it. `event_type` is `Full`, `Patch` (with the patched `key`) or `Empty`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CompressedDataSignature {
+    pub signature: Option<String>,
+    pub event_type: String,
+    pub key: Option<String>,
+    pub seq: i64,
+    pub slot: i64,
+}
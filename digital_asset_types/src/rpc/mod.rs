@@ -1,9 +1,15 @@
 mod asset;
 mod compressed_data;
+mod honeycomb;
+mod token;
 
 pub mod filter;
+pub mod notification;
 pub mod options;
+pub mod request;
 pub mod response;
 
 pub use asset::*;
 pub use compressed_data::*;
+pub use honeycomb::*;
+pub use token::*;
@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use {
+    crate::rpc::{Asset, CompressedData},
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Pushed to `subscribeCompressedData` subscribers. `data` is `None` once the leaf is emptied.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CompressedDataNotification {
+    pub operation: ChangeOperation,
+    pub tree_id: String,
+    pub leaf_idx: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    pub data: Option<CompressedData>,
+}
+
+/// Pushed to `subscribeCharacterEvents` subscribers for every new `character_history` row.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CharacterEventNotification {
+    pub id: i64,
+    pub character_id: String,
+    pub event: String,
+    pub event_data: serde_json::Value,
+    pub slot_updated: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+/// Pushed to `subscribeAssetChanges` subscribers with the asset as `getAsset` renders it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetNotification {
+    pub asset: Asset,
+}
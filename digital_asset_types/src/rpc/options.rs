@@ -1,9 +1,40 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Options {
     #[serde(default)]
     pub show_unverified_collections: bool,
+    /// Links assets and the characters wrapping them: assets get their `character` and
+    /// characters their `source_asset`.
+    #[serde(default)]
+    pub show_character: bool,
+    /// Also returns the fungible assets held through token accounts.
+    #[serde(default)]
+    pub show_fungible: bool,
+    /// Keeps the assets whose token accounts are all empty, along with those token accounts.
+    #[serde(default = "default_show_zero_balance")]
+    pub show_zero_balance: bool,
+    #[serde(default)]
+    pub show_collection_metadata: bool,
+    #[serde(default)]
+    pub show_raw_data: bool,
+}
+
+const fn default_show_zero_balance() -> bool {
+    true
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            show_unverified_collections: false,
+            show_character: false,
+            show_fungible: false,
+            show_zero_balance: default_show_zero_balance(),
+            show_collection_metadata: false,
+            show_raw_data: false,
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use schemars::JsonSchema;
 use {
-    crate::rpc::Asset,
+    crate::rpc::{Asset, CompressedDataSignature, Profile, TokenAccount, TokenBalance},
     serde::{Deserialize, Serialize},
 };
 
@@ -16,7 +16,26 @@ pub struct AssetError {
 pub struct GetGroupingResponse {
     pub group_key: String,
     pub group_name: String,
+    /// Verified members, same as `verified`.
     pub group_size: u64,
+    pub compressed: u64,
+    pub uncompressed: u64,
+    pub burnt: u64,
+    pub unique_holders: u64,
+    pub verified: u64,
+    pub unverified: u64,
+    /// Mint slot of the earliest minted verified member. Members indexed before mint slots
+    /// were recorded are left out until reindexed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_mint_slot: Option<u64>,
+    /// Mint slot of the latest minted verified member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_mint_slot: Option<u64>,
+    /// Only set for MPL Core collections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpl_core_num_minted: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpl_core_current_size: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
@@ -50,3 +69,43 @@ pub struct TransactionSignatureList {
     pub after: Option<String>,
     pub items: Vec<(String, String)>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TokenAccountList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<TokenAccount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TokenBalanceList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<TokenBalance>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct ProfileList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<Profile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct CompressedDataSignatureList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub items: Vec<CompressedDataSignature>,
+}
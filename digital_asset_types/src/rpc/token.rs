@@ -0,0 +1,53 @@
+use {
+    schemars::JsonSchema,
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TokenAccount {
+    pub address: String,
+    pub mint: String,
+    pub owner: String,
+    pub amount: u64,
+    pub delegate: Option<String>,
+    pub delegated_amount: u64,
+    pub frozen: bool,
+    pub close_authority: Option<String>,
+    pub token_program: String,
+    pub slot_updated: i64,
+}
+
+/// The balance of one mint summed over all token accounts of an owner.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenBalance {
+    pub mint: String,
+    /// The sum of many `u64` amounts can exceed `u64`.
+    pub amount: u128,
+    /// `None` while the mint itself hasn't been indexed yet.
+    pub decimals: Option<u8>,
+    pub ui_amount: Option<f64>,
+    pub ui_amount_string: Option<String>,
+    pub token_accounts: u64,
+    pub token_program: String,
+}
+
+/// Formats a raw token amount like Solana's `uiAmountString`, without trailing zeros.
+pub fn ui_amount_string(amount: u64, decimals: u8) -> String {
+    ui_amount_string_u128(u128::from(amount), decimals)
+}
+
+/// [`ui_amount_string`] for amounts summed over several token accounts.
+pub fn ui_amount_string_u128(amount: u128, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
@@ -0,0 +1,18 @@
+use digital_asset_types::dapi::source_mint;
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn source_mints() {
+    let mint = Keypair::new().pubkey();
+    assert_eq!(
+        source_mint(&json!({
+            "source": {
+                "__kind": "Wrapped",
+                "params": { "mint": format!("pubkey:{mint}"), "is_compressed": true },
+            },
+        })),
+        Some(mint.to_string())
+    );
+    assert_eq!(source_mint(&json!({ "owner": "pubkey:1" })), None);
+}
@@ -0,0 +1,68 @@
+use digital_asset_types::{
+    dao::compressed_data_changelog, dapi::get_compressed_data_signatures,
+    rpc::filter::AssetSortDirection,
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase};
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+fn entry(
+    id: u64,
+    key: Option<&str>,
+    event_type: &str,
+    signature: Option<Vec<u8>>,
+) -> compressed_data_changelog::Model {
+    compressed_data_changelog::Model {
+        id,
+        tree_id: vec![1; 32],
+        leaf_idx: 0,
+        key: key.map(str::to_string),
+        data: json!(null),
+        seq: id as i64,
+        slot: 100 + id as i64,
+        created_at: None,
+        signature,
+        event_type: event_type.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn get_compressed_data_signatures_lists_events() -> Result<(), DbErr> {
+    let tree = Keypair::new().pubkey();
+    let signature = vec![7; 64];
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![
+            entry(3, None, "Empty", Some(signature.clone())),
+            entry(2, Some("used_by"), "Patch", Some(signature.clone())),
+            entry(1, None, "Full", None),
+        ]])
+        .into_connection();
+
+    let signatures = get_compressed_data_signatures(
+        &db,
+        tree.to_bytes().to_vec(),
+        0,
+        10,
+        None,
+        Some(AssetSortDirection::Desc),
+    )
+    .await?;
+
+    assert_eq!(signatures.total, 3);
+    assert_eq!(
+        signatures.cursor,
+        Some(bs58::encode(1i64.to_be_bytes()).into_string())
+    );
+    let items = &signatures.items;
+    assert_eq!(items[0].event_type, "Empty");
+    assert_eq!(
+        items[0].signature,
+        Some(bs58::encode(&signature).into_string())
+    );
+    assert_eq!(items[1].event_type, "Patch");
+    assert_eq!(items[1].key.as_deref(), Some("used_by"));
+    assert_eq!(items[2].signature, None);
+    assert_eq!(items[2].slot, 101);
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use digital_asset_types::{
+    dao::{accounts, token_accounts, tokens},
+    dapi::{common::json_enum_kind, get_currency_holdings},
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase};
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn enum_kinds() {
+    assert_eq!(
+        json_enum_kind(Some(&json!("Active"))),
+        Some("Active".to_string())
+    );
+    assert_eq!(
+        json_enum_kind(Some(&json!({ "__kind": "Inactive" }))),
+        Some("Inactive".to_string())
+    );
+    assert_eq!(
+        json_enum_kind(Some(
+            &json!({ "Staking": { "pool": "pubkey:11111111111111111111111111111111" } })
+        )),
+        Some("Staking".to_string())
+    );
+    assert_eq!(json_enum_kind(Some(&json!(1))), None);
+    assert_eq!(json_enum_kind(None), None);
+}
+
+#[tokio::test]
+async fn get_currency_holdings_joins_balances() -> Result<(), DbErr> {
+    let owner = Keypair::new().pubkey();
+    let project = Keypair::new().pubkey();
+    let holder = Keypair::new().pubkey();
+    let currency = Keypair::new().pubkey();
+    let mint = Keypair::new().pubkey();
+    let token_account = Keypair::new().pubkey();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![accounts::Model {
+            id: holder.to_bytes().to_vec(),
+            program_id: vec![],
+            discriminator: vec![],
+            parsed_data: json!({
+                "currency": format!("pubkey:{currency}"),
+                "owner": format!("pubkey:{owner}"),
+                "token_account": format!("pubkey:{token_account}"),
+                "status": "Inactive",
+                "created_at": 1_700_000_000,
+            }),
+            created_at: None,
+            slot_updated: 5,
+            project: None,
+        }]])
+        .append_query_results(vec![vec![accounts::Model {
+            id: currency.to_bytes().to_vec(),
+            program_id: vec![],
+            discriminator: vec![],
+            parsed_data: json!({
+                "project": format!("pubkey:{project}"),
+                "mint": format!("pubkey:{mint}"),
+                "kind": "Wrapped",
+                "tx_hook": "User",
+            }),
+            created_at: None,
+            slot_updated: 4,
+            project: None,
+        }]])
+        .append_query_results(vec![vec![token_accounts::Model {
+            pubkey: token_account.to_bytes().to_vec(),
+            mint: mint.to_bytes().to_vec(),
+            amount: 2_500_000,
+            owner: owner.to_bytes().to_vec(),
+            frozen: false,
+            close_authority: None,
+            delegate: None,
+            delegated_amount: 0,
+            slot_updated: 6,
+            token_program: vec![],
+        }]])
+        .append_query_results(vec![vec![tokens::Model {
+            mint: mint.to_bytes().to_vec(),
+            supply: 10_000_000,
+            decimals: 6,
+            token_program: vec![],
+            mint_authority: None,
+            freeze_authority: None,
+            close_authority: None,
+            extension_data: None,
+            slot_updated: 3,
+            extensions: None,
+        }]])
+        .into_connection();
+
+    let holdings = get_currency_holdings(&db, owner.to_string(), None).await?;
+
+    assert_eq!(holdings.len(), 1);
+    let holding = &holdings[0];
+    assert_eq!(holding.holder_account, holder.to_string());
+    assert_eq!(holding.currency, currency.to_string());
+    assert_eq!(holding.project, Some(project.to_string()));
+    assert_eq!(holding.mint, Some(mint.to_string()));
+    assert_eq!(holding.token_account, token_account.to_string());
+    assert_eq!(holding.amount, Some(2_500_000));
+    assert_eq!(holding.decimals, Some(6));
+    assert_eq!(holding.ui_amount_string.as_deref(), Some("2.5"));
+    assert_eq!(holding.status.as_deref(), Some("Inactive"));
+    assert!(holding.inactive);
+    assert_eq!(holding.created_at, Some(1_700_000_000));
+
+    // same predicate as the partial `accounts_holder_account_owner_idx` index
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("accounts.discriminator = '\\\\xa45f46f891eea9b0'::bytea"));
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use digital_asset_types::{
+    dao::{
+        asset, asset_authority, asset_creators, asset_data, asset_grouping,
+        scopes::asset::get_assets_by_owner,
+        sea_orm_active_enums::{ChainMutability, Mutability},
+        token_accounts, tokens, Pagination,
+    },
+    dapi::attach_collection_metadata,
+    rpc::{options::Options, Asset},
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase, Order};
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn attach_collection_metadata_inlines_collection_json() -> Result<(), DbErr> {
+    let collection = Keypair::new().pubkey();
+    let mut asset: Asset = serde_json::from_value(json!({
+        "interface": "V1_NFT",
+        "id": Keypair::new().pubkey().to_string(),
+        "grouping": [
+            { "group_key": "collection", "group_value": collection.to_string() },
+            { "group_key": "other", "group_value": collection.to_string() },
+        ],
+        "ownership": {
+            "frozen": false,
+            "delegated": false,
+            "delegate": null,
+            "ownership_model": "single",
+            "owner": Keypair::new().pubkey().to_string(),
+        },
+        "supply": null,
+        "mutable": true,
+        "burnt": false,
+    }))
+    .unwrap();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![asset_data::Model {
+            id: collection.to_bytes().to_vec(),
+            chain_data_mutability: ChainMutability::Mutable,
+            chain_data: json!({ "name": "Bees\u{0}\u{0}", "symbol": "BEE" }),
+            metadata_url: String::new(),
+            metadata_mutability: Mutability::Mutable,
+            metadata: json!({ "image": "https://bees.test/bee.png", "description": "Bees" }),
+            slot_updated: 0,
+            reindex: None,
+            raw_name: None,
+            raw_symbol: None,
+            base_info_seq: None,
+        }]])
+        .into_connection();
+
+    attach_collection_metadata(&db, std::slice::from_mut(&mut asset)).await?;
+
+    let grouping = asset.grouping.unwrap();
+    let metadata = grouping[0].collection_metadata.as_ref().unwrap();
+    assert_eq!(metadata.name.as_deref(), Some("Bees"));
+    assert_eq!(metadata.symbol.as_deref(), Some("BEE"));
+    assert_eq!(metadata.image.as_deref(), Some("https://bees.test/bee.png"));
+    assert_eq!(metadata.description.as_deref(), Some("Bees"));
+    assert_eq!(grouping[1].collection_metadata, None);
+    Ok(())
+}
+
+/// Returns the statements `get_assets_by_owner` runs for an owner without assets.
+async fn assets_by_owner_statements(options: Options) -> Result<String, DbErr> {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![Vec::<asset::Model>::new()])
+        .append_query_results(vec![Vec::<asset_data::Model>::new()])
+        .append_query_results(vec![Vec::<asset_creators::Model>::new()])
+        .append_query_results(vec![Vec::<tokens::Model>::new()])
+        .append_query_results(vec![Vec::<token_accounts::Model>::new()])
+        .append_query_results(vec![Vec::<asset_authority::Model>::new()])
+        .append_query_results(vec![Vec::<asset_grouping::Model>::new()])
+        .into_connection();
+
+    let assets = get_assets_by_owner(
+        &db,
+        vec![1; 32],
+        None,
+        Order::Asc,
+        &Pagination::Page { page: 1 },
+        10,
+        &options,
+    )
+    .await?;
+    assert!(assets.is_empty());
+    Ok(format!("{:?}", db.into_transaction_log()))
+}
+
+#[tokio::test]
+async fn assets_by_owner_keeps_zero_balances_without_holdings_by_default() -> Result<(), DbErr> {
+    let statements = assets_by_owner_statements(Options::default()).await?;
+
+    assert!(!statements.contains("IN (SELECT"));
+    assert!(!statements.contains("\\\"amount\\\" <>"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn assets_by_owner_filters_zero_balance_assets_when_hidden() -> Result<(), DbErr> {
+    let statements = assets_by_owner_statements(Options {
+        show_zero_balance: false,
+        ..Default::default()
+    })
+    .await?;
+
+    assert!(statements.contains("NOT IN (SELECT \\\"token_accounts\\\".\\\"mint\\\""));
+    // the funded holdings of the asset filter and the token accounts attached to the assets
+    assert_eq!(statements.matches("\\\"amount\\\" <>").count(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn assets_by_owner_adds_fungible_holdings_when_enabled() -> Result<(), DbErr> {
+    let statements = assets_by_owner_statements(Options {
+        show_fungible: true,
+        ..Default::default()
+    })
+    .await?;
+
+    assert!(statements.contains("IN (SELECT \\\"token_accounts\\\".\\\"mint\\\""));
+    assert!(!statements.contains("NOT IN"));
+    assert!(!statements.contains("\\\"amount\\\" <>"));
+    Ok(())
+}
+
+#[test]
+fn options_default_to_the_previous_behaviour() {
+    let options: Options = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(options, Options::default());
+    assert!(!options.show_fungible);
+    assert!(options.show_zero_balance);
+
+    let options: Options = serde_json::from_value(json!({ "showZeroBalance": false })).unwrap();
+    assert!(!options.show_zero_balance);
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use digital_asset_types::dao::{scopes::asset::get_grouping, GroupingStats};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase, Value};
+
+#[tokio::test]
+async fn get_grouping_reads_counts_and_mint_slots() -> Result<(), DbErr> {
+    let row = BTreeMap::from([
+        ("verified", Value::BigInt(Some(5))),
+        ("unverified", Value::BigInt(Some(1))),
+        ("compressed", Value::BigInt(Some(4))),
+        ("uncompressed", Value::BigInt(Some(1))),
+        ("burnt", Value::BigInt(Some(1))),
+        ("unique_holders", Value::BigInt(Some(3))),
+        ("first_mint_slot", Value::BigInt(Some(100))),
+        ("last_mint_slot", Value::BigInt(Some(250))),
+    ]);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![row]])
+        .into_connection();
+
+    let stats = get_grouping(&db, "collection".to_string(), "collection-id".to_string()).await?;
+
+    assert_eq!(
+        stats,
+        GroupingStats {
+            verified: 5,
+            unverified: 1,
+            compressed: 4,
+            uncompressed: 1,
+            burnt: 1,
+            unique_holders: 3,
+            first_mint_slot: Some(100),
+            last_mint_slot: Some(250),
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_grouping_of_an_empty_group() -> Result<(), DbErr> {
+    let row = BTreeMap::from([
+        ("verified", Value::BigInt(Some(0))),
+        ("unverified", Value::BigInt(Some(0))),
+        ("compressed", Value::BigInt(Some(0))),
+        ("uncompressed", Value::BigInt(Some(0))),
+        ("burnt", Value::BigInt(Some(0))),
+        ("unique_holders", Value::BigInt(Some(0))),
+        ("first_mint_slot", Value::BigInt(None)),
+        ("last_mint_slot", Value::BigInt(None)),
+    ]);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![row]])
+        .into_connection();
+
+    let stats = get_grouping(&db, "collection".to_string(), "collection-id".to_string()).await?;
+
+    assert_eq!(stats, GroupingStats::default());
+    Ok(())
+}
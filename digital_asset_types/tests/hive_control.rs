@@ -0,0 +1,113 @@
+use digital_asset_types::{
+    dao::{accounts, compressed_data},
+    dapi::{get_delegate_authorities, get_profiles},
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase};
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn get_profiles_names_achievements_and_custom_data() -> Result<(), DbErr> {
+    let project = Keypair::new().pubkey();
+    let tree = Keypair::new().pubkey();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![accounts::Model {
+            id: project.to_bytes().to_vec(),
+            program_id: vec![],
+            discriminator: vec![],
+            parsed_data: json!({
+                "name": "Game",
+                "profile_data_config": {
+                    "achievements": ["First Blood", "Explorer"],
+                    "custom_data_fields": ["twitter", "guild"],
+                },
+                "profile_trees": {
+                    "active": 0,
+                    "merkle_trees": [format!("pubkey:{tree}")],
+                },
+            }),
+            created_at: None,
+            slot_updated: 1,
+            project: None,
+        }]])
+        .append_query_results(vec![vec![compressed_data::Model {
+            id: vec![5; 32],
+            tree_id: tree.to_bytes().to_vec(),
+            leaf_idx: 0,
+            seq: 1,
+            schema_validated: true,
+            raw_data: vec![],
+            parsed_data: json!({
+                "user_id": 7,
+                "project": format!("pubkey:{project}"),
+                "platform_data": { "xp": 120, "achievements": [1, 4] },
+                "custom_data": { "0": ["@player"], "guild": ["bees"] },
+            }),
+            created_at: None,
+            slot_updated: 3,
+        }]])
+        .into_connection();
+
+    let profiles = get_profiles(&db, project.to_bytes().to_vec(), None, 10, None).await?;
+
+    assert_eq!(profiles.total, 1);
+    assert_eq!(
+        profiles.cursor,
+        Some(bs58::encode(vec![5; 32]).into_string())
+    );
+    let profile = &profiles.items[0];
+    assert_eq!(profile.project, project.to_string());
+    assert_eq!(profile.user_id, Some(7));
+    assert_eq!(profile.xp, Some(120));
+    assert_eq!(profile.achievements, vec!["Explorer", "4"]);
+    assert_eq!(profile.custom_data["twitter"], json!(["@player"]));
+    assert_eq!(profile.custom_data["guild"], json!(["bees"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_delegate_authorities_lists_permissions() -> Result<(), DbErr> {
+    let project = Keypair::new().pubkey();
+    let authority = Keypair::new().pubkey();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![accounts::Model {
+            id: vec![9; 32],
+            program_id: vec![],
+            discriminator: vec![],
+            parsed_data: json!({
+                "project": format!("pubkey:{project}"),
+                "authority": format!("pubkey:{authority}"),
+                "delegations": [
+                    {
+                        "__kind": "NectarStaking",
+                        "params": { "index": 2, "permission": "ManageStakingPool" },
+                    },
+                    {
+                        "__kind": "HiveControl",
+                        "params": { "permission": "ManageProfiles" },
+                    },
+                ],
+            }),
+            created_at: None,
+            slot_updated: 2,
+            project: None,
+        }]])
+        .into_connection();
+
+    let authorities = get_delegate_authorities(&db, project.to_string()).await?;
+
+    assert_eq!(authorities.len(), 1);
+    assert_eq!(authorities[0].authority, Some(authority.to_string()));
+    let delegations = &authorities[0].delegations;
+    assert_eq!(delegations[0].service, "NectarStaking");
+    assert_eq!(delegations[0].index, Some(2));
+    assert_eq!(
+        delegations[0].permission.as_deref(),
+        Some("ManageStakingPool")
+    );
+    assert_eq!(delegations[1].service, "HiveControl");
+    assert_eq!(delegations[1].index, None);
+    Ok(())
+}
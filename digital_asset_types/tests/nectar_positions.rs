@@ -0,0 +1,177 @@
+use digital_asset_types::{
+    dao::{accounts, character_history, compressed_data},
+    dapi::{common::calculate_reward, get_mission_participations, get_staking_positions},
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase};
+use serde_json::json;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn rewards_are_calculated_from_delta() {
+    let mission_rewards = vec![json!({ "min": 100, "max": 200, "reward_type": "Xp" })];
+    assert_eq!(
+        calculate_reward(
+            &json!({ "delta": 50, "reward_idx": 0, "collected": true }),
+            &mission_rewards
+        ),
+        Some(json!({ "reward": 150, "reward_type": "Xp" }))
+    );
+    assert_eq!(
+        calculate_reward(
+            &json!({ "delta": 50, "reward_idx": 0, "collected": false }),
+            &mission_rewards
+        ),
+        None
+    );
+    assert_eq!(
+        calculate_reward(
+            &json!({ "delta": 50, "reward_idx": 1, "collected": true }),
+            &mission_rewards
+        ),
+        None
+    );
+}
+
+#[test]
+fn rewards_with_invalid_bounds_or_overflowing_deltas_are_skipped() {
+    let mission_rewards = vec![
+        json!({ "min": 200, "max": 100, "reward_type": "Xp" }),
+        json!({ "min": 0, "max": u64::MAX, "reward_type": "Xp" }),
+    ];
+    assert_eq!(
+        calculate_reward(
+            &json!({ "delta": 50, "reward_idx": 0, "collected": true }),
+            &mission_rewards
+        ),
+        None
+    );
+    assert_eq!(
+        calculate_reward(
+            &json!({ "delta": 50, "reward_idx": 1, "collected": true }),
+            &mission_rewards
+        ),
+        None
+    );
+    assert_eq!(
+        calculate_reward(
+            &json!({ "delta": 1, "reward_idx": 1, "collected": true }),
+            &mission_rewards
+        ),
+        Some(json!({ "reward": u64::MAX / 100, "reward_type": "Xp" }))
+    );
+}
+
+#[tokio::test]
+async fn get_mission_participations_joins_missions() -> Result<(), DbErr> {
+    let wallet = Keypair::new().pubkey();
+    let mission = Keypair::new().pubkey();
+    let mission_pool = Keypair::new().pubkey();
+    let tree = Keypair::new().pubkey();
+    let character = vec![1; 32];
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![compressed_data::Model {
+            id: character.clone(),
+            tree_id: tree.to_bytes().to_vec(),
+            leaf_idx: 3,
+            seq: 1,
+            schema_validated: true,
+            raw_data: vec![],
+            parsed_data: json!({
+                "owner": format!("pubkey:{wallet}"),
+                "used_by": {
+                    "__kind": "Mission",
+                    "params": {
+                        "id": format!("pubkey:{mission}"),
+                        "end_time": 1_000,
+                        "rewards": [
+                            { "delta": 50, "reward_idx": 0, "collected": true },
+                            { "delta": 10, "reward_idx": 1, "collected": false },
+                        ],
+                    },
+                },
+            }),
+            created_at: None,
+            slot_updated: 9,
+        }]])
+        .append_query_results(vec![vec![accounts::Model {
+            id: mission.to_bytes().to_vec(),
+            program_id: vec![],
+            discriminator: vec![],
+            parsed_data: json!({
+                "mission_pool": format!("pubkey:{mission_pool}"),
+                "name": "Quest",
+                "rewards": [
+                    { "min": 100, "max": 200, "reward_type": "Xp" },
+                    {
+                        "min": 0,
+                        "max": 1_000,
+                        "reward_type": { "Resource": { "address": format!("pubkey:{wallet}") } },
+                    },
+                ],
+            }),
+            created_at: None,
+            slot_updated: 4,
+            project: None,
+        }]])
+        .append_query_results(vec![vec![accounts::Model {
+            id: mission_pool.to_bytes().to_vec(),
+            program_id: vec![],
+            discriminator: vec![],
+            parsed_data: json!({ "name": "Pool" }),
+            created_at: None,
+            slot_updated: 2,
+            project: None,
+        }]])
+        .append_query_results(vec![vec![character_history::Model {
+            id: 1,
+            character_id: character.clone(),
+            event: "MissionParticipation".to_string(),
+            event_data: json!({}),
+            created_at: None,
+            slot_updated: 7,
+            signature: None,
+        }]])
+        .into_connection();
+
+    let participations = get_mission_participations(&db, wallet.to_string(), None, 2_000).await?;
+
+    assert_eq!(participations.len(), 1);
+    let participation = &participations[0];
+    assert_eq!(participation.mission, mission.to_string());
+    assert_eq!(participation.mission_pool, Some(mission_pool.to_string()));
+    assert_eq!(
+        participation.mission_pool_config,
+        Some(json!({ "name": "Pool" }))
+    );
+    assert_eq!(participation.tree_id, tree.to_string());
+    assert_eq!(participation.end_time, Some(1_000));
+    assert_eq!(participation.entered_slot, Some(7));
+
+    let rewards = &participation.rewards;
+    assert_eq!(rewards.len(), 2);
+    assert_eq!(rewards[0].amount, Some(150));
+    assert!(rewards[0].collected && !rewards[0].claimable);
+    assert_eq!(rewards[1].amount, Some(100));
+    assert!(!rewards[1].collected && rewards[1].claimable);
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_staking_positions_scopes_characters_to_project_trees() -> Result<(), DbErr> {
+    let wallet = Keypair::new().pubkey();
+    let project = Keypair::new().pubkey();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![Vec::<compressed_data::Model>::new()])
+        .into_connection();
+
+    let positions =
+        get_staking_positions(&db, wallet.to_string(), Some(project.to_bytes().to_vec())).await?;
+
+    assert!(positions.is_empty());
+    // the statements are debug formatted, quotes included
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains(r#"\"merkle_tree\".\"project\""#));
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use digital_asset_types::{
+    dao::{accounts, compressed_data},
+    dapi::get_resource_holdings,
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase};
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+fn account(id: Pubkey, parsed_data: Value) -> accounts::Model {
+    accounts::Model {
+        id: id.to_bytes().to_vec(),
+        program_id: vec![],
+        discriminator: vec![],
+        parsed_data,
+        created_at: None,
+        slot_updated: 1,
+        project: None,
+    }
+}
+
+fn leaf(tree: Pubkey, leaf_idx: i64, parsed_data: Value) -> compressed_data::Model {
+    compressed_data::Model {
+        id: vec![leaf_idx as u8; 32],
+        tree_id: tree.to_bytes().to_vec(),
+        leaf_idx,
+        seq: 1,
+        schema_validated: true,
+        raw_data: vec![],
+        parsed_data,
+        created_at: None,
+        slot_updated: 10 + leaf_idx,
+    }
+}
+
+#[tokio::test]
+async fn get_resource_holdings_with_recipes() -> Result<(), DbErr> {
+    let wallet = Keypair::new().pubkey();
+    let project = Keypair::new().pubkey();
+    let resource = Keypair::new().pubkey();
+    let mint = Keypair::new().pubkey();
+    let tree = Keypair::new().pubkey();
+    let recipe = Keypair::new().pubkey();
+    let faucet = Keypair::new().pubkey();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![account(
+            resource,
+            json!({
+                "project": format!("pubkey:{project}"),
+                "mint": format!("pubkey:{mint}"),
+                "kind": { "__kind": "HplFungible", "params": { "decimals": 2 } },
+                "storage": {
+                    "__kind": "LedgerState",
+                    "params": {
+                        "merkle_trees": {
+                            "active": 0,
+                            "merkle_trees": [format!("pubkey:{tree}")],
+                        },
+                        "promise_supply": 0,
+                    },
+                },
+            }),
+        )]])
+        .append_query_results(vec![vec![
+            leaf(
+                tree,
+                0,
+                json!({ "holder": format!("pubkey:{wallet}"), "balance": 250 }),
+            ),
+            leaf(
+                tree,
+                1,
+                json!({ "holder": format!("pubkey:{wallet}"), "balance": 100 }),
+            ),
+        ]])
+        .append_query_results(vec![vec![account(
+            recipe,
+            json!({
+                "project": format!("pubkey:{project}"),
+                "xp": 5,
+                "ingredients": [
+                    { "resource_address": format!("pubkey:{resource}"), "amount": 100 },
+                ],
+                "meal": { "resource_address": format!("pubkey:{resource}"), "amount": 1 },
+            }),
+        )]])
+        .append_query_results(vec![vec![account(
+            faucet,
+            json!({
+                "project": format!("pubkey:{project}"),
+                "resource": format!("pubkey:{resource}"),
+                "amount": 10,
+                "repeat_interval": 60,
+                "last_claimed": 1_000,
+            }),
+        )]])
+        .into_connection();
+
+    let holdings = get_resource_holdings(&db, wallet.to_string(), None).await?;
+
+    assert_eq!(holdings.holdings.len(), 1);
+    let holding = &holdings.holdings[0];
+    assert_eq!(holding.resource, resource.to_string());
+    assert_eq!(holding.mint, Some(mint.to_string()));
+    assert_eq!(holding.kind.as_deref(), Some("HplFungible"));
+    assert_eq!(holding.balance, Some(350));
+    assert_eq!(holding.ui_amount_string.as_deref(), Some("3.5"));
+    assert_eq!(holding.leaves.len(), 2);
+
+    assert_eq!(holdings.recipes.len(), 1);
+    assert_eq!(holdings.recipes[0].craftable, 3);
+    assert_eq!(holdings.recipes[0].ingredients[0].held, 350);
+
+    assert_eq!(holdings.faucets.len(), 1);
+    assert_eq!(holdings.faucets[0].next_claim_at, Some(1_060));
+    Ok(())
+}
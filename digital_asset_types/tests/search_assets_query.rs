@@ -0,0 +1,191 @@
+use digital_asset_types::{
+    dao::{
+        asset, asset_authority, asset_creators, asset_data, asset_grouping, tokens, Cursor,
+        PageOptions, SearchAssetsQuery,
+    },
+    dapi::search_assets,
+    rpc::{
+        filter::{AssetSortBy, AssetSorting, AttributeFilter},
+        options::Options,
+    },
+};
+use sea_orm::{
+    entity::*, query::*, sea_query::ConditionType, DatabaseBackend, DbBackend, DbErr, MockDatabase,
+    Statement, Value,
+};
+use serde_json::json;
+
+fn query() -> SearchAssetsQuery {
+    SearchAssetsQuery {
+        negate: None,
+        condition_type: None,
+        specification_version: None,
+        specification_asset_class: None,
+        owner_address: None,
+        owner_type: None,
+        creator_address: None,
+        creator_verified: None,
+        authority_address: None,
+        grouping: None,
+        delegate: None,
+        frozen: None,
+        supply: None,
+        supply_mint: None,
+        compressed: None,
+        compressible: None,
+        royalty_target_type: None,
+        royalty_target: None,
+        royalty_amount: None,
+        burnt: None,
+        json_uri: None,
+        name: None,
+        symbol: None,
+        attributes: None,
+        attribute_condition_type: None,
+    }
+}
+
+fn build(query: &SearchAssetsQuery) -> Result<(Statement, usize), DbErr> {
+    let (condition, joins) = query.conditions()?;
+    let joined = joins.len();
+    Ok((
+        asset::Entity::find()
+            .filter(condition)
+            .build(DbBackend::Postgres),
+        joined,
+    ))
+}
+
+fn values(statement: &Statement) -> Vec<Value> {
+    statement
+        .values
+        .as_ref()
+        .map(|values| values.0.clone())
+        .unwrap_or_default()
+}
+
+#[test]
+fn search_by_name_and_symbol_is_parameterized() -> Result<(), DbErr> {
+    let (statement, joined) = build(&SearchAssetsQuery {
+        json_uri: Some("https://example.com/1.json".to_string()),
+        name: Some("Bee's 100%".as_bytes().to_vec()),
+        symbol: Some("BEE".to_string()),
+        ..query()
+    })?;
+
+    // json_uri, name and symbol share a single join on asset_data
+    assert_eq!(joined, 1);
+    assert!(statement
+        .sql
+        .contains("asset_data.chain_data->>'name' ILIKE $"));
+    assert!(statement
+        .sql
+        .contains("asset_data.chain_data->>'symbol' % $"));
+    let values = values(&statement);
+    // names still match anywhere, as the `LIKE '%name%'` search they replace did
+    assert!(values.contains(&Value::from("%Bee's 100\\%%".to_string())));
+    assert!(values.contains(&Value::from("Bee's 100%".to_string())));
+    assert!(values.contains(&Value::from("%BEE%".to_string())));
+    Ok(())
+}
+
+#[test]
+fn search_by_attributes() -> Result<(), DbErr> {
+    let attributes = vec![
+        AttributeFilter {
+            trait_type: "Background".to_string(),
+            value: json!("Honey"),
+        },
+        AttributeFilter {
+            trait_type: "Level".to_string(),
+            value: json!(3),
+        },
+    ];
+
+    let (statement, joined) = build(&SearchAssetsQuery {
+        attributes: Some(attributes.clone()),
+        ..query()
+    })?;
+    assert_eq!(joined, 1);
+    assert!(statement
+        .sql
+        .contains("::jsonb AND asset_data.metadata->'attributes' @> $"));
+    assert!(values(&statement).contains(&Value::from(
+        json!([{"trait_type": "Level", "value": 3}]).to_string()
+    )));
+
+    let (statement, _) = build(&SearchAssetsQuery {
+        attributes: Some(attributes),
+        attribute_condition_type: Some(ConditionType::Any),
+        ..query()
+    })?;
+    assert!(statement
+        .sql
+        .contains("::jsonb OR asset_data.metadata->'attributes' @> $"));
+    Ok(())
+}
+
+#[test]
+fn search_without_asset_data_filters_does_not_join() -> Result<(), DbErr> {
+    let query = SearchAssetsQuery {
+        frozen: Some(false),
+        ..query()
+    };
+    let (_, joined) = build(&query)?;
+    assert!(!query.joins_asset_data());
+    assert_eq!(joined, 0);
+    Ok(())
+}
+
+const BY_NAME: AssetSorting = AssetSorting {
+    sort_by: AssetSortBy::Name,
+    sort_direction: None,
+};
+
+#[tokio::test]
+async fn sorting_by_name_pages_by_offset() -> Result<(), DbErr> {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![Vec::<asset::Model>::new()])
+        .append_query_results(vec![Vec::<asset_data::Model>::new()])
+        .append_query_results(vec![Vec::<asset_creators::Model>::new()])
+        .append_query_results(vec![Vec::<tokens::Model>::new()])
+        .append_query_results(vec![Vec::<asset_authority::Model>::new()])
+        .append_query_results(vec![Vec::<asset_grouping::Model>::new()])
+        .into_connection();
+    let page_options = PageOptions {
+        limit: 10,
+        page: Some(2),
+        ..Default::default()
+    };
+
+    let list = search_assets(&db, query(), BY_NAME, &page_options, &Options::default()).await?;
+
+    assert_eq!(list.page, Some(2));
+    assert_eq!(list.cursor, None);
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("ORDER BY asset_data.chain_data->>'name'"));
+    assert!(log.contains("OFFSET"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn sorting_by_name_rejects_id_cursors() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let cursors = [
+        PageOptions {
+            limit: 10,
+            cursor: Some(Cursor::default()),
+            ..Default::default()
+        },
+        PageOptions {
+            limit: 10,
+            after: Some(vec![1; 32]),
+            ..Default::default()
+        },
+    ];
+
+    for page_options in cursors {
+        let result = search_assets(&db, query(), BY_NAME, &page_options, &Options::default()).await;
+        assert!(matches!(result, Err(DbErr::Custom(_))));
+    }
+}
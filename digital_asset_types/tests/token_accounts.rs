@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use digital_asset_types::{
+    dao::token_accounts,
+    dapi::{get_token_accounts, get_token_balances},
+    rpc::{ui_amount_string, ui_amount_string_u128},
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase, Value};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn ui_amount_strings() {
+    assert_eq!(ui_amount_string(0, 0), "0");
+    assert_eq!(ui_amount_string(42, 0), "42");
+    assert_eq!(ui_amount_string(0, 6), "0");
+    assert_eq!(ui_amount_string(1_500_000, 6), "1.5");
+    assert_eq!(ui_amount_string(1, 9), "0.000000001");
+    assert_eq!(ui_amount_string(1_000_000_000, 9), "1");
+    assert_eq!(ui_amount_string(u64::MAX, 9), "18446744073.709551615");
+    assert_eq!(
+        ui_amount_string_u128(u128::from(u64::MAX) * 2, 9),
+        "36893488147.41910323"
+    );
+}
+
+#[tokio::test]
+async fn get_token_accounts_by_owner() -> Result<(), DbErr> {
+    let owner = Keypair::new().pubkey();
+    let mint = Keypair::new().pubkey();
+    let token_account = Keypair::new().pubkey();
+    let token_program = Keypair::new().pubkey();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![token_accounts::Model {
+            pubkey: token_account.to_bytes().to_vec(),
+            mint: mint.to_bytes().to_vec(),
+            amount: 1_500,
+            owner: owner.to_bytes().to_vec(),
+            frozen: false,
+            close_authority: None,
+            delegate: None,
+            delegated_amount: u64::MAX as i64,
+            slot_updated: 10,
+            token_program: token_program.to_bytes().to_vec(),
+        }]])
+        .into_connection();
+
+    let list = get_token_accounts(&db, Some(owner.to_bytes().to_vec()), None, 10, None).await?;
+
+    assert_eq!(list.total, 1);
+    assert_eq!(list.limit, 10);
+    assert_eq!(list.cursor, Some(token_account.to_string()));
+    let account = &list.items[0];
+    assert_eq!(account.address, token_account.to_string());
+    assert_eq!(account.mint, mint.to_string());
+    assert_eq!(account.owner, owner.to_string());
+    assert_eq!(account.amount, 1_500);
+    assert_eq!(account.delegated_amount, u64::MAX);
+    assert_eq!(account.token_program, token_program.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_token_balances_sums_past_u64() -> Result<(), DbErr> {
+    let owner = Keypair::new().pubkey();
+    let mint = Keypair::new().pubkey();
+    let token_program = Keypair::new().pubkey();
+    let total = u128::from(u64::MAX) * 2;
+
+    let row = BTreeMap::from([
+        ("mint", Value::from(mint.to_bytes().to_vec())),
+        ("amount", Value::from(total.to_string())),
+        ("token_accounts", Value::BigInt(Some(2))),
+        ("decimals", Value::Int(Some(9))),
+        (
+            "token_program",
+            Value::from(token_program.to_bytes().to_vec()),
+        ),
+    ]);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![row]])
+        .into_connection();
+
+    let list = get_token_balances(&db, owner.to_bytes().to_vec(), 10, None, false).await?;
+
+    assert_eq!(list.total, 1);
+    assert_eq!(list.cursor, Some(mint.to_string()));
+    let balance = &list.items[0];
+    assert_eq!(balance.mint, mint.to_string());
+    assert_eq!(balance.amount, total);
+    assert_eq!(balance.decimals, Some(9));
+    assert_eq!(
+        balance.ui_amount_string.as_deref(),
+        Some("36893488147.41910323")
+    );
+    assert_eq!(balance.token_accounts, 2);
+    assert_eq!(balance.token_program, token_program.to_string());
+
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("::TEXT AS amount"));
+    assert!(!log.contains("::BIGINT"));
+    assert!(log.contains("HAVING bool_or(ta.amount <> 0)"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_token_balances_with_zero_balances_and_cursor() -> Result<(), DbErr> {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![Vec::<BTreeMap<&str, Value>>::new()])
+        .into_connection();
+
+    let list = get_token_balances(&db, vec![1; 32], 5, Some(vec![2; 32]), true).await?;
+
+    assert_eq!(list.total, 0);
+    assert_eq!(list.cursor, None);
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("AND ta.mint > $2"));
+    assert!(!log.contains("HAVING"));
+    assert!(log.contains("LIMIT 5"));
+    Ok(())
+}
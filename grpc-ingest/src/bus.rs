@@ -0,0 +1,374 @@
+use {
+    crate::{
+        config::ConfigIngesterRedisStreamType,
+        redis::{account_info_from_update, transaction_info_from_update},
+    },
+    futures::future::{BoxFuture, Fuse, FutureExt},
+    program_transformers::{AccountInfo, TransactionInfo},
+    std::{
+        collections::{BTreeMap, HashMap, VecDeque},
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    },
+    tokio::sync::{mpsc, Notify},
+    yellowstone_grpc_proto::prost::Message,
+};
+
+#[derive(Debug)]
+pub enum ProgramTransformerInfo {
+    Account(AccountInfo),
+    Transaction(TransactionInfo),
+}
+
+impl ProgramTransformerInfo {
+    /// Decodes the `SubscribeUpdateAccount` or `SubscribeUpdateTransaction` published to a stream.
+    pub fn decode(stream_type: ConfigIngesterRedisStreamType, data: &[u8]) -> anyhow::Result<Self> {
+        Ok(match stream_type {
+            ConfigIngesterRedisStreamType::Account => {
+                Self::Account(account_info_from_update(Message::decode(data)?)?)
+            }
+            ConfigIngesterRedisStreamType::Transaction => {
+                Self::Transaction(transaction_info_from_update(Message::decode(data)?)?)
+            }
+        })
+    }
+}
+
+/// Message received from a stream, delivered again to the next consumer until it is acked.
+#[derive(Debug)]
+pub struct StreamMessage {
+    id: String,
+    data: ProgramTransformerInfo,
+    ack_tx: mpsc::UnboundedSender<String>,
+    skipped: Option<Arc<AtomicUsize>>,
+}
+
+impl StreamMessage {
+    pub const fn new(
+        id: String,
+        data: ProgramTransformerInfo,
+        ack_tx: mpsc::UnboundedSender<String>,
+    ) -> Self {
+        Self {
+            id,
+            data,
+            ack_tx,
+            skipped: None,
+        }
+    }
+
+    /// Counts the message in `skipped` if it is acked by [StreamMessage::skip].
+    pub fn with_skipped(mut self, skipped: Arc<AtomicUsize>) -> Self {
+        self.skipped = Some(skipped);
+        self
+    }
+
+    pub const fn get_data(&self) -> &ProgramTransformerInfo {
+        &self.data
+    }
+
+    pub fn ack(self) -> anyhow::Result<()> {
+        self.ack_tx
+            .send(self.id)
+            .map_err(|_error| anyhow::anyhow!("failed to send message to ack channel"))
+    }
+
+    /// Acks a message that could not be handled, it is not delivered again.
+    pub fn skip(self) -> anyhow::Result<()> {
+        if let Some(skipped) = &self.skipped {
+            skipped.fetch_add(1, Ordering::Relaxed);
+        }
+        self.ack()
+    }
+}
+
+/// Update published to a stream, `data` is the encoded `SubscribeUpdateAccount` or
+/// `SubscribeUpdateTransaction`.
+#[derive(Debug, Clone)]
+pub struct StreamEntry {
+    pub stream: String,
+    pub data: Vec<u8>,
+}
+
+/// Spawned tasks of a consumer (prefetch, acks), resolved once they are all finished.
+pub type ConsumerTasks = Fuse<BoxFuture<'static, anyhow::Result<()>>>;
+
+/// Receiving side of the streams, as used by `ingester::consume`.
+pub trait MessageConsumer: Send {
+    /// Next prefetched message, `None` once the consumer is drained.
+    fn recv(&mut self) -> BoxFuture<'_, Option<StreamMessage>>;
+
+    /// Stops prefetching, messages not acked yet are delivered again to the next consumer.
+    fn shutdown(self: Box<Self>);
+}
+
+/// Publishing side of the streams.
+pub trait MessageProducer: Send + Sync {
+    /// Publishes the entries atomically, either all of them or none.
+    fn publish(&self, entries: Vec<StreamEntry>) -> BoxFuture<'static, anyhow::Result<()>>;
+}
+
+#[derive(Debug)]
+struct MemoryStreamInfo {
+    name: String,
+    stream_type: ConfigIngesterRedisStreamType,
+    ack_tx: mpsc::UnboundedSender<String>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryStream {
+    queue: VecDeque<(u64, Vec<u8>)>,
+    pending: BTreeMap<u64, Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryStreams {
+    /// Ids are shared by the streams so entries are delivered in the order they were published.
+    next_id: u64,
+    streams: HashMap<String, MemoryStream>,
+}
+
+/// In-process streams, for tests and single binary setups without Redis.
+///
+/// Like the Redis consumer group, a message stays pending once delivered and is delivered again
+/// to the next consumer if it was not acked. Streams are read by one consumer at a time, publishing
+/// waits while a stream holds `queue_size` undelivered entries.
+#[derive(Debug, Clone)]
+pub struct MemoryBus {
+    streams: Arc<Mutex<MemoryStreams>>,
+    /// Woken on every publish, delivery and ack.
+    changed: Arc<Notify>,
+    queue_size: usize,
+    skipped: Arc<AtomicUsize>,
+}
+
+impl MemoryBus {
+    pub fn new(queue_size: usize) -> Self {
+        Self {
+            streams: Arc::default(),
+            changed: Arc::new(Notify::new()),
+            queue_size: queue_size.max(1),
+            skipped: Arc::default(),
+        }
+    }
+
+    /// Messages acked without being handled so far, by every consumer of the bus.
+    pub fn skipped(&self) -> usize {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    pub fn consumer(
+        &self,
+        streams: &[(String, ConfigIngesterRedisStreamType)],
+        prefetch_queue_size: usize,
+    ) -> (MemoryConsumer, ConsumerTasks) {
+        // requeue messages left pending by the previous consumer, oldest first
+        {
+            let mut locked = self.lock();
+            for (name, _stream_type) in streams {
+                let stream = locked.streams.entry(name.clone()).or_default();
+                for (id, data) in std::mem::take(&mut stream.pending).into_iter().rev() {
+                    stream.queue.push_front((id, data));
+                }
+            }
+        }
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let mut ack_jh_vec = vec![];
+        let streams = streams
+            .iter()
+            .map(|(name, stream_type)| {
+                let (ack_tx, ack_rx) = mpsc::unbounded_channel();
+                ack_jh_vec.push(tokio::spawn({
+                    let bus = self.clone();
+                    let name = name.clone();
+                    async move { bus.run_ack(name, ack_rx).await }
+                }));
+                MemoryStreamInfo {
+                    name: name.clone(),
+                    stream_type: *stream_type,
+                    ack_tx,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let (messages_tx, messages_rx) = mpsc::channel(prefetch_queue_size);
+        let jh_prefetch = tokio::spawn({
+            let bus = self.clone();
+            let shutdown = Arc::clone(&shutdown);
+            async move { bus.run_prefetch(streams, messages_tx, shutdown).await }
+        });
+
+        let spawned_tasks = async move {
+            jh_prefetch.await??;
+            for jh in ack_jh_vec.into_iter() {
+                jh.await??;
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+
+        (
+            MemoryConsumer {
+                shutdown,
+                changed: Arc::clone(&self.changed),
+                messages_rx,
+            },
+            spawned_tasks.boxed().fuse(),
+        )
+    }
+
+    /// Waits until every entry published to the stream was acked.
+    pub async fn drained(&self, stream: &str) {
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            let drained = self.lock().streams.get(stream).map_or(true, |stream| {
+                stream.queue.is_empty() && stream.pending.is_empty()
+            });
+            if drained {
+                return;
+            }
+            changed.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryStreams> {
+        self.streams
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Moves the oldest queued entry of the streams to pending.
+    fn next_entry(&self, streams: &[MemoryStreamInfo]) -> Option<(usize, u64, Vec<u8>)> {
+        let mut locked = self.lock();
+        let (index, _id) = streams
+            .iter()
+            .enumerate()
+            .filter_map(|(index, info)| {
+                let (id, _data) = locked.streams.get(&info.name)?.queue.front()?;
+                Some((index, *id))
+            })
+            .min_by_key(|(_index, id)| *id)?;
+        let stream = locked.streams.get_mut(&streams[index].name)?;
+        let (id, data) = stream.queue.pop_front()?;
+        stream.pending.insert(id, data.clone());
+        drop(locked);
+        self.changed.notify_waiters();
+        Some((index, id, data))
+    }
+
+    async fn run_prefetch(
+        self,
+        streams: Vec<MemoryStreamInfo>,
+        messages_tx: mpsc::Sender<StreamMessage>,
+        shutdown: Arc<AtomicBool>,
+    ) -> anyhow::Result<()> {
+        while !shutdown.load(Ordering::Relaxed) {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            let Some((index, id, data)) = self.next_entry(&streams) else {
+                changed.await;
+                continue;
+            };
+
+            let stream = &streams[index];
+            let data =
+                ProgramTransformerInfo::decode(stream.stream_type, &data).map_err(|error| {
+                    anyhow::anyhow!("invalid data from stream {:?}: {:?}", stream.name, error)
+                })?;
+            let item = StreamMessage::new(id.to_string(), data, stream.ack_tx.clone())
+                .with_skipped(Arc::clone(&self.skipped));
+            messages_tx
+                .send(item)
+                .await
+                .map_err(|_error| anyhow::anyhow!("failed to send item to prefetch channel"))?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_ack(
+        self,
+        stream: String,
+        mut ack_rx: mpsc::UnboundedReceiver<String>,
+    ) -> anyhow::Result<()> {
+        while let Some(id) = ack_rx.recv().await {
+            let id = id.parse::<u64>()?;
+            if let Some(stream) = self.lock().streams.get_mut(&stream) {
+                stream.pending.remove(&id);
+            }
+            self.changed.notify_waiters();
+        }
+
+        Ok(())
+    }
+}
+
+impl MessageProducer for MemoryBus {
+    /// Waits until every stream written to has room, the entries are then all queued even if
+    /// they go past `queue_size`.
+    fn publish(&self, entries: Vec<StreamEntry>) -> BoxFuture<'static, anyhow::Result<()>> {
+        let bus = self.clone();
+        async move {
+            loop {
+                let changed = bus.changed.notified();
+                tokio::pin!(changed);
+                changed.as_mut().enable();
+
+                {
+                    let mut locked = bus.lock();
+                    let full = entries.iter().any(|entry| {
+                        locked
+                            .streams
+                            .get(&entry.stream)
+                            .map_or(false, |stream| stream.queue.len() >= bus.queue_size)
+                    });
+                    if !full {
+                        for StreamEntry { stream, data } in entries {
+                            let id = locked.next_id;
+                            locked.next_id += 1;
+                            locked
+                                .streams
+                                .entry(stream)
+                                .or_default()
+                                .queue
+                                .push_back((id, data));
+                        }
+                        break;
+                    }
+                }
+                changed.await;
+            }
+
+            bus.changed.notify_waiters();
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryConsumer {
+    shutdown: Arc<AtomicBool>,
+    changed: Arc<Notify>,
+    messages_rx: mpsc::Receiver<StreamMessage>,
+}
+
+impl MessageConsumer for MemoryConsumer {
+    fn recv(&mut self) -> BoxFuture<'_, Option<StreamMessage>> {
+        self.messages_rx.recv().boxed()
+    }
+
+    fn shutdown(mut self: Box<Self>) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.changed.notify_waiters();
+        tokio::spawn(async move { while self.messages_rx.recv().await.is_some() {} });
+    }
+}
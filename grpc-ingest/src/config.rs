@@ -1,7 +1,13 @@
 use {
+    crate::replay::ReplayFormat,
     anyhow::Context,
+    program_transformers::events::DomainEvent,
     serde::{de, Deserialize},
-    std::{net::SocketAddr, path::Path, time::Duration},
+    std::{
+        net::SocketAddr,
+        path::{Path, PathBuf},
+        time::Duration,
+    },
     tokio::fs,
     tracing::warn,
     yellowstone_grpc_tools::config::{
@@ -13,6 +19,10 @@ use {
 pub const REDIS_STREAM_ACCOUNTS: &str = "ACCOUNTS";
 pub const REDIS_STREAM_TRANSACTIONS: &str = "TRANSACTIONS";
 pub const REDIS_STREAM_DATA_KEY: &str = "data";
+pub const REDIS_STREAM_EVENTS: &str = "EVENTS";
+pub const REDIS_GRPC_CHECKPOINT_PREFIX: &str = "GRPC_CHECKPOINT";
+pub const REDIS_STREAM_GRPC_GAPS: &str = "GRPC_GAPS";
+pub const POLL_CHECKPOINT_FILE: &str = "poll-checkpoint.json";
 
 pub async fn load<T>(path: impl AsRef<Path> + Copy) -> anyhow::Result<T>
 where
@@ -60,6 +70,11 @@ pub struct ConfigGrpc {
     pub solana_seen_event_cache_max_size: usize,
 
     pub redis: ConfigGrpcRedis,
+
+    #[serde(default)]
+    pub checkpoint: ConfigGrpcCheckpoint,
+    #[serde(default)]
+    pub reconnect: ConfigGrpcReconnect,
 }
 
 impl ConfigGrpc {
@@ -72,6 +87,70 @@ impl ConfigGrpc {
     }
 }
 
+/// Where the last slot fully forwarded to Redis is kept for each endpoint, and where the slot
+/// ranges missed while an endpoint was disconnected are recorded.
+#[derive(Debug, Deserialize)]
+pub struct ConfigGrpcCheckpoint {
+    #[serde(default = "ConfigGrpcCheckpoint::default_key_prefix")]
+    pub key_prefix: String,
+    #[serde(default = "ConfigGrpcCheckpoint::default_gaps_stream")]
+    pub gaps_stream: String,
+}
+
+impl Default for ConfigGrpcCheckpoint {
+    fn default() -> Self {
+        Self {
+            key_prefix: Self::default_key_prefix(),
+            gaps_stream: Self::default_gaps_stream(),
+        }
+    }
+}
+
+impl ConfigGrpcCheckpoint {
+    pub fn default_key_prefix() -> String {
+        REDIS_GRPC_CHECKPOINT_PREFIX.to_owned()
+    }
+
+    pub fn default_gaps_stream() -> String {
+        REDIS_STREAM_GRPC_GAPS.to_owned()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ConfigGrpcReconnect {
+    #[serde(
+        default = "ConfigGrpcReconnect::default_backoff_base",
+        deserialize_with = "deserialize_duration_str",
+        rename = "backoff_base_ms"
+    )]
+    pub backoff_base: Duration,
+    #[serde(
+        default = "ConfigGrpcReconnect::default_backoff_max",
+        deserialize_with = "deserialize_duration_str",
+        rename = "backoff_max_ms"
+    )]
+    pub backoff_max: Duration,
+}
+
+impl Default for ConfigGrpcReconnect {
+    fn default() -> Self {
+        Self {
+            backoff_base: Self::default_backoff_base(),
+            backoff_max: Self::default_backoff_max(),
+        }
+    }
+}
+
+impl ConfigGrpcReconnect {
+    pub const fn default_backoff_base() -> Duration {
+        Duration::from_millis(500)
+    }
+
+    pub const fn default_backoff_max() -> Duration {
+        Duration::from_millis(60_000)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ConfigGrpcAccounts {
     #[serde(default = "ConfigGrpcAccounts::default_stream")]
@@ -179,6 +258,8 @@ pub struct ConfigIngester {
     pub postgres: ConfigIngesterPostgres,
     pub program_transformer: ConfigIngesterProgramTransformer,
     pub download_metadata: ConfigIngesterDownloadMetadata,
+    #[serde(default)]
+    pub events: Option<ConfigIngesterEvents>,
     #[serde(
         default = "ConfigIngester::default_inactivity_timeout",
         deserialize_with = "deserialize_usize_str"
@@ -197,6 +278,160 @@ impl ConfigIngester {
     }
 }
 
+/// Subset of [ConfigIngester], the ingester config can be used for replays as is.
+#[derive(Debug, Deserialize)]
+pub struct ConfigReplay {
+    pub postgres: ConfigIngesterPostgres,
+    pub program_transformer: ConfigIngesterProgramTransformer,
+    pub download_metadata: ConfigIngesterDownloadMetadata,
+}
+
+/// Ingests from a plain JSON-RPC node, the transactions are handled directly without Redis.
+#[derive(Debug, Deserialize)]
+pub struct ConfigPoll {
+    pub poll: ConfigPollSource,
+    /// Slot ranges missed by `grpc2redis`, backfilled from the same node
+    #[serde(default)]
+    pub gaps: Option<ConfigPollGaps>,
+    pub postgres: ConfigIngesterPostgres,
+    pub program_transformer: ConfigIngesterProgramTransformer,
+    pub download_metadata: ConfigIngesterDownloadMetadata,
+}
+
+/// Everything in one process for local development, only Postgres is needed: the migrations, the
+/// source feeding the ingester through in-process streams, the metadata downloader and the API.
+#[derive(Debug, Deserialize)]
+pub struct ConfigLocal {
+    pub postgres: ConfigIngesterPostgres,
+    pub source: ConfigLocalSource,
+    /// Schema applied by the first migration instead of the embedded `init.sql`
+    #[serde(default)]
+    pub init_file: Option<PathBuf>,
+    #[serde(default)]
+    pub api: ConfigLocalApi,
+    /// Undelivered updates held by each in-process stream before the source waits
+    #[serde(
+        default = "ConfigLocal::default_queue_size",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub queue_size: usize,
+    #[serde(default)]
+    pub program_transformer: ConfigIngesterProgramTransformer,
+    #[serde(default)]
+    pub download_metadata: ConfigDownloadMetadataOpts,
+}
+
+impl ConfigLocal {
+    pub const fn default_queue_size() -> usize {
+        10_000
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigLocalSource {
+    /// Dump handled once on startup
+    Replay(ConfigLocalReplay),
+    /// Signatures followed on a JSON-RPC node, such as a test validator
+    Poll(ConfigPollSource),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigLocalReplay {
+    pub file: PathBuf,
+    #[serde(default)]
+    pub format: Option<ReplayFormat>,
+    #[serde(default)]
+    pub sort: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigLocalApi {
+    #[serde(default = "ConfigLocalApi::default_port")]
+    pub port: u16,
+}
+
+impl Default for ConfigLocalApi {
+    fn default() -> Self {
+        Self {
+            port: Self::default_port(),
+        }
+    }
+}
+
+impl ConfigLocalApi {
+    pub const fn default_port() -> u16 {
+        9090
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigPollSource {
+    pub rpc_url: String,
+    /// Program ids and trees whose signatures are followed
+    pub addresses: Vec<String>,
+    #[serde(
+        default = "ConfigPollSource::default_interval",
+        deserialize_with = "deserialize_duration_str",
+        rename = "interval_ms"
+    )]
+    pub interval: Duration,
+    #[serde(
+        default = "ConfigPollSource::default_max_requests_per_second",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub max_requests_per_second: usize,
+    /// Last signature handled for every address
+    #[serde(default = "ConfigPollSource::default_checkpoint_file")]
+    pub checkpoint_file: PathBuf,
+    /// Walk the whole history of addresses without a checkpoint instead of starting from their
+    /// latest signature
+    #[serde(default)]
+    pub backfill: bool,
+    /// Signatures fetched and checkpointed at once, at most 1000
+    #[serde(
+        default = "ConfigPollSource::default_signatures_page_limit",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub signatures_page_limit: usize,
+}
+
+/// Redis stream where `grpc2redis` records the slot ranges it may have missed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigPollGaps {
+    pub url: String,
+    #[serde(default = "ConfigGrpcCheckpoint::default_gaps_stream")]
+    pub stream: String,
+    #[serde(default = "ConfigPollGaps::default_group")]
+    pub group: String,
+    #[serde(default = "ConfigIngesterRedis::default_consumer")]
+    pub consumer: String,
+}
+
+impl ConfigPollGaps {
+    pub fn default_group() -> String {
+        "poll".to_owned()
+    }
+}
+
+impl ConfigPollSource {
+    pub const fn default_interval() -> Duration {
+        Duration::from_millis(1_000)
+    }
+
+    pub const fn default_max_requests_per_second() -> usize {
+        10
+    }
+
+    pub fn default_checkpoint_file() -> PathBuf {
+        PathBuf::from(POLL_CHECKPOINT_FILE)
+    }
+
+    pub const fn default_signatures_page_limit() -> usize {
+        1_000
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ConfigIngesterRedis {
     pub url: String,
@@ -325,7 +560,7 @@ pub enum ConfigIngesterRedisStreamType {
     Transaction,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ConfigIngesterPostgres {
     pub url: String,
     #[serde(
@@ -361,6 +596,15 @@ pub struct ConfigIngesterProgramTransformer {
     pub max_tasks_in_process: usize,
 }
 
+impl Default for ConfigIngesterProgramTransformer {
+    fn default() -> Self {
+        Self {
+            transactions_cl_audits: Self::default_transactions_cl_audits(),
+            max_tasks_in_process: Self::default_max_tasks_in_process(),
+        }
+    }
+}
+
 impl ConfigIngesterProgramTransformer {
     pub const fn default_transactions_cl_audits() -> bool {
         false
@@ -386,6 +630,27 @@ impl ConfigIngesterDownloadMetadata {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigIngesterEvents {
+    #[serde(default = "ConfigIngesterEvents::default_stream")]
+    pub stream: String,
+    #[serde(
+        default = "ConfigIngesterEvents::default_stream_maxlen",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub stream_maxlen: usize,
+}
+
+impl ConfigIngesterEvents {
+    pub fn default_stream() -> String {
+        REDIS_STREAM_EVENTS.to_owned()
+    }
+
+    pub const fn default_stream_maxlen() -> usize {
+        1_000_000
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ConfigDownloadMetadata {
     pub postgres: ConfigIngesterPostgres,
@@ -423,6 +688,18 @@ pub struct ConfigDownloadMetadataOpts {
     pub download_timeout: Duration,
 }
 
+impl Default for ConfigDownloadMetadataOpts {
+    fn default() -> Self {
+        Self {
+            max_in_process: Self::default_max_in_process(),
+            prefetch_queue_size: Self::default_prefetch_queue_size(),
+            limit_to_fetch: Self::default_limit_to_fetch(),
+            wait_tasks_max_idle: Self::default_wait_tasks_max_idle(),
+            download_timeout: Self::default_download_timeout(),
+        }
+    }
+}
+
 impl ConfigDownloadMetadataOpts {
     pub const fn default_max_in_process() -> usize {
         50
@@ -444,3 +721,146 @@ impl ConfigDownloadMetadataOpts {
         Duration::from_millis(5_000)
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigWebhooks {
+    pub redis: ConfigWebhooksRedis,
+    pub postgres: ConfigIngesterPostgres,
+    #[serde(default)]
+    pub dispatcher: ConfigWebhooksDispatcher,
+    pub webhooks: Vec<ConfigWebhook>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigWebhooksRedis {
+    pub url: String,
+    #[serde(default = "ConfigIngesterEvents::default_stream")]
+    pub stream: String,
+    #[serde(default = "ConfigWebhooksRedis::default_group")]
+    pub group: String,
+    #[serde(default = "ConfigIngesterRedis::default_consumer")]
+    pub consumer: String,
+    #[serde(
+        default = "ConfigWebhooksRedis::default_xreadgroup_max",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub xreadgroup_max: usize,
+}
+
+impl ConfigWebhooksRedis {
+    pub fn default_group() -> String {
+        "webhooks".to_owned()
+    }
+
+    pub const fn default_xreadgroup_max() -> usize {
+        100
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ConfigWebhooksDispatcher {
+    #[serde(
+        default = "ConfigWebhooksDispatcher::default_max_attempts",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub max_attempts: usize,
+    #[serde(
+        default = "ConfigWebhooksDispatcher::default_batch_size",
+        deserialize_with = "deserialize_usize_str"
+    )]
+    pub batch_size: usize,
+    #[serde(
+        default = "ConfigWebhooksDispatcher::default_poll_interval",
+        deserialize_with = "deserialize_duration_str",
+        rename = "poll_interval_ms"
+    )]
+    pub poll_interval: Duration,
+    #[serde(
+        default = "ConfigWebhooksDispatcher::default_retry_base",
+        deserialize_with = "deserialize_duration_str",
+        rename = "retry_base_ms"
+    )]
+    pub retry_base: Duration,
+    #[serde(
+        default = "ConfigWebhooksDispatcher::default_retry_max",
+        deserialize_with = "deserialize_duration_str",
+        rename = "retry_max_ms"
+    )]
+    pub retry_max: Duration,
+    #[serde(
+        default = "ConfigWebhooksDispatcher::default_request_timeout",
+        deserialize_with = "deserialize_duration_str",
+        rename = "request_timeout_ms"
+    )]
+    pub request_timeout: Duration,
+}
+
+impl Default for ConfigWebhooksDispatcher {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            batch_size: Self::default_batch_size(),
+            poll_interval: Self::default_poll_interval(),
+            retry_base: Self::default_retry_base(),
+            retry_max: Self::default_retry_max(),
+            request_timeout: Self::default_request_timeout(),
+        }
+    }
+}
+
+impl ConfigWebhooksDispatcher {
+    pub const fn default_max_attempts() -> usize {
+        10
+    }
+
+    pub const fn default_batch_size() -> usize {
+        100
+    }
+
+    pub const fn default_poll_interval() -> Duration {
+        Duration::from_millis(500)
+    }
+
+    pub const fn default_retry_base() -> Duration {
+        Duration::from_millis(1_000)
+    }
+
+    pub const fn default_retry_max() -> Duration {
+        Duration::from_millis(3_600_000)
+    }
+
+    pub const fn default_request_timeout() -> Duration {
+        Duration::from_millis(5_000)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigWebhook {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub filter: ConfigEventFilter,
+}
+
+/// Empty lists match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfigEventFilter {
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub programs: Vec<String>,
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+impl ConfigEventFilter {
+    pub fn matches(&self, event: &DomainEvent) -> bool {
+        (self.projects.is_empty()
+            || event
+                .project
+                .as_ref()
+                .map_or(false, |project| self.projects.contains(project)))
+            && (self.programs.is_empty() || self.programs.contains(&event.program))
+            && (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+    }
+}
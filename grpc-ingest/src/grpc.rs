@@ -1,12 +1,20 @@
 use {
     crate::{
-        config::ConfigGrpc, prom::redis_xadd_status_inc, redis::metrics_xlen, util::create_shutdown,
+        bus::{MessageProducer, StreamEntry},
+        config::{ConfigGrpc, ConfigGrpcReconnect},
+        prom::{
+            grpc_checkpoint_slot_set, grpc_endpoint_connected_set, grpc_endpoints_total_set,
+            grpc_reconnect_inc, grpc_slot_gap_inc, redis_xadd_status_inc,
+        },
+        redis::{metrics_xlen, RedisProducer, RedisProducerStream},
+        slots::{Batch, SlotTracker},
+        util::create_shutdown,
     },
     anyhow::Context,
     futures::{channel::mpsc, stream::StreamExt, SinkExt},
-    log::{debug, error, info},
+    log::{debug, info},
     lru::LruCache,
-    redis::{streams::StreamMaxlen, RedisResult, Value as RedisValue},
+    redis::{aio::MultiplexedConnection, AsyncCommands, Value as RedisValue},
     std::{collections::HashMap, num::NonZeroUsize, sync::Arc, time::Duration},
     tokio::{
         spawn,
@@ -16,20 +24,122 @@ use {
     tracing::warn,
     yellowstone_grpc_client::GeyserGrpcClient,
     yellowstone_grpc_proto::{
-        geyser::SubscribeRequest, prelude::subscribe_update::UpdateOneof, prost::Message,
+        geyser::{CommitmentLevel, SubscribeRequest, SubscribeRequestFilterSlots},
+        prelude::subscribe_update::UpdateOneof,
+        prost::Message,
     },
     yellowstone_grpc_tools::config::GrpcRequestToProto,
 };
 
-pub struct GrpcStream {
-    endpoint_index: u8,
-    update: UpdateOneof,
+/// Stream of the transactions kept for `tools/redis_cache`, prefixed by the endpoint index.
+const TXN_CACHE_STREAM: &str = "TXN_CACHE";
+
+pub enum GrpcMessage {
+    /// The endpoint (re)subscribed, updates sent before its first one were missed.
+    Subscribed { endpoint_index: u8 },
+    Update {
+        endpoint_index: u8,
+        update: UpdateOneof,
+    },
+}
+
+/// Redis side of the [SlotTracker]: where each endpoint checkpoint is stored and where the slot
+/// ranges missed while an endpoint was disconnected are recorded.
+struct Checkpoints {
+    endpoints: Vec<(String, String)>,
+    gaps_stream: String,
+    slots: SlotTracker,
+}
+
+impl Checkpoints {
+    async fn load(
+        connection: &mut MultiplexedConnection,
+        config: &ConfigGrpc,
+    ) -> anyhow::Result<Self> {
+        let mut endpoints = Vec::with_capacity(config.geyser_endpoints.len());
+        let mut stored_slots = Vec::with_capacity(config.geyser_endpoints.len());
+        for endpoint in config.geyser_endpoints.iter() {
+            let key = format!("{}:{}", config.checkpoint.key_prefix, endpoint);
+            let stored: Option<u64> = connection
+                .get(&key)
+                .await
+                .with_context(|| format!("failed to load checkpoint {key}"))?;
+            if let Some(slot) = stored {
+                info!("resuming {} from checkpoint slot {}", endpoint, slot);
+                grpc_checkpoint_slot_set(endpoint, slot);
+            }
+            endpoints.push((endpoint.clone(), key));
+            stored_slots.push(stored);
+        }
+        Ok(Self {
+            endpoints,
+            gaps_stream: config.checkpoint.gaps_stream.clone(),
+            slots: SlotTracker::new(stored_slots),
+        })
+    }
+
+    /// Stores the checkpoints of the pipelines executed in order.
+    async fn complete(
+        &mut self,
+        connection: &mut MultiplexedConnection,
+        batch: Batch,
+    ) -> anyhow::Result<()> {
+        let updates = self.slots.complete(batch);
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
+        for (index, slot) in updates.iter() {
+            if let Some((_, key)) = self.endpoints.get(*index as usize) {
+                pipe.set(key, *slot);
+            }
+        }
+        let _: RedisValue = pipe
+            .query_async(connection)
+            .await
+            .context("failed to store checkpoints")?;
+        for (index, slot) in updates {
+            if let Some((endpoint, _)) = self.endpoints.get(index as usize) {
+                grpc_checkpoint_slot_set(endpoint, slot);
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the missed slots so they can be backfilled.
+    async fn record_gap(
+        &self,
+        connection: &mut MultiplexedConnection,
+        endpoint_index: u8,
+        (from_slot, to_slot): (u64, u64),
+    ) -> anyhow::Result<()> {
+        let Some((endpoint, _)) = self.endpoints.get(endpoint_index as usize) else {
+            return Ok(());
+        };
+        warn!("{endpoint} may have missed slots {from_slot} to {to_slot} while disconnected");
+        grpc_slot_gap_inc(endpoint, to_slot - from_slot + 1);
+        let _: RedisValue = connection
+            .xadd(
+                &self.gaps_stream,
+                "*",
+                &[
+                    ("endpoint", endpoint.clone()),
+                    ("from_slot", from_slot.to_string()),
+                    ("to_slot", to_slot.to_string()),
+                ],
+            )
+            .await
+            .context("failed to record slot gap")?;
+        Ok(())
+    }
 }
 
 pub async fn try_streaming_grpc_loop(
     config: &ConfigGrpc,
-    tx: &mut mpsc::Sender<GrpcStream>,
+    tx: &mut mpsc::Sender<GrpcMessage>,
     endpoint: (u8, &String),
+    attempt: &mut u32,
 ) -> anyhow::Result<()> {
     let mut client = GeyserGrpcClient::build_from_shared(endpoint.1.to_owned())?
         .x_token(config.x_token.clone())?
@@ -49,59 +159,68 @@ pub async fn try_streaming_grpc_loop(
         config.transactions.filter.clone().to_proto(),
     );
 
+    // Slot statuses tell which slots are confirmed, and so fully received.
+    let slots = HashMap::from([("das".to_string(), SubscribeRequestFilterSlots::default())]);
+
+    // The pinned yellowstone-grpc-proto has no `from_slot`, the slots missed since the checkpoint
+    // are recorded as a gap and backfilled by the `poll` action instead.
     let request = SubscribeRequest {
         accounts,
+        slots,
         transactions,
         ..Default::default()
     };
-    error!(
-        "subscribing to client {:?}, status {:?}",
-        endpoint,
+    info!(
+        "subscribing to {}, status {:?}",
+        endpoint.1,
         client.health_check().await?
     );
-    match client.subscribe_with_request(Some(request)).await {
-        Ok((_subscribe_tx, mut stream)) => {
-            error!("subscribtion sent to client {}", endpoint.1);
-
-            while let Some(resp) = stream.next().await {
-                match resp {
-                    Ok(msg) => {
-                        if let Some(update) = msg.update_oneof {
-                            error!("Got Data {:?}", update);
-                            tx.send(GrpcStream {
-                                endpoint_index: endpoint.0,
-                                update,
-                            })
-                            .await
-                            .expect("Failed to send update to management thread");
-                        } else {
-                            debug!("unhandled response from {:?}: {:?}", endpoint, msg);
-                        }
-                    }
-                    Err(error) => {
-                        debug!("got error from grpc {:?}: {}", endpoint, error);
-                    }
-                }
+    let (_subscribe_tx, mut stream) = client
+        .subscribe_with_request(Some(request))
+        .await
+        .context("failed to subscribe")?;
+    info!("subscribed to {}", endpoint.1);
+    *attempt = 0;
+    tx.send(GrpcMessage::Subscribed {
+        endpoint_index: endpoint.0,
+    })
+    .await?;
+
+    grpc_endpoint_connected_set(endpoint.1, true);
+    let result: anyhow::Result<()> = async {
+        while let Some(message) = stream.next().await {
+            let message = message.context("failed to receive update")?;
+            if let Some(update) = message.update_oneof {
+                tx.send(GrpcMessage::Update {
+                    endpoint_index: endpoint.0,
+                    update,
+                })
+                .await?;
+            } else {
+                debug!("unhandled response from {:?}: {:?}", endpoint, message);
             }
-            Ok::<(), anyhow::Error>(())
-        }
-        Err(error) => {
-            debug!(
-                "got error from grpc {:?} while connecting stream: {}",
-                endpoint, error
-            );
-            Err(error.into())
         }
+        Ok(())
     }
+    .await;
+    grpc_endpoint_connected_set(endpoint.1, false);
+    result
+}
+
+fn reconnect_delay(config: &ConfigGrpcReconnect, attempt: u32) -> Duration {
+    config
+        .backoff_base
+        .saturating_mul(2u32.pow(attempt.min(20)))
+        .min(config.backoff_max)
 }
 
 pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
     let config = Arc::new(config);
-    let (tx, mut rx) = mpsc::channel::<GrpcStream>(config.geyser_update_message_buffer_size); // Adjust buffer size as needed
+    let (tx, mut rx) = mpsc::channel::<GrpcMessage>(config.geyser_update_message_buffer_size); // Adjust buffer size as needed
 
     // Connect to Redis
     let client = redis::Client::open(config.redis.url.clone())?;
-    let connection = client.get_multiplexed_tokio_connection().await?;
+    let mut connection = client.get_multiplexed_tokio_connection().await?;
 
     // Check stream length for the metrics
     let jh_metrics_xlen = spawn({
@@ -114,24 +233,58 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
     });
     tokio::pin!(jh_metrics_xlen);
 
-    // Spawn gRPC client connections
+    let producer: Arc<dyn MessageProducer> = Arc::new(RedisProducer::new(
+        connection.clone(),
+        HashMap::from([
+            (
+                config.accounts.stream.clone(),
+                RedisProducerStream {
+                    maxlen: config.accounts.stream_maxlen,
+                    data_key: config.accounts.stream_data_key.clone(),
+                },
+            ),
+            (
+                config.transactions.stream.clone(),
+                RedisProducerStream {
+                    maxlen: config.transactions.stream_maxlen,
+                    data_key: config.transactions.stream_data_key.clone(),
+                },
+            ),
+            (
+                TXN_CACHE_STREAM.to_owned(),
+                RedisProducerStream {
+                    maxlen: config.transactions.stream_maxlen,
+                    data_key: config.transactions.stream_data_key.clone(),
+                },
+            ),
+        ]),
+    ));
+
+    let mut checkpoints = Checkpoints::load(&mut connection, &config).await?;
+    grpc_endpoints_total_set(config.geyser_endpoints.len());
+
+    // Spawn gRPC client connections, reconnecting with exponential backoff
     for (i, endpoint) in config.geyser_endpoints.iter().enumerate() {
         let config = Arc::clone(&config);
         let mut tx = tx.clone();
         let ep = endpoint.clone();
-        debug!("client conncted for {}", &ep);
-        let mut retry_count: usize = 0;
+        grpc_endpoint_connected_set(&ep, false);
         spawn(async move {
-            while retry_count < 10 {
-                match try_streaming_grpc_loop(&config, &mut tx, (i as u8, &ep)).await {
-                    Ok(_) => {
-                        error!("try_streaming_grpc_loop: Ended unexpectedly");
-                    }
-                    Err(err) => {
-                        error!("try_streaming_grpc_loop: Final Error, {}", err);
-                    }
+            let mut attempt: u32 = 0;
+            loop {
+                match try_streaming_grpc_loop(&config, &mut tx, (i as u8, &ep), &mut attempt).await
+                {
+                    Ok(()) => warn!("gRPC stream from {ep} ended"),
+                    Err(error) => warn!("gRPC stream from {ep} failed: {error:?}"),
+                }
+                if tx.is_closed() {
+                    break;
                 }
-                retry_count += 1;
+                let delay = reconnect_delay(&config.reconnect, attempt);
+                attempt = attempt.saturating_add(1);
+                grpc_reconnect_inc(&ep);
+                info!("reconnecting to {} in {:?}", ep, delay);
+                sleep(delay).await;
             }
         });
     }
@@ -139,7 +292,7 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
     // Management thread
     let mut shutdown = create_shutdown()?;
     let mut tasks = JoinSet::new();
-    let mut pipe = redis::pipe();
+    let mut pipe = Vec::new();
     let mut pipe_accounts = 0;
     let mut pipe_transactions = 0;
     let deadline = sleep(config.redis.pipeline_max_idle);
@@ -156,12 +309,49 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
                 Ok(Err(error)) => break Err(error),
                 Err(error) => break Err(error.into()),
             },
+            Some(result) = tasks.join_next() => {
+                let result = match result {
+                    Ok(Ok(batch)) => checkpoints.complete(&mut connection, batch).await,
+                    Ok(Err(error)) => Err(error),
+                    Err(error) => Err(error.into()),
+                };
+                if let Err(error) = result {
+                    break Err(error);
+                }
+            },
             Some(signal) = shutdown.next() => {
                 warn!("{signal} received, waiting spawned tasks...");
                 break Ok(());
             },
-            Some(GrpcStream { endpoint_index, update }) = rx.next() => {
+            Some(message) = rx.next() => {
+                let (endpoint_index, update) = match message {
+                    GrpcMessage::Subscribed { endpoint_index } => {
+                        checkpoints.slots.subscribed(endpoint_index);
+                        continue;
+                    }
+                    GrpcMessage::Update { endpoint_index, update } => (endpoint_index, update),
+                };
+                let slot = match &update {
+                    UpdateOneof::Account(account) => Some(account.slot),
+                    UpdateOneof::Transaction(transaction) => Some(transaction.slot),
+                    UpdateOneof::Slot(slot) => Some(slot.slot),
+                    _ => None,
+                };
+                if let Some(gap) = slot.and_then(|slot| checkpoints.slots.observe(endpoint_index, slot)) {
+                    if let Err(error) = checkpoints.record_gap(&mut connection, endpoint_index, gap).await {
+                        break Err(error);
+                    }
+                }
+
                 match update {
+                    UpdateOneof::Slot(slot) => {
+                        if slot.status == CommitmentLevel::Confirmed as i32
+                            || slot.status == CommitmentLevel::Finalized as i32
+                        {
+                            checkpoints.slots.confirmed(endpoint_index, slot.slot);
+                        }
+                        continue;
+                    }
                     UpdateOneof::Account(account) => {
                         let slot_pubkey = format!("{}:{}", account.slot, hex::encode(account.account.as_ref().map(|account| account.pubkey.clone()).unwrap_or_default()));
 
@@ -172,12 +362,10 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
                             seen_update_events.put(slot_pubkey, ());
                         };
 
-                        pipe.xadd_maxlen(
-                            &config.accounts.stream,
-                            StreamMaxlen::Approx(config.accounts.stream_maxlen),
-                            "*",
-                            &[(&config.accounts.stream_data_key, account.encode_to_vec())],
-                        );
+                        pipe.push(StreamEntry {
+                            stream: config.accounts.stream.clone(),
+                            data: account.encode_to_vec(),
+                        });
 
                         pipe_accounts += 1;
                     }
@@ -197,70 +385,71 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
                         if seen_update_events.get(&slot_signature).is_some() {
                             continue;
                         } else {
-                            info!("Adding new tx: {} {}", endpoint_index, &slot_signature);
+                            debug!("Adding new tx: {} {}", endpoint_index, &slot_signature);
 
                             seen_update_events.put(slot_signature, ());
                         };
 
-                        pipe.xadd_maxlen(
-                            &config.transactions.stream,
-                            StreamMaxlen::Approx(config.transactions.stream_maxlen),
-                            "*",
-                            &[(&config.transactions.stream_data_key, transaction.encode_to_vec())]
-                        );
-
-                        pipe.xadd_maxlen(
-                            &String::from("TXN_CACHE"),
-                            StreamMaxlen::Approx(config.transactions.stream_maxlen),
-                            "*",
-                            &[(&config.transactions.stream_data_key, [vec![endpoint_index], transaction.encode_to_vec()].concat())]
-                        );
+                        let data = transaction.encode_to_vec();
+                        let cached = [vec![endpoint_index], data.clone()].concat();
+                        pipe.push(StreamEntry {
+                            stream: config.transactions.stream.clone(),
+                            data,
+                        });
+                        pipe.push(StreamEntry {
+                            stream: TXN_CACHE_STREAM.to_owned(),
+                            data: cached,
+                        });
 
                         pipe_transactions += 1;
                     }
                     _ => continue,
                 }
                 if pipe_accounts + pipe_transactions >= config.redis.pipeline_max_size {
-                    let mut pipe = std::mem::replace(&mut pipe, redis::pipe());
+                    let pipe = std::mem::take(&mut pipe);
                     let pipe_accounts = std::mem::replace(&mut pipe_accounts, 0);
                     let pipe_transactions = std::mem::replace(&mut pipe_transactions, 0);
                     deadline.as_mut().reset(Instant::now() + config.redis.pipeline_max_idle);
+                    let batch = checkpoints.slots.cut_batch();
 
                     tasks.spawn({
-                        let mut connection = connection.clone();
+                        let publish = producer.publish(pipe);
                         let config = Arc::clone(&config);
                         async move {
-                            let result: RedisResult<RedisValue> =
-                                pipe.atomic().query_async(&mut connection).await;
+                            let result = publish.await;
 
-                            let status = result.map(|_| ()).map_err(|_| ());
+                            let status = result.as_ref().map(|_| ()).map_err(|_| ());
                             redis_xadd_status_inc(&config.accounts.stream, status, pipe_accounts);
                             redis_xadd_status_inc(&config.transactions.stream, status, pipe_transactions);
 
-                            Ok::<(), anyhow::Error>(())
+                            // Stop rather than checkpoint past updates that never reached Redis.
+                            result.context("failed to forward updates to Redis")?;
+                            Ok::<Batch, anyhow::Error>(batch)
                         }
                     });
                 }
             },
             _ = &mut deadline => {
                 if pipe_accounts + pipe_transactions > 0 {
-                    let mut pipe = std::mem::replace(&mut pipe, redis::pipe());
+                    let pipe = std::mem::take(&mut pipe);
                     let pipe_accounts = std::mem::replace(&mut pipe_accounts, 0);
                     let pipe_transactions = std::mem::replace(&mut pipe_transactions, 0);
                     deadline.as_mut().reset(Instant::now() + config.redis.pipeline_max_idle);
+                    let batch = checkpoints.slots.cut_batch();
 
                     tasks.spawn({
-                        let mut connection = connection.clone();
+                        let publish = producer.publish(pipe);
                         let config = Arc::clone(&config);
                         async move {
-                            let result: RedisResult<RedisValue> =
-                                pipe.atomic().query_async(&mut connection).await;
+                            let result = publish.await;
 
-                            let status = result.map(|_| ()).map_err(|_| ());
+                            let status = result.as_ref().map(|_| ()).map_err(|_| ());
                             redis_xadd_status_inc(&config.accounts.stream, status, pipe_accounts);
                             redis_xadd_status_inc(&config.transactions.stream, status, pipe_transactions);
 
-                            Ok::<(), anyhow::Error>(())
+                            // Stop rather than checkpoint past updates that never reached Redis.
+                            result.context("failed to forward updates to Redis")?;
+                            Ok::<Batch, anyhow::Error>(batch)
                         }
                     });
                 }
@@ -269,7 +458,7 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
 
         while tasks.len() >= config.redis.max_xadd_in_process {
             if let Some(result) = tasks.join_next().await {
-                result??;
+                checkpoints.complete(&mut connection, result??).await?;
             }
         }
     };
@@ -280,7 +469,7 @@ pub async fn run(config: ConfigGrpc) -> anyhow::Result<()> {
         }
         result = async move {
             while let Some(result) = tasks.join_next().await {
-                result??;
+                checkpoints.complete(&mut connection, result??).await?;
             }
             Ok::<(), anyhow::Error>(())
         } => result?,
@@ -1,13 +1,15 @@
 use {
     crate::{
-        config::{ConfigIngester, ConfigIngesterDownloadMetadata},
+        bus::{ConsumerTasks, MessageConsumer, ProgramTransformerInfo},
+        config::{ConfigIngester, ConfigIngesterDownloadMetadata, ConfigIngesterEvents},
         download_metadata::TASK_TYPE,
         postgres::{create_pool as pg_create_pool, metrics_pgpool},
         prom::{
             download_metadata_inserted_total_inc, program_transformer_task_status_inc,
-            program_transformer_tasks_total_set, ProgramTransformerTaskStatusKind,
+            program_transformer_tasks_total_set, redis_xadd_status_inc,
+            ProgramTransformerTaskStatusKind,
         },
-        redis::{metrics_xlen, ProgramTransformerInfo, RedisStream},
+        redis::{metrics_xlen, RedisStream},
         util::create_shutdown,
     },
     chrono::Utc,
@@ -19,9 +21,11 @@ use {
     },
     log::debug,
     program_transformers::{
-        error::ProgramTransformerError, DownloadMetadataInfo, DownloadMetadataNotifier,
-        ProgramTransformer,
+        error::ProgramTransformerError,
+        events::{DomainEvent, DomainEventNotifier},
+        DownloadMetadataInfo, DownloadMetadataNotifier, ProgramTransformer,
     },
+    redis::{aio::MultiplexedConnection, streams::StreamMaxlen, Value as RedisValue},
     sea_orm::{
         entity::{ActiveModelTrait, ActiveValue},
         error::{DbErr, RuntimeErr},
@@ -69,7 +73,6 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
             .collect::<Vec<_>>();
         async move { metrics_xlen(connection, &streams).await }
     });
-    tokio::pin!(jh_metrics_xlen);
 
     // open connection to postgres
     let pgpool = pg_create_pool(config.postgres).await?;
@@ -79,24 +82,70 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
     });
 
     // create redis stream reader
-    let (mut redis_messages, redis_tasks_fut) = RedisStream::new(config.redis, connection).await?;
-    tokio::pin!(redis_tasks_fut);
+    let events_connection = connection.clone();
+    let (redis_messages, redis_tasks_fut) = RedisStream::new(config.redis, connection).await?;
+    // stop on metrics errors as well
+    let redis_tasks_fut = async move {
+        tokio::select! {
+            result = redis_tasks_fut => result,
+            result = jh_metrics_xlen => match result {
+                Ok(Ok(_)) => unreachable!(),
+                Ok(Err(error)) => Err(error),
+                Err(error) => Err(error.into()),
+            },
+        }
+    };
 
     // program transforms related
-    let pt_accounts = Arc::new(ProgramTransformer::new(
+    let mut pt_accounts = ProgramTransformer::new(
         pgpool.clone(),
         create_download_metadata_notifier(pgpool.clone(), config.download_metadata)?,
         false,
-    ));
-    let pt_transactions = Arc::new(ProgramTransformer::new(
+    );
+    let mut pt_transactions = ProgramTransformer::new(
         pgpool.clone(),
         create_download_metadata_notifier(pgpool.clone(), config.download_metadata)?,
         config.program_transformer.transactions_cl_audits,
-    ));
-    let pt_max_tasks_in_process = config.program_transformer.max_tasks_in_process;
+    );
+    if let Some(events) = config.events {
+        pt_accounts = pt_accounts.with_event_notifier(create_event_notifier(
+            events_connection.clone(),
+            events.clone(),
+        ));
+        pt_transactions =
+            pt_transactions.with_event_notifier(create_event_notifier(events_connection, events));
+    }
+
+    let result = consume(
+        Box::new(redis_messages),
+        redis_tasks_fut.boxed().fuse(),
+        Arc::new(pt_accounts),
+        Arc::new(pt_transactions),
+        config.program_transformer.max_tasks_in_process,
+        inactivity_timeout,
+    )
+    .await;
+
+    // shutdown database connection
+    pgpool.close().await;
+    result
+}
+
+/// Handles `messages` with the program transformers, acking each one once handled, until the
+/// consumer is drained, a signal is received, its tasks fail or nothing was received for
+/// `inactivity_timeout`.
+pub async fn consume(
+    mut messages: Box<dyn MessageConsumer>,
+    messages_tasks_fut: ConsumerTasks,
+    pt_accounts: Arc<ProgramTransformer>,
+    pt_transactions: Arc<ProgramTransformer>,
+    pt_max_tasks_in_process: usize,
+    inactivity_timeout: Option<Duration>,
+) -> anyhow::Result<()> {
+    tokio::pin!(messages_tasks_fut);
     let mut pt_tasks = JoinSet::new();
     let pt_tasks_len = Arc::new(AtomicUsize::new(0));
-    tokio::spawn({
+    let jh_pt_tasks_len = tokio::spawn({
         let pt_tasks_len = Arc::clone(&pt_tasks_len);
         async move {
             loop {
@@ -109,14 +158,14 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
     // read and process messages in the loop
     let mut shutdown = create_shutdown()?;
     let mut last_activity = tokio::time::Instant::now();
-    loop {
+    let result = loop {
         // Check for inactivity and shutdown if necessary
         pt_tasks_len.store(pt_tasks.len(), Ordering::Relaxed);
 
-        let redis_messages_recv = if pt_tasks.len() == pt_max_tasks_in_process {
+        let messages_recv = if pt_tasks.len() == pt_max_tasks_in_process {
             pending().boxed()
         } else {
-            redis_messages.recv().boxed()
+            messages.recv()
         };
         let pt_tasks_next = if pt_tasks.is_empty() {
             pending().boxed()
@@ -135,17 +184,12 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
             }
 
             tokio::select! {
-                result = &mut jh_metrics_xlen => match result {
-                    Ok(Ok(_)) => unreachable!(),
-                    Ok(Err(error)) => break Err(error),
-                    Err(error) => break Err(error.into()),
-                },
                 Some(signal) = shutdown.next() => {
                     warn!("{signal} received, waiting spawned tasks...");
                     break Ok(());
                 },
-                result = &mut redis_tasks_fut => break result,
-                msg = redis_messages_recv => match msg {
+                result = &mut messages_tasks_fut => break result,
+                msg = messages_recv => match msg {
                     Some(msg) => {
                         last_activity = tokio::time::Instant::now();
                             debug!(
@@ -170,17 +214,12 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
             }
         } else {
             tokio::select! {
-                result = &mut jh_metrics_xlen => match result {
-                    Ok(Ok(_)) => unreachable!(),
-                    Ok(Err(error)) => break Err(error),
-                    Err(error) => break Err(error.into()),
-                },
                 Some(signal) = shutdown.next() => {
                     warn!("{signal} received, waiting spawned tasks...");
                     break Ok(());
                 },
-                result = &mut redis_tasks_fut => break result,
-                msg = redis_messages_recv => match msg {
+                result = &mut messages_tasks_fut => break result,
+                msg = messages_recv => match msg {
                     Some(msg) => {
                         msg
                     },
@@ -226,26 +265,32 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
                     };
                 }
 
-                match result {
-                    Ok(()) => program_transformer_task_status_inc(
-                        ProgramTransformerTaskStatusKind::Success,
-                    ),
+                let handled = match result {
+                    Ok(()) => {
+                        program_transformer_task_status_inc(
+                            ProgramTransformerTaskStatusKind::Success,
+                        );
+                        true
+                    }
                     Err(ProgramTransformerError::NotImplemented) => {
                         program_transformer_task_status_inc(
                             ProgramTransformerTaskStatusKind::NotImplemented,
-                        )
+                        );
+                        true
                     }
                     Err(ProgramTransformerError::DeserializationError(error)) => {
                         program_transformer_task_status_inc(
                             ProgramTransformerTaskStatusKind::DeserializationError,
                         );
-                        log_or_bail!(warn, "failed to deserialize", error)
+                        log_or_bail!(warn, "failed to deserialize", error);
+                        false
                     }
                     Err(ProgramTransformerError::ParsingError(error)) => {
                         program_transformer_task_status_inc(
                             ProgramTransformerTaskStatusKind::ParsingError,
                         );
-                        log_or_bail!(warn, "failed to parse", error)
+                        log_or_bail!(warn, "failed to parse", error);
+                        false
                     }
                     Err(ProgramTransformerError::DatabaseError(error)) => {
                         log_or_bail!(anyhow::bail, "database error for", error)
@@ -256,12 +301,18 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
                     Err(error) => {
                         log_or_bail!(anyhow::bail, "failed to handle", error)
                     }
-                }
+                };
 
-                msg.ack()
+                if handled {
+                    msg.ack()
+                } else {
+                    msg.skip()
+                }
             }
         });
-    }?;
+    };
+    jh_pt_tasks_len.abort();
+    result?;
 
     tokio::select! {
         Some(signal) = shutdown.next() => {
@@ -269,17 +320,15 @@ pub async fn run(config: ConfigIngester) -> anyhow::Result<()> {
         }
         result = async move {
             // shutdown `prefetch` channel (but not Receiver)
-            redis_messages.shutdown();
+            messages.shutdown();
             // wait all `program_transformer` spawned tasks
             while let Some(result) = pt_tasks.join_next().await {
                 result??;
             }
             // wait all `ack` spawned tasks
-            if !redis_tasks_fut.is_terminated() {
-                redis_tasks_fut.await?;
+            if !messages_tasks_fut.is_terminated() {
+                messages_tasks_fut.await?;
             }
-            // shutdown database connection
-            pgpool.close().await;
             Ok::<(), anyhow::Error>(())
         } => result,
     }
@@ -329,3 +378,41 @@ pub fn create_download_metadata_notifier(
         })
     }))
 }
+
+/// Publishes domain events to a Redis stream. `event_type`, `program` and `project` are stored as
+/// separate fields so consumers can filter without decoding `data`.
+pub fn create_event_notifier(
+    connection: MultiplexedConnection,
+    config: ConfigIngesterEvents,
+) -> DomainEventNotifier {
+    Box::new(move |events: Vec<DomainEvent>| -> BoxFuture<
+        'static,
+        Result<(), Box<dyn std::error::Error + Send + Sync>>,
+    > {
+        let mut connection = connection.clone();
+        let stream = config.stream.clone();
+        let maxlen = config.stream_maxlen;
+        Box::pin(async move {
+            let count = events.len();
+            let mut pipe = redis::pipe();
+            for event in events {
+                let data = serde_json::to_vec(&event)?;
+                pipe.xadd_maxlen(
+                    &stream,
+                    StreamMaxlen::Approx(maxlen),
+                    "*",
+                    &[
+                        ("event_type", event.event_type.into_bytes()),
+                        ("program", event.program.into_bytes()),
+                        ("project", event.project.unwrap_or_default().into_bytes()),
+                        ("data", data),
+                    ],
+                );
+            }
+            let result = pipe.query_async::<_, RedisValue>(&mut connection).await;
+            redis_xadd_status_inc(&stream, result.as_ref().map(|_| ()).map_err(|_| ()), count);
+            result?;
+            Ok(())
+        })
+    })
+}
@@ -1,10 +1,16 @@
+pub mod bus;
 pub mod config;
 pub mod download_metadata;
 pub mod ingester;
+pub mod local;
+pub mod poll;
 pub mod postgres;
 pub mod prom;
 pub mod redis;
+pub mod replay;
+pub mod slots;
 pub mod util;
 pub mod version;
+pub mod webhooks;
 
 pub use ingester::create_download_metadata_notifier;
@@ -0,0 +1,244 @@
+use {
+    crate::{
+        bus::{MemoryBus, MessageProducer, StreamEntry},
+        config::{
+            ConfigDownloadMetadata, ConfigIngesterDownloadMetadata, ConfigIngesterRedisStreamType,
+            ConfigLocal, ConfigLocalReplay, ConfigLocalSource, ConfigPollSource,
+            REDIS_STREAM_ACCOUNTS, REDIS_STREAM_TRANSACTIONS,
+        },
+        download_metadata,
+        ingester::{consume, create_download_metadata_notifier},
+        poll::Poller,
+        postgres::{create_pool as pg_create_pool, metrics_pgpool},
+        replay::{load_updates, ReplayUpdate},
+    },
+    anyhow::Context,
+    futures::future::{pending, FutureExt},
+    migration::{Migrator, MigratorTrait},
+    program_transformers::{ProgramTransformer, TransactionInfo},
+    sea_orm::SqlxPostgresConnector,
+    std::sync::Arc,
+    tokio::time::{interval, MissedTickBehavior},
+    tracing::{info, warn},
+    yellowstone_grpc_proto::{
+        prelude::{
+            CompiledInstruction, InnerInstruction, InnerInstructions, Message,
+            SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, Transaction,
+            TransactionStatusMeta,
+        },
+        prost::Message as _,
+    },
+};
+
+pub async fn run(config: ConfigLocal) -> anyhow::Result<()> {
+    // open connection to postgres and bring the schema up to date
+    let pgpool = pg_create_pool(config.postgres.clone()).await?;
+    tokio::spawn({
+        let pgpool = pgpool.clone();
+        async move { metrics_pgpool(pgpool).await }
+    });
+    if let Some(init_file) = &config.init_file {
+        migration::set_init_file(init_file);
+    }
+    let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pgpool.clone());
+    Migrator::up(&conn, None)
+        .await
+        .context("failed to apply migrations")?;
+    info!("migrations applied");
+
+    // serve the API from the same database
+    let api = das_api::server::start(das_api::config::Config {
+        database_url: config.postgres.url.clone(),
+        server_port: config.api.port,
+        ..Default::default()
+    })
+    .await?;
+    info!("API listening on port {}", config.api.port);
+
+    let mut jh_download_metadata = tokio::spawn(download_metadata::run(ConfigDownloadMetadata {
+        postgres: config.postgres,
+        download_metadata: config.download_metadata,
+    }));
+
+    // the source publishes to in-process streams read by the ingester
+    let bus = MemoryBus::new(config.queue_size);
+    let (messages, messages_tasks_fut) = bus.consumer(
+        &[
+            (
+                REDIS_STREAM_ACCOUNTS.to_owned(),
+                ConfigIngesterRedisStreamType::Account,
+            ),
+            (
+                REDIS_STREAM_TRANSACTIONS.to_owned(),
+                ConfigIngesterRedisStreamType::Transaction,
+            ),
+        ],
+        config.program_transformer.max_tasks_in_process,
+    );
+    let pt_download_metadata = ConfigIngesterDownloadMetadata {
+        max_attempts: ConfigIngesterDownloadMetadata::default_max_attempts(),
+    };
+    let pt_accounts = ProgramTransformer::new(
+        pgpool.clone(),
+        create_download_metadata_notifier(pgpool.clone(), pt_download_metadata)?,
+        false,
+    );
+    let pt_transactions = ProgramTransformer::new(
+        pgpool.clone(),
+        create_download_metadata_notifier(pgpool.clone(), pt_download_metadata)?,
+        config.program_transformer.transactions_cl_audits,
+    );
+    let source = match config.source {
+        ConfigLocalSource::Replay(replay) => run_replay(bus, replay).boxed(),
+        ConfigLocalSource::Poll(poll) => run_poll(bus, poll).boxed(),
+    };
+
+    // sources only return on errors, the ingester and the downloader on signals and errors
+    let result = tokio::select! {
+        result = consume(
+            Box::new(messages),
+            messages_tasks_fut,
+            Arc::new(pt_accounts),
+            Arc::new(pt_transactions),
+            config.program_transformer.max_tasks_in_process,
+            None,
+        ) => result,
+        result = source => result,
+        result = &mut jh_download_metadata => match result {
+            Ok(result) => result.context("metadata downloader failed"),
+            Err(error) => Err(error).context("metadata downloader panicked"),
+        },
+    };
+
+    api.stop()?;
+    api.stopped().await;
+    jh_download_metadata.abort();
+    pgpool.close().await;
+    result
+}
+
+/// Publishes the dump once, the API keeps serving it afterwards.
+async fn run_replay(bus: MemoryBus, config: ConfigLocalReplay) -> anyhow::Result<()> {
+    let updates = load_updates(&config.file, config.format, config.sort).await?;
+    let count = updates.len();
+    // published one by one, the bus makes the dump wait for the ingester
+    for update in updates {
+        let entry = match update {
+            ReplayUpdate::Account(update) => StreamEntry {
+                stream: REDIS_STREAM_ACCOUNTS.to_owned(),
+                data: update.encode_to_vec(),
+            },
+            ReplayUpdate::Transaction(update) => StreamEntry {
+                stream: REDIS_STREAM_TRANSACTIONS.to_owned(),
+                data: update.encode_to_vec(),
+            },
+        };
+        bus.publish(vec![entry]).await?;
+    }
+
+    bus.drained(REDIS_STREAM_ACCOUNTS).await;
+    bus.drained(REDIS_STREAM_TRANSACTIONS).await;
+    info!("replayed {count} updates from {}", config.file.display());
+    pending().await
+}
+
+/// Publishes the transactions polled every round, a page is checkpointed once the ingester acked
+/// all of its transactions.
+///
+/// Like the `poll` action, transactions that fail to parse are skipped for good. Other failures
+/// stop the ingester, so the page is polled again on restart.
+async fn run_poll(bus: MemoryBus, config: ConfigPollSource) -> anyhow::Result<()> {
+    let mut poller = Poller::new(&config).await?;
+    let mut rounds = interval(config.interval);
+    rounds.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        rounds.tick().await;
+        'addresses: for address in poller.addresses().to_vec() {
+            loop {
+                let batch = match poller.poll(address).await {
+                    Ok(batch) => batch,
+                    Err(error) => {
+                        warn!("failed to poll {address}: {error:?}");
+                        continue 'addresses;
+                    }
+                };
+
+                let entries = batch.transactions.iter().map(transaction_entry).collect();
+                let skipped = bus.skipped();
+                bus.publish(entries).await?;
+                bus.drained(REDIS_STREAM_TRANSACTIONS).await;
+
+                let skipped = bus.skipped() - skipped;
+                if skipped > 0 {
+                    warn!("skipped {skipped} transactions of {address} that failed to parse");
+                }
+                let has_more = batch.has_more();
+                poller.commit(batch).await?;
+                if !has_more {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Entry of the transactions stream holding the transaction.
+pub fn transaction_entry(transaction: &TransactionInfo) -> StreamEntry {
+    StreamEntry {
+        stream: REDIS_STREAM_TRANSACTIONS.to_owned(),
+        data: transaction_update_from_info(transaction).encode_to_vec(),
+    }
+}
+
+/// Inverse of `transaction_info_from_update`, the loaded addresses are kept in the account keys.
+pub fn transaction_update_from_info(transaction: &TransactionInfo) -> SubscribeUpdateTransaction {
+    let signature = transaction.signature.as_ref().to_vec();
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: signature.clone(),
+            transaction: Some(Transaction {
+                signatures: vec![signature],
+                message: Some(Message {
+                    account_keys: transaction
+                        .account_keys
+                        .iter()
+                        .map(|key| key.to_bytes().to_vec())
+                        .collect(),
+                    instructions: transaction
+                        .message_instructions
+                        .iter()
+                        .map(|ix| CompiledInstruction {
+                            program_id_index: u32::from(ix.program_id_index),
+                            accounts: ix.accounts.clone(),
+                            data: ix.data.clone(),
+                        })
+                        .collect(),
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                inner_instructions: transaction
+                    .meta_inner_instructions
+                    .iter()
+                    .map(|inner| InnerInstructions {
+                        index: u32::from(inner.index),
+                        instructions: inner
+                            .instructions
+                            .iter()
+                            .map(|ix| InnerInstruction {
+                                program_id_index: u32::from(ix.instruction.program_id_index),
+                                accounts: ix.instruction.accounts.clone(),
+                                data: ix.instruction.data.clone(),
+                                stack_height: ix.stack_height,
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        slot: transaction.slot,
+    }
+}
@@ -1,10 +1,11 @@
 use {
     crate::{
         config::{
-            load as config_load, ConfigDownloadMetadata, ConfigGrpc, ConfigIngester,
-            ConfigPrometheus,
+            load as config_load, ConfigDownloadMetadata, ConfigGrpc, ConfigIngester, ConfigLocal,
+            ConfigPoll, ConfigPrometheus, ConfigReplay, ConfigWebhooks,
         },
         prom::run_server as prometheus_run_server,
+        replay::ArgsReplay,
         tracing::init as tracing_init,
     },
     anyhow::Context,
@@ -12,16 +13,22 @@ use {
     std::net::SocketAddr,
 };
 
+mod bus;
 mod config;
 mod download_metadata;
 mod grpc;
 mod ingester;
+mod local;
+mod poll;
 mod postgres;
 mod prom;
 mod redis;
+mod replay;
+mod slots;
 mod tracing;
 mod util;
 mod version;
+mod webhooks;
 
 #[derive(Debug, Parser)]
 #[clap(author, version)]
@@ -49,6 +56,18 @@ enum ArgsAction {
     /// Run metadata downloader
     #[command(name = "download-metadata")]
     DownloadMetadata,
+    /// Deliver published domain events to configured HTTP webhooks
+    #[command(name = "webhooks")]
+    Webhooks,
+    /// Run a dump of transactions and account updates through the program transformers
+    #[command(name = "replay")]
+    Replay(ArgsReplay),
+    /// Follow the signatures of programs and trees on a JSON-RPC node, without Geyser or Redis
+    #[command(name = "poll")]
+    Poll,
+    /// Run migrations, ingester, metadata downloader and API in one process, only Postgres is needed
+    #[command(name = "local")]
+    Local,
 }
 
 #[tokio::main]
@@ -65,7 +84,7 @@ async fn main() -> anyhow::Result<()> {
         prometheus_run_server(address)?;
     }
 
-    // Run grpc / ingester / download-metadata
+    // Run grpc / ingester / download-metadata / webhooks / replay / poll / local
     match args.action {
         ArgsAction::Grpc => {
             let config = config_load::<ConfigGrpc>(&args.config)
@@ -86,5 +105,29 @@ async fn main() -> anyhow::Result<()> {
                 .with_context(|| format!("failed to parse config from: {}", args.config))?;
             download_metadata::run(config).await
         }
+        ArgsAction::Webhooks => {
+            let config = config_load::<ConfigWebhooks>(&args.config)
+                .await
+                .with_context(|| format!("failed to parse config from: {}", args.config))?;
+            webhooks::run(config).await
+        }
+        ArgsAction::Replay(replay_args) => {
+            let config = config_load::<ConfigReplay>(&args.config)
+                .await
+                .with_context(|| format!("failed to parse config from: {}", args.config))?;
+            replay::run(config, replay_args).await
+        }
+        ArgsAction::Poll => {
+            let config = config_load::<ConfigPoll>(&args.config)
+                .await
+                .with_context(|| format!("failed to parse config from: {}", args.config))?;
+            poll::run(config).await
+        }
+        ArgsAction::Local => {
+            let config = config_load::<ConfigLocal>(&args.config)
+                .await
+                .with_context(|| format!("failed to parse config from: {}", args.config))?;
+            local::run(config).await
+        }
     }
 }
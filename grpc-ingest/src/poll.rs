@@ -0,0 +1,640 @@
+use {
+    crate::{
+        config::{ConfigPoll, ConfigPollGaps, ConfigPollSource},
+        ingester::create_download_metadata_notifier,
+        postgres::{create_pool as pg_create_pool, metrics_pgpool},
+        prom::{poll_checkpoint_slot_set, poll_transactions_inc},
+        redis::xgroup_create,
+        util::create_shutdown,
+    },
+    anyhow::Context,
+    futures::stream::StreamExt,
+    lru::LruCache,
+    program_transformers::{
+        error::ProgramTransformerError, rpc::transaction_info_from_rpc, ProgramTransformer,
+        TransactionInfo,
+    },
+    redis::{
+        aio::MultiplexedConnection,
+        streams::{StreamReadOptions, StreamReadReply},
+        AsyncCommands,
+    },
+    serde::{Deserialize, Serialize},
+    solana_client::{
+        client_error::Result as ClientResult, nonblocking::rpc_client::RpcClient,
+        rpc_client::GetConfirmedSignaturesForAddress2Config, rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    solana_transaction_status::UiTransactionEncoding,
+    std::{
+        collections::{BTreeMap, HashMap},
+        future::Future,
+        num::NonZeroUsize,
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::Duration,
+    },
+    tokio::{
+        fs,
+        time::{interval, sleep, Interval, MissedTickBehavior},
+    },
+    tracing::{info, warn},
+};
+
+/// `getSignaturesForAddress` returns at most this many signatures.
+const SIGNATURES_PAGE_LIMIT: usize = 1_000;
+
+const RPC_TIMEOUT: Duration = Duration::from_secs(60);
+const RPC_ATTEMPTS: usize = 4;
+const RPC_RETRY_MIN_DELAY: Duration = Duration::from_millis(100);
+const RPC_RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+const SEEN_SIGNATURES_CACHE_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    #[serde(with = "signature_str")]
+    pub signature: Signature,
+    pub slot: u64,
+}
+
+mod signature_str {
+    use {
+        serde::{de, Deserialize, Deserializer, Serializer},
+        solana_sdk::signature::Signature,
+        std::str::FromStr,
+    };
+
+    pub fn serialize<S: Serializer>(
+        signature: &Signature,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(signature)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Signature, D::Error> {
+        let signature = String::deserialize(deserializer)?;
+        Signature::from_str(&signature).map_err(de::Error::custom)
+    }
+}
+
+/// Last signature handled for every address, persisted as JSON.
+#[derive(Debug)]
+pub struct Checkpoints {
+    path: PathBuf,
+    checkpoints: BTreeMap<String, Checkpoint>,
+}
+
+impl Checkpoints {
+    pub async fn load(path: &Path) -> anyhow::Result<Self> {
+        let checkpoints = match fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("invalid checkpoint file {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("failed to read checkpoints {}", path.display()))
+            }
+        };
+        Ok(Self {
+            path: path.to_owned(),
+            checkpoints,
+        })
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<Checkpoint> {
+        self.checkpoints.get(&address.to_string()).copied()
+    }
+
+    async fn store(&mut self, address: &Pubkey, checkpoint: Checkpoint) -> anyhow::Result<()> {
+        self.checkpoints.insert(address.to_string(), checkpoint);
+
+        // Written aside and renamed so a crash never leaves a truncated file.
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.checkpoints)?)
+            .await
+            .with_context(|| format!("failed to write checkpoints {}", self.path.display()))?;
+        fs::rename(&tmp, &self.path)
+            .await
+            .with_context(|| format!("failed to write checkpoints {}", self.path.display()))
+    }
+}
+
+/// Transactions of one page of signatures since the checkpoint of an address, oldest first.
+#[derive(Debug)]
+pub struct PollBatch {
+    pub address: Pubkey,
+    pub transactions: Vec<TransactionInfo>,
+    signatures: Vec<Signature>,
+    checkpoint: Option<Checkpoint>,
+    /// `before` of the page when older than the latest one.
+    cursor: Option<Signature>,
+}
+
+impl PollBatch {
+    /// Whether newer signatures are left to poll once the batch is committed.
+    pub const fn has_more(&self) -> bool {
+        self.cursor.is_some()
+    }
+}
+
+/// Follows `getSignaturesForAddress` of every configured address.
+///
+/// Signatures are fetched one page at a time, oldest first, and a page is only checkpointed by
+/// [Poller::commit] once its transactions were handled, so they are fetched again after a
+/// failure or restart. When more than a page was signed since the checkpoint, only the `before`
+/// cursor of every older page is kept while walking back to it.
+pub struct Poller {
+    rpc: RpcClient,
+    rate_limit: Interval,
+    addresses: Vec<Pubkey>,
+    backfill: bool,
+    page_limit: usize,
+    checkpoints: Checkpoints,
+    /// Cursors of the pages left before the latest one, oldest last.
+    cursors: HashMap<Pubkey, Vec<Signature>>,
+    seen: LruCache<Signature, ()>,
+}
+
+impl Poller {
+    pub async fn new(config: &ConfigPollSource) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            config.max_requests_per_second > 0,
+            "`max_requests_per_second` should be positive"
+        );
+        anyhow::ensure!(
+            (1..=SIGNATURES_PAGE_LIMIT).contains(&config.signatures_page_limit),
+            "`signatures_page_limit` should be between 1 and {SIGNATURES_PAGE_LIMIT}"
+        );
+        let addresses = config
+            .addresses
+            .iter()
+            .map(|address| {
+                Pubkey::from_str(address).with_context(|| format!("invalid address {address}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut rate_limit = interval(Duration::from_secs_f64(
+            1.0 / config.max_requests_per_second as f64,
+        ));
+        rate_limit.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let checkpoints = Checkpoints::load(&config.checkpoint_file).await?;
+        for address in addresses.iter() {
+            if let Some(checkpoint) = checkpoints.get(address) {
+                info!(
+                    "resuming {} from signature {} at slot {}",
+                    address, checkpoint.signature, checkpoint.slot
+                );
+                poll_checkpoint_slot_set(&address.to_string(), checkpoint.slot);
+            }
+        }
+
+        Ok(Self {
+            rpc: RpcClient::new_with_timeout(config.rpc_url.clone(), RPC_TIMEOUT),
+            rate_limit,
+            addresses,
+            backfill: config.backfill,
+            page_limit: config.signatures_page_limit,
+            checkpoints,
+            cursors: HashMap::new(),
+            seen: LruCache::new(
+                NonZeroUsize::new(SEEN_SIGNATURES_CACHE_SIZE).expect("Non zero value"),
+            ),
+        })
+    }
+
+    pub fn addresses(&self) -> &[Pubkey] {
+        &self.addresses
+    }
+
+    /// Fetches the successful transactions of the oldest page of `address` signed since its
+    /// checkpoint. Without a checkpoint the batch is empty and starts following from the latest
+    /// signature, unless `backfill` is set.
+    pub async fn poll(&mut self, address: Pubkey) -> anyhow::Result<PollBatch> {
+        let until = self.checkpoints.get(&address).map(|c| c.signature);
+
+        let (cursor, page) = loop {
+            let cursor = self
+                .cursors
+                .get(&address)
+                .and_then(|cursors| cursors.last().copied());
+            let page = self.signatures(&address, cursor, until).await?;
+
+            if cursor.is_none() && until.is_none() && !self.backfill {
+                return Ok(PollBatch {
+                    address,
+                    transactions: vec![],
+                    signatures: vec![],
+                    checkpoint: page
+                        .first()
+                        .map(|&(signature, slot, _)| Checkpoint { signature, slot }),
+                    cursor: None,
+                });
+            }
+
+            if cursor.is_none() && page.len() == self.page_limit {
+                // Walk back to the checkpoint, the older pages are fetched again oldest first.
+                let mut cursors = Vec::new();
+                let mut before = page.last().map(|&(signature, _, _)| signature);
+                while let Some(cursor) = before {
+                    let older = self.signatures(&address, Some(cursor), until).await?;
+                    if older.is_empty() {
+                        break;
+                    }
+                    cursors.push(cursor);
+                    before = if older.len() == self.page_limit {
+                        older.last().map(|&(signature, _, _)| signature)
+                    } else {
+                        None
+                    };
+                }
+                if !cursors.is_empty() {
+                    info!(
+                        "{address} is {} pages behind its checkpoint",
+                        cursors.len() + 1
+                    );
+                    self.cursors.insert(address, cursors);
+                    continue;
+                }
+            }
+            break (cursor, page);
+        };
+
+        // Signatures are returned newest first.
+        let mut batch = PollBatch {
+            address,
+            transactions: Vec::with_capacity(page.len()),
+            signatures: Vec::with_capacity(page.len()),
+            checkpoint: page
+                .first()
+                .map(|&(signature, slot, _)| Checkpoint { signature, slot }),
+            cursor,
+        };
+        for (signature, _slot, succeeded) in page.into_iter().rev() {
+            if !succeeded || self.seen.contains(&signature) {
+                continue;
+            }
+            if let Some(transaction) = self.fetch_transaction(&signature).await? {
+                batch.transactions.push(transaction);
+            }
+            batch.signatures.push(signature);
+        }
+        poll_transactions_inc(&address.to_string(), batch.transactions.len());
+        Ok(batch)
+    }
+
+    /// Fetches the successful transactions of `address` in the inclusive slot range, oldest
+    /// first, walking its signatures back from the latest one. Checkpoints are left untouched.
+    pub async fn poll_slots(
+        &mut self,
+        address: Pubkey,
+        from_slot: u64,
+        to_slot: u64,
+    ) -> anyhow::Result<Vec<TransactionInfo>> {
+        let mut signatures = Vec::new();
+        let mut before = None;
+        'pages: loop {
+            let page = self.signatures(&address, before, None).await?;
+            let last_page = page.len() < self.page_limit;
+            for (signature, slot, succeeded) in page {
+                if slot < from_slot {
+                    break 'pages;
+                }
+                before = Some(signature);
+                if slot <= to_slot && succeeded {
+                    signatures.push(signature);
+                }
+            }
+            if last_page {
+                break;
+            }
+        }
+
+        let mut transactions = Vec::with_capacity(signatures.len());
+        for signature in signatures.into_iter().rev() {
+            if let Some(transaction) = self.fetch_transaction(&signature).await? {
+                transactions.push(transaction);
+            }
+        }
+        poll_transactions_inc(&address.to_string(), transactions.len());
+        Ok(transactions)
+    }
+
+    /// One page of signatures, newest first, with their slot and whether they succeeded.
+    async fn signatures(
+        &mut self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+    ) -> anyhow::Result<Vec<(Signature, u64, bool)>> {
+        let limit = Some(self.page_limit);
+        let page = rate_limited(&mut self.rate_limit, || {
+            self.rpc.get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit,
+                    commitment: Some(CommitmentConfig::finalized()),
+                },
+            )
+        })
+        .await
+        .with_context(|| format!("failed to get signatures of {address}"))?;
+        page.into_iter()
+            .map(|status| {
+                let signature = Signature::from_str(&status.signature)
+                    .with_context(|| format!("invalid signature {}", status.signature))?;
+                Ok((signature, status.slot, status.err.is_none()))
+            })
+            .collect()
+    }
+
+    async fn fetch_transaction(
+        &mut self,
+        signature: &Signature,
+    ) -> anyhow::Result<Option<TransactionInfo>> {
+        let transaction = rate_limited(&mut self.rate_limit, || {
+            self.rpc.get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base58),
+                    commitment: Some(CommitmentConfig::finalized()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+        })
+        .await
+        .with_context(|| format!("failed to get transaction {signature}"))?;
+        // Failed transactions are not indexed, like in the Bubblegum backfiller.
+        match &transaction.transaction.meta {
+            Some(meta) if meta.err.is_none() => {}
+            _ => return Ok(None),
+        }
+        transaction_info_from_rpc(transaction)
+            .map(Some)
+            .with_context(|| format!("failed to decode transaction {signature}"))
+    }
+
+    /// Moves the checkpoint of the batch address past its transactions.
+    pub async fn commit(&mut self, batch: PollBatch) -> anyhow::Result<()> {
+        for signature in batch.signatures {
+            self.seen.put(signature, ());
+        }
+        if let Some(cursor) = batch.cursor {
+            if let Some(cursors) = self.cursors.get_mut(&batch.address) {
+                if cursors.last() == Some(&cursor) {
+                    cursors.pop();
+                }
+                if cursors.is_empty() {
+                    self.cursors.remove(&batch.address);
+                }
+            }
+        }
+        let Some(checkpoint) = batch.checkpoint else {
+            return Ok(());
+        };
+        self.checkpoints.store(&batch.address, checkpoint).await?;
+        poll_checkpoint_slot_set(&batch.address.to_string(), checkpoint.slot);
+        Ok(())
+    }
+}
+
+/// Runs an RPC request once the rate limit allows it, retrying failures with an exponential
+/// backoff. Retries wait for the rate limit too so they never exceed `max_requests_per_second`.
+async fn rate_limited<T, F, Fut>(rate_limit: &mut Interval, mut request: F) -> ClientResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ClientResult<T>>,
+{
+    let mut delay = RPC_RETRY_MIN_DELAY;
+    let mut attempt = 1;
+    loop {
+        rate_limit.tick().await;
+        match request().await {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt < RPC_ATTEMPTS => {
+                warn!("RPC request failed, retrying in {delay:?}: {error}");
+                sleep(delay).await;
+                delay = (delay * 2).min(RPC_RETRY_MAX_DELAY);
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+pub async fn run(config: ConfigPoll) -> anyhow::Result<()> {
+    let pgpool = pg_create_pool(config.postgres).await?;
+    tokio::spawn({
+        let pgpool = pgpool.clone();
+        async move { metrics_pgpool(pgpool).await }
+    });
+    let program_transformer = ProgramTransformer::new(
+        pgpool.clone(),
+        create_download_metadata_notifier(pgpool, config.download_metadata)?,
+        config.program_transformer.transactions_cl_audits,
+    );
+
+    let mut poller = Poller::new(&config.poll).await?;
+    let mut gaps = match config.gaps {
+        Some(config) => Some(Gaps::connect(config).await?),
+        None => None,
+    };
+    let mut rounds = interval(config.poll.interval);
+    rounds.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut shutdown = create_shutdown()?;
+
+    loop {
+        tokio::select! {
+            Some(signal) = shutdown.next() => {
+                warn!("{signal} received, stopping");
+                break Ok(());
+            },
+            result = async {
+                rounds.tick().await;
+                poll_round(&mut poller, &program_transformer).await?;
+                match gaps.as_mut() {
+                    Some(gaps) => backfill_gaps(&mut poller, gaps, &program_transformer).await,
+                    None => Ok(()),
+                }
+            } => result?,
+        }
+    }
+}
+
+async fn poll_round(
+    poller: &mut Poller,
+    program_transformer: &ProgramTransformer,
+) -> anyhow::Result<()> {
+    'addresses: for address in poller.addresses().to_vec() {
+        loop {
+            let batch = match poller.poll(address).await {
+                Ok(batch) => batch,
+                Err(error) => {
+                    warn!("failed to poll {address}: {error:?}");
+                    continue 'addresses;
+                }
+            };
+
+            // Retried on the next round, the checkpoint stays before the batch.
+            if !handle_transactions(program_transformer, &batch.transactions).await {
+                continue 'addresses;
+            }
+            let has_more = batch.has_more();
+            poller.commit(batch).await?;
+            if !has_more {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs the transactions through the program transformers, `false` when one of them failed and
+/// should be retried. Transactions that can't be parsed are skipped.
+async fn handle_transactions(
+    program_transformer: &ProgramTransformer,
+    transactions: &[TransactionInfo],
+) -> bool {
+    for transaction in transactions {
+        match program_transformer.handle_transaction(transaction).await {
+            Ok(()) | Err(ProgramTransformerError::NotImplemented) => {}
+            Err(
+                error @ (ProgramTransformerError::DeserializationError(_)
+                | ProgramTransformerError::ParsingError(_)),
+            ) => {
+                warn!(
+                    "failed to handle transaction {}: {error:?}",
+                    transaction.signature
+                );
+            }
+            Err(error) => {
+                warn!(
+                    "failed to handle transaction {}: {error:?}",
+                    transaction.signature
+                );
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Slot range `grpc2redis` may have missed, both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotGap {
+    pub from_slot: u64,
+    pub to_slot: u64,
+}
+
+/// Reads the gaps recorded by `grpc2redis`. A gap stays pending until it is acked, and pending
+/// gaps are read again on every round.
+pub struct Gaps {
+    connection: MultiplexedConnection,
+    config: ConfigPollGaps,
+    read_id: String,
+}
+
+impl Gaps {
+    pub async fn connect(config: ConfigPollGaps) -> anyhow::Result<Self> {
+        let client = redis::Client::open(config.url.clone())?;
+        let mut connection = client.get_multiplexed_tokio_connection().await?;
+        xgroup_create(
+            &mut connection,
+            &config.stream,
+            &config.group,
+            &config.consumer,
+        )
+        .await?;
+        Ok(Self {
+            connection,
+            config,
+            read_id: "0".to_owned(),
+        })
+    }
+
+    /// Starts over from the first pending gap.
+    pub fn rewind(&mut self) {
+        self.read_id = "0".to_owned();
+    }
+
+    /// Returns the next pending gap, then the new ones, with their entry id.
+    pub async fn next(&mut self) -> anyhow::Result<Option<(String, SlotGap)>> {
+        loop {
+            let opts = StreamReadOptions::default()
+                .count(1)
+                .group(&self.config.group, &self.config.consumer);
+            let reply: StreamReadReply = self
+                .connection
+                .xread_options(&[&self.config.stream], &[&self.read_id], &opts)
+                .await
+                .context("failed to read slot gaps")?;
+            let Some(entry) = reply.keys.into_iter().flat_map(|key| key.ids).next() else {
+                if self.read_id == ">" {
+                    return Ok(None);
+                }
+                // pending gaps are read, switch to new ones
+                self.read_id = ">".to_owned();
+                continue;
+            };
+            if self.read_id != ">" {
+                self.read_id = entry.id.clone();
+            }
+
+            match (entry.get("from_slot"), entry.get("to_slot")) {
+                (Some(from_slot), Some(to_slot)) => {
+                    return Ok(Some((entry.id, SlotGap { from_slot, to_slot })))
+                }
+                _ => {
+                    warn!("dropping malformed slot gap {}", entry.id);
+                    self.ack(&entry.id).await?;
+                }
+            }
+        }
+    }
+
+    pub async fn ack(&mut self, id: &str) -> anyhow::Result<()> {
+        let _: usize = self
+            .connection
+            .xack(&self.config.stream, &self.config.group, &[id])
+            .await
+            .context("failed to ack slot gap")?;
+        Ok(())
+    }
+}
+
+/// Backfills the slot ranges recorded by `grpc2redis`, a gap is acked once the transactions of
+/// every address in it were handled.
+async fn backfill_gaps(
+    poller: &mut Poller,
+    gaps: &mut Gaps,
+    program_transformer: &ProgramTransformer,
+) -> anyhow::Result<()> {
+    gaps.rewind();
+    'gaps: while let Some((id, gap)) = gaps.next().await? {
+        for address in poller.addresses().to_vec() {
+            let transactions = match poller.poll_slots(address, gap.from_slot, gap.to_slot).await {
+                Ok(transactions) => transactions,
+                Err(error) => {
+                    warn!(
+                        "failed to backfill slots {} to {} of {address}: {error:?}",
+                        gap.from_slot, gap.to_slot
+                    );
+                    continue 'gaps;
+                }
+            };
+            if !handle_transactions(program_transformer, &transactions).await {
+                continue 'gaps;
+            }
+        }
+        info!(
+            "backfilled slots {} to {} missed by grpc2redis",
+            gap.from_slot, gap.to_slot
+        );
+        gaps.ack(&id).await?;
+    }
+    Ok(())
+}
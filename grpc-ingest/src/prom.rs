@@ -50,6 +50,49 @@ lazy_static::lazy_static! {
     static ref DOWNLOAD_METADATA_INSERTED_TOTAL: IntCounter = IntCounter::new(
         "download_metadata_inserted_total", "Total number of inserted tasks for download metadata"
     ).unwrap();
+
+    static ref WEBHOOK_DELIVERY_STATUS: IntCounterVec = IntCounterVec::new(
+        Opts::new("webhook_delivery_status", "Status of webhook delivery attempts"),
+        &["webhook", "status"]
+    ).unwrap();
+
+    static ref GRPC_ENDPOINTS_TOTAL: IntGauge = IntGauge::new(
+        "grpc_endpoints_total", "Number of configured gRPC endpoints"
+    ).unwrap();
+
+    static ref GRPC_ENDPOINTS_CONNECTED: IntGauge = IntGauge::new(
+        "grpc_endpoints_connected", "Number of gRPC endpoints currently streaming"
+    ).unwrap();
+
+    static ref GRPC_ENDPOINT_CONNECTED: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("grpc_endpoint_connected", "Whether the gRPC endpoint is currently streaming"),
+        &["endpoint"]
+    ).unwrap();
+
+    static ref GRPC_RECONNECT_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new("grpc_reconnect_total", "Total number of reconnections to the gRPC endpoint"),
+        &["endpoint"]
+    ).unwrap();
+
+    static ref GRPC_CHECKPOINT_SLOT: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("grpc_checkpoint_slot", "Last slot fully forwarded to Redis"),
+        &["endpoint"]
+    ).unwrap();
+
+    static ref GRPC_SLOT_GAP_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new("grpc_slot_gap_total", "Total number of slots missed while disconnected"),
+        &["endpoint"]
+    ).unwrap();
+
+    static ref POLL_CHECKPOINT_SLOT: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("poll_checkpoint_slot", "Slot of the last signature handled by the RPC poller"),
+        &["address"]
+    ).unwrap();
+
+    static ref POLL_TRANSACTIONS_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new("poll_transactions_total", "Total number of transactions fetched by the RPC poller"),
+        &["address"]
+    ).unwrap();
 }
 
 pub fn run_server(address: SocketAddr) -> anyhow::Result<()> {
@@ -70,6 +113,15 @@ pub fn run_server(address: SocketAddr) -> anyhow::Result<()> {
         register!(PROGRAM_TRANSFORMER_TASKS_TOTAL);
         register!(PROGRAM_TRANSFORMER_TASK_STATUS);
         register!(DOWNLOAD_METADATA_INSERTED_TOTAL);
+        register!(WEBHOOK_DELIVERY_STATUS);
+        register!(GRPC_ENDPOINTS_TOTAL);
+        register!(GRPC_ENDPOINTS_CONNECTED);
+        register!(GRPC_ENDPOINT_CONNECTED);
+        register!(GRPC_RECONNECT_TOTAL);
+        register!(GRPC_CHECKPOINT_SLOT);
+        register!(GRPC_SLOT_GAP_TOTAL);
+        register!(POLL_CHECKPOINT_SLOT);
+        register!(POLL_TRANSACTIONS_TOTAL);
 
         VERSION
             .with_label_values(&[
@@ -88,6 +140,7 @@ pub fn run_server(address: SocketAddr) -> anyhow::Result<()> {
         Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| async move {
             let response = match req.uri().path() {
                 "/metrics" => metrics_handler(),
+                "/health" => health_handler(),
                 _ => not_found_handler(),
             };
             Ok::<_, hyper::Error>(response)
@@ -114,6 +167,19 @@ fn metrics_handler() -> Response<Body> {
     Response::builder().body(Body::from(metrics)).unwrap()
 }
 
+/// Unhealthy only when gRPC endpoints are configured and none of them is streaming.
+fn health_handler() -> Response<Body> {
+    let total = GRPC_ENDPOINTS_TOTAL.get();
+    let connected = GRPC_ENDPOINTS_CONNECTED.get();
+    if total > 0 && connected == 0 {
+        return Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(Body::from(format!("0/{total} gRPC endpoints connected")))
+            .unwrap();
+    }
+    Response::builder().body(Body::from("ok")).unwrap()
+}
+
 fn not_found_handler() -> Response<Body> {
     Response::builder()
         .status(StatusCode::NOT_FOUND)
@@ -180,3 +246,64 @@ pub fn program_transformer_task_status_inc(kind: ProgramTransformerTaskStatusKin
 pub fn download_metadata_inserted_total_inc() {
     DOWNLOAD_METADATA_INSERTED_TOTAL.inc()
 }
+
+#[derive(Debug, Clone, Copy)]
+pub enum WebhookDeliveryStatusKind {
+    Enqueued,
+    Delivered,
+    Retry,
+    Failed,
+}
+
+pub fn webhook_delivery_status_inc(webhook: &str, kind: WebhookDeliveryStatusKind) {
+    WEBHOOK_DELIVERY_STATUS
+        .with_label_values(&[
+            webhook,
+            match kind {
+                WebhookDeliveryStatusKind::Enqueued => "enqueued",
+                WebhookDeliveryStatusKind::Delivered => "delivered",
+                WebhookDeliveryStatusKind::Retry => "retry",
+                WebhookDeliveryStatusKind::Failed => "failed",
+            },
+        ])
+        .inc()
+}
+
+pub fn grpc_endpoints_total_set(total: usize) {
+    GRPC_ENDPOINTS_TOTAL.set(total as i64)
+}
+
+pub fn grpc_endpoint_connected_set(endpoint: &str, connected: bool) {
+    let gauge = GRPC_ENDPOINT_CONNECTED.with_label_values(&[endpoint]);
+    let connected = i64::from(connected);
+    GRPC_ENDPOINTS_CONNECTED.add(connected - gauge.get());
+    gauge.set(connected);
+}
+
+pub fn grpc_reconnect_inc(endpoint: &str) {
+    GRPC_RECONNECT_TOTAL.with_label_values(&[endpoint]).inc()
+}
+
+pub fn grpc_checkpoint_slot_set(endpoint: &str, slot: u64) {
+    GRPC_CHECKPOINT_SLOT
+        .with_label_values(&[endpoint])
+        .set(slot as i64)
+}
+
+pub fn grpc_slot_gap_inc(endpoint: &str, slots: u64) {
+    GRPC_SLOT_GAP_TOTAL
+        .with_label_values(&[endpoint])
+        .inc_by(slots)
+}
+
+pub fn poll_checkpoint_slot_set(address: &str, slot: u64) {
+    POLL_CHECKPOINT_SLOT
+        .with_label_values(&[address])
+        .set(slot as i64)
+}
+
+pub fn poll_transactions_inc(address: &str, delta: usize) {
+    POLL_TRANSACTIONS_TOTAL
+        .with_label_values(&[address])
+        .inc_by(delta as u64)
+}
@@ -1,15 +1,19 @@
 use {
     crate::{
+        bus::{
+            ConsumerTasks, MessageConsumer, MessageProducer, ProgramTransformerInfo, StreamEntry,
+            StreamMessage,
+        },
         config::{ConfigIngesterRedis, ConfigIngesterRedisStreamType},
         prom::{redis_xack_inc, redis_xlen_set},
     },
-    futures::future::{BoxFuture, Fuse, FutureExt},
+    futures::future::{BoxFuture, FutureExt},
     program_transformers::{AccountInfo, TransactionInfo},
     redis::{
         aio::MultiplexedConnection,
         streams::{
-            StreamClaimReply, StreamId, StreamKey, StreamPendingCountReply, StreamReadOptions,
-            StreamReadReply,
+            StreamClaimReply, StreamId, StreamKey, StreamMaxlen, StreamPendingCountReply,
+            StreamReadOptions, StreamReadReply,
         },
         AsyncCommands, ErrorKind as RedisErrorKind, RedisResult, Value as RedisValue,
     },
@@ -32,7 +36,6 @@ use {
             create_message_instructions, create_meta_inner_instructions, create_pubkey_vec,
         },
         prelude::{SubscribeUpdateAccount, SubscribeUpdateTransaction},
-        prost::Message,
     },
 };
 
@@ -95,125 +98,88 @@ struct RedisStreamInfo {
     xack_max_in_process: usize,
 }
 
-#[derive(Debug)]
-pub enum ProgramTransformerInfo {
-    Account(AccountInfo),
-    Transaction(TransactionInfo),
-}
-
-#[derive(Debug)]
-pub struct RedisStreamMessageInfo {
-    id: String,
-    data: ProgramTransformerInfo,
+fn parse_message(
+    stream: &RedisStreamInfo,
+    StreamId { id, map }: StreamId,
     ack_tx: mpsc::UnboundedSender<String>,
+) -> anyhow::Result<StreamMessage> {
+    let data = match map.get(&stream.stream_data_key) {
+        Some(RedisValue::Data(vec)) => ProgramTransformerInfo::decode(stream.stream_type, vec)?,
+        Some(_) => anyhow::bail!(
+            "invalid data (key: {:?}) from stream {:?}",
+            stream.stream_data_key,
+            stream.stream_name
+        ),
+        None => anyhow::bail!(
+            "failed to get data (key: {:?}) from stream {:?}",
+            stream.stream_data_key,
+            stream.stream_name
+        ),
+    };
+    Ok(StreamMessage::new(id, data, ack_tx))
 }
 
-impl RedisStreamMessageInfo {
-    fn parse(
-        stream: &RedisStreamInfo,
-        StreamId { id, map }: StreamId,
-        ack_tx: mpsc::UnboundedSender<String>,
-    ) -> anyhow::Result<Self> {
-        let to_anyhow = |error: String| anyhow::anyhow!(error);
-
-        let data = match map.get(&stream.stream_data_key) {
-            Some(RedisValue::Data(vec)) => match stream.stream_type {
-                ConfigIngesterRedisStreamType::Account => {
-                    let SubscribeUpdateAccount { account, slot, .. } =
-                        Message::decode(vec.as_ref())?;
-
-                    let account = account.ok_or_else(|| {
-                        anyhow::anyhow!("received invalid SubscribeUpdateAccount")
-                    })?;
-
-                    ProgramTransformerInfo::Account(AccountInfo {
-                        slot,
-                        pubkey: Pubkey::try_from(account.pubkey.as_slice())?,
-                        owner: Pubkey::try_from(account.owner.as_slice())?,
-                        data: account.data,
-                    })
-                }
-                ConfigIngesterRedisStreamType::Transaction => {
-                    let SubscribeUpdateTransaction { transaction, slot } =
-                        Message::decode(vec.as_ref())?;
-
-                    let transaction = transaction.ok_or_else(|| {
-                        anyhow::anyhow!("received invalid SubscribeUpdateTransaction")
-                    })?;
-                    let tx = transaction.transaction.ok_or_else(|| {
-                        anyhow::anyhow!(
-                            "received invalid transaction in SubscribeUpdateTransaction"
-                        )
-                    })?;
-                    let message = tx.message.ok_or_else(|| {
-                        anyhow::anyhow!("received invalid message in SubscribeUpdateTransaction")
-                    })?;
-                    let meta = transaction.meta.ok_or_else(|| {
-                        anyhow::anyhow!("received invalid meta in SubscribeUpdateTransaction")
-                    })?;
-
-                    let mut account_keys =
-                        create_pubkey_vec(message.account_keys).map_err(to_anyhow)?;
-                    for pubkey in
-                        create_pubkey_vec(meta.loaded_writable_addresses).map_err(to_anyhow)?
-                    {
-                        account_keys.push(pubkey);
-                    }
-                    for pubkey in
-                        create_pubkey_vec(meta.loaded_readonly_addresses).map_err(to_anyhow)?
-                    {
-                        account_keys.push(pubkey);
-                    }
+pub fn account_info_from_update(
+    SubscribeUpdateAccount { account, slot, .. }: SubscribeUpdateAccount,
+) -> anyhow::Result<AccountInfo> {
+    let account =
+        account.ok_or_else(|| anyhow::anyhow!("received invalid SubscribeUpdateAccount"))?;
+
+    Ok(AccountInfo {
+        slot,
+        pubkey: Pubkey::try_from(account.pubkey.as_slice())?,
+        owner: Pubkey::try_from(account.owner.as_slice())?,
+        data: account.data,
+    })
+}
 
-                    ProgramTransformerInfo::Transaction(TransactionInfo {
-                        slot,
-                        signature: Signature::try_from(transaction.signature.as_slice())?,
-                        account_keys,
-                        message_instructions: create_message_instructions(message.instructions)
-                            .map_err(to_anyhow)?,
-                        meta_inner_instructions: create_meta_inner_instructions(
-                            meta.inner_instructions,
-                        )
-                        .map_err(to_anyhow)?,
-                    })
-                }
-            },
-            Some(_) => anyhow::bail!(
-                "invalid data (key: {:?}) from stream {:?}",
-                stream.stream_data_key,
-                stream.stream_name
-            ),
-            None => anyhow::bail!(
-                "failed to get data (key: {:?}) from stream {:?}",
-                stream.stream_data_key,
-                stream.stream_name
-            ),
-        };
-        Ok(Self { id, data, ack_tx })
+pub fn transaction_info_from_update(
+    SubscribeUpdateTransaction { transaction, slot }: SubscribeUpdateTransaction,
+) -> anyhow::Result<TransactionInfo> {
+    let to_anyhow = |error: String| anyhow::anyhow!(error);
+
+    let transaction = transaction
+        .ok_or_else(|| anyhow::anyhow!("received invalid SubscribeUpdateTransaction"))?;
+    let tx = transaction.transaction.ok_or_else(|| {
+        anyhow::anyhow!("received invalid transaction in SubscribeUpdateTransaction")
+    })?;
+    let message = tx
+        .message
+        .ok_or_else(|| anyhow::anyhow!("received invalid message in SubscribeUpdateTransaction"))?;
+    let meta = transaction
+        .meta
+        .ok_or_else(|| anyhow::anyhow!("received invalid meta in SubscribeUpdateTransaction"))?;
+
+    let mut account_keys = create_pubkey_vec(message.account_keys).map_err(to_anyhow)?;
+    for pubkey in create_pubkey_vec(meta.loaded_writable_addresses).map_err(to_anyhow)? {
+        account_keys.push(pubkey);
     }
-
-    pub const fn get_data(&self) -> &ProgramTransformerInfo {
-        &self.data
+    for pubkey in create_pubkey_vec(meta.loaded_readonly_addresses).map_err(to_anyhow)? {
+        account_keys.push(pubkey);
     }
 
-    pub fn ack(self) -> anyhow::Result<()> {
-        self.ack_tx
-            .send(self.id)
-            .map_err(|_error| anyhow::anyhow!("failed to send message to ack channel"))
-    }
+    Ok(TransactionInfo {
+        slot,
+        signature: Signature::try_from(transaction.signature.as_slice())?,
+        account_keys,
+        message_instructions: create_message_instructions(message.instructions)
+            .map_err(to_anyhow)?,
+        meta_inner_instructions: create_meta_inner_instructions(meta.inner_instructions)
+            .map_err(to_anyhow)?,
+    })
 }
 
 #[derive(Debug)]
 pub struct RedisStream {
     shutdown: Arc<AtomicBool>,
-    messages_rx: mpsc::Receiver<RedisStreamMessageInfo>,
+    messages_rx: mpsc::Receiver<StreamMessage>,
 }
 
 impl RedisStream {
     pub async fn new(
         config: ConfigIngesterRedis,
         mut connection: MultiplexedConnection,
-    ) -> anyhow::Result<(Self, Fuse<BoxFuture<'static, anyhow::Result<()>>>)> {
+    ) -> anyhow::Result<(Self, ConsumerTasks)> {
         // create group with consumer per stream
         for stream in config.streams.iter() {
             xgroup_create(
@@ -284,20 +250,11 @@ impl RedisStream {
         ))
     }
 
-    pub async fn recv(&mut self) -> Option<RedisStreamMessageInfo> {
-        self.messages_rx.recv().await
-    }
-
-    pub fn shutdown(mut self) {
-        self.shutdown.store(true, Ordering::Relaxed);
-        tokio::spawn(async move { while self.messages_rx.recv().await.is_some() {} });
-    }
-
     async fn run_prefetch(
         config: ConfigIngesterRedis,
         streams: HashMap<String, (mpsc::UnboundedSender<String>, Arc<RedisStreamInfo>)>,
         mut connection: MultiplexedConnection,
-        messages_tx: mpsc::Sender<RedisStreamMessageInfo>,
+        messages_tx: mpsc::Sender<StreamMessage>,
         shutdown: Arc<AtomicBool>,
     ) -> anyhow::Result<()> {
         // read pending first
@@ -344,7 +301,7 @@ impl RedisStream {
                     )
                     .await?;
                 for pending in pendings {
-                    let item = RedisStreamMessageInfo::parse(stream, pending, ack_tx.clone())?;
+                    let item = parse_message(stream, pending, ack_tx.clone())?;
                     messages_tx.send(item).await.map_err(|_error| {
                         anyhow::anyhow!("failed to send item to prefetch channel")
                     })?;
@@ -378,7 +335,7 @@ impl RedisStream {
                 };
 
                 for id in ids {
-                    let item = RedisStreamMessageInfo::parse(stream, id, ack_tx.clone())?;
+                    let item = parse_message(stream, id, ack_tx.clone())?;
                     messages_tx.send(item).await.map_err(|_error| {
                         anyhow::anyhow!("failed to send item to prefetch channel")
                     })?;
@@ -452,3 +409,64 @@ impl RedisStream {
         result
     }
 }
+
+impl MessageConsumer for RedisStream {
+    fn recv(&mut self) -> BoxFuture<'_, Option<StreamMessage>> {
+        self.messages_rx.recv().boxed()
+    }
+
+    fn shutdown(mut self: Box<Self>) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        tokio::spawn(async move { while self.messages_rx.recv().await.is_some() {} });
+    }
+}
+
+/// Length and data key of a stream published by [`RedisProducer`].
+#[derive(Debug, Clone)]
+pub struct RedisProducerStream {
+    pub maxlen: usize,
+    pub data_key: String,
+}
+
+/// Publishes entries with `XADD MAXLEN ~`, in one atomic pipeline per batch.
+#[derive(Debug, Clone)]
+pub struct RedisProducer {
+    connection: MultiplexedConnection,
+    streams: Arc<HashMap<String, RedisProducerStream>>,
+}
+
+impl RedisProducer {
+    pub fn new(
+        connection: MultiplexedConnection,
+        streams: HashMap<String, RedisProducerStream>,
+    ) -> Self {
+        Self {
+            connection,
+            streams: Arc::new(streams),
+        }
+    }
+}
+
+impl MessageProducer for RedisProducer {
+    fn publish(&self, entries: Vec<StreamEntry>) -> BoxFuture<'static, anyhow::Result<()>> {
+        let mut connection = self.connection.clone();
+        let streams = Arc::clone(&self.streams);
+        async move {
+            let mut pipe = redis::pipe();
+            for StreamEntry { stream, data } in entries {
+                let Some(config) = streams.get(&stream) else {
+                    anyhow::bail!("unknown stream: {:?}", stream);
+                };
+                pipe.xadd_maxlen(
+                    &stream,
+                    StreamMaxlen::Approx(config.maxlen),
+                    "*",
+                    &[(&config.data_key, data)],
+                );
+            }
+            let _: RedisValue = pipe.atomic().query_async(&mut connection).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
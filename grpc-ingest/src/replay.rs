@@ -0,0 +1,267 @@
+use {
+    crate::{
+        config::ConfigReplay,
+        ingester::create_download_metadata_notifier,
+        postgres::create_pool as pg_create_pool,
+        redis::{account_info_from_update, transaction_info_from_update},
+    },
+    anyhow::Context,
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
+    clap::ValueEnum,
+    program_transformers::{dry_run::DryRun, error::ProgramTransformerError, ProgramTransformer},
+    serde::{Deserialize, Serialize},
+    std::path::{Path, PathBuf},
+    tokio::fs,
+    tracing::{info, warn},
+    yellowstone_grpc_proto::{
+        prelude::{
+            subscribe_update::UpdateOneof, SubscribeUpdate, SubscribeUpdateAccount,
+            SubscribeUpdateTransaction,
+        },
+        prost::Message,
+    },
+};
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ArgsReplay {
+    /// Dump of transactions and account updates
+    pub file: PathBuf,
+
+    /// Format of the dump, detected from the file extension by default
+    #[clap(long, value_enum)]
+    pub format: Option<ReplayFormat>,
+
+    /// Process the updates by slot and position in the slot instead of in the dump order
+    #[clap(long)]
+    pub sort: bool,
+
+    /// Print the rows each update would write as JSON lines and roll them back
+    #[clap(long)]
+    pub dry_run: bool,
+
+    /// Decode the updates without connecting to Postgres
+    #[clap(long, conflicts_with = "dry_run")]
+    pub decode_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayFormat {
+    /// JSON array of entries, such as the `TXN_CACHE.json` written by `tools/redis_cache`
+    Json,
+    /// One entry per line
+    Jsonl,
+    /// Length-delimited `SubscribeUpdate` protobufs
+    Protobuf,
+}
+
+impl ReplayFormat {
+    fn detect(path: &Path) -> anyhow::Result<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Ok(Self::Json),
+            Some("jsonl") | Some("ndjson") => Ok(Self::Jsonl),
+            Some("pb") | Some("bin") => Ok(Self::Protobuf),
+            value => anyhow::bail!("unknown replay extension: {value:?}, set `--format`"),
+        }
+    }
+}
+
+/// Entry of a JSON dump.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ReplayEntry {
+    /// `TXN_CACHE` entry, the endpoint index followed by a `SubscribeUpdateTransaction`.
+    TxnCache(Vec<u8>),
+    Record(ReplayRecord),
+}
+
+/// Base64 encoded `SubscribeUpdateAccount` or `SubscribeUpdateTransaction`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ReplayRecord {
+    Account { data: String },
+    Transaction { data: String },
+}
+
+/// Line printed for every update by `--dry-run`.
+#[derive(Debug, Serialize)]
+struct DryRunRecord<'a> {
+    slot: u64,
+    update: &'a str,
+    #[serde(flatten)]
+    dry_run: DryRun,
+}
+
+#[derive(Debug)]
+pub enum ReplayUpdate {
+    Account(SubscribeUpdateAccount),
+    Transaction(SubscribeUpdateTransaction),
+}
+
+impl ReplayUpdate {
+    fn from_entry(entry: ReplayEntry) -> anyhow::Result<Self> {
+        Ok(match entry {
+            ReplayEntry::TxnCache(data) => {
+                let data = data.get(1..).context("empty TXN_CACHE entry")?;
+                Self::Transaction(Message::decode(data)?)
+            }
+            ReplayEntry::Record(ReplayRecord::Account { data }) => {
+                Self::Account(Message::decode(BASE64.decode(data)?.as_slice())?)
+            }
+            ReplayEntry::Record(ReplayRecord::Transaction { data }) => {
+                Self::Transaction(Message::decode(BASE64.decode(data)?.as_slice())?)
+            }
+        })
+    }
+
+    /// Slot, then write version for accounts and index in the block for transactions.
+    fn sort_key(&self) -> (u64, u64) {
+        match self {
+            Self::Account(update) => (
+                update.slot,
+                update
+                    .account
+                    .as_ref()
+                    .map_or(0, |account| account.write_version),
+            ),
+            Self::Transaction(update) => (
+                update.slot,
+                update
+                    .transaction
+                    .as_ref()
+                    .map_or(0, |transaction| transaction.index),
+            ),
+        }
+    }
+}
+
+pub async fn read_updates(path: &Path, format: ReplayFormat) -> anyhow::Result<Vec<ReplayUpdate>> {
+    let bytes = fs::read(path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    match format {
+        ReplayFormat::Json => serde_json::from_slice::<Vec<ReplayEntry>>(&bytes)?
+            .into_iter()
+            .map(ReplayUpdate::from_entry)
+            .collect(),
+        ReplayFormat::Jsonl => std::str::from_utf8(&bytes)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<ReplayEntry>(line)
+                    .map_err(anyhow::Error::from)
+                    .and_then(ReplayUpdate::from_entry)
+                    .with_context(|| format!("invalid entry on line {}", index + 1))
+            })
+            .collect(),
+        ReplayFormat::Protobuf => {
+            let mut buf = bytes.as_slice();
+            let mut updates = Vec::new();
+            while !buf.is_empty() {
+                match SubscribeUpdate::decode_length_delimited(&mut buf)?.update_oneof {
+                    Some(UpdateOneof::Account(update)) => {
+                        updates.push(ReplayUpdate::Account(update))
+                    }
+                    Some(UpdateOneof::Transaction(update)) => {
+                        updates.push(ReplayUpdate::Transaction(update))
+                    }
+                    _ => {}
+                }
+            }
+            Ok(updates)
+        }
+    }
+}
+
+/// Reads a dump, with the format detected from the extension when not set.
+pub async fn load_updates(
+    path: &Path,
+    format: Option<ReplayFormat>,
+    sort: bool,
+) -> anyhow::Result<Vec<ReplayUpdate>> {
+    let format = match format {
+        Some(format) => format,
+        None => ReplayFormat::detect(path)?,
+    };
+    let mut updates = read_updates(path, format).await?;
+    info!("read {} updates from {}", updates.len(), path.display());
+    if sort {
+        // stable, updates without a position keep their relative order
+        updates.sort_by_key(ReplayUpdate::sort_key);
+    }
+    Ok(updates)
+}
+
+pub async fn run(config: ConfigReplay, args: ArgsReplay) -> anyhow::Result<()> {
+    let updates = load_updates(&args.file, args.format, args.sort).await?;
+
+    let program_transformer = if args.decode_only {
+        None
+    } else {
+        let pgpool = pg_create_pool(config.postgres).await?;
+        Some(ProgramTransformer::new(
+            pgpool.clone(),
+            create_download_metadata_notifier(pgpool, config.download_metadata)?,
+            config.program_transformer.transactions_cl_audits,
+        ))
+    };
+
+    let (mut handled, mut not_implemented, mut failed) = (0, 0, 0);
+    for update in updates {
+        let (slot, name, result) = match update {
+            ReplayUpdate::Account(update) => {
+                let account = account_info_from_update(update)?;
+                let name = format!("account {}", account.pubkey);
+                let result = match &program_transformer {
+                    Some(pt) if args.dry_run => pt.dry_run_account_update(&account).await.map(Some),
+                    Some(pt) => pt.handle_account_update(&account).await.map(|()| None),
+                    None => Ok(None),
+                };
+                (account.slot, name, result)
+            }
+            ReplayUpdate::Transaction(update) => {
+                let transaction = transaction_info_from_update(update)?;
+                let name = format!("transaction {}", transaction.signature);
+                let result = match &program_transformer {
+                    Some(pt) if args.dry_run => {
+                        pt.dry_run_transaction(&transaction).await.map(Some)
+                    }
+                    Some(pt) => pt.handle_transaction(&transaction).await.map(|()| None),
+                    None => Ok(None),
+                };
+                (transaction.slot, name, result)
+            }
+        };
+
+        match result {
+            Ok(dry_run) => {
+                if let Some(dry_run) = dry_run {
+                    let record = DryRunRecord {
+                        slot,
+                        update: &name,
+                        dry_run,
+                    };
+                    println!("{}", serde_json::to_string(&record)?);
+                } else if program_transformer.is_none() {
+                    info!("slot {slot}: {name}");
+                }
+                handled += 1;
+            }
+            Err(ProgramTransformerError::NotImplemented) => not_implemented += 1,
+            Err(
+                error @ (ProgramTransformerError::DeserializationError(_)
+                | ProgramTransformerError::ParsingError(_)),
+            ) => {
+                warn!("slot {slot}: failed to handle {name}: {error:?}");
+                failed += 1;
+            }
+            Err(error) => {
+                return Err(error).with_context(|| format!("slot {slot}: failed to handle {name}"))
+            }
+        }
+    }
+
+    info!("replayed {handled} updates, {not_implemented} not implemented, {failed} failed");
+    Ok(())
+}
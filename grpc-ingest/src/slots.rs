@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Slots of the updates received from one endpoint.
+#[derive(Debug, Default)]
+struct EndpointSlots {
+    /// Highest slot confirmed by the endpoint, every update of a slot is streamed before its
+    /// confirmation.
+    confirmed: Option<u64>,
+    /// Last slot checkpointed.
+    stored: Option<u64>,
+    /// Set when subscribed, until the first update tells which slots were missed.
+    resumed: bool,
+}
+
+/// Pipeline cut by [SlotTracker::cut_batch] and the confirmed slot of every endpoint at the time.
+pub type Batch = (u64, HashMap<u8, u64>);
+
+/// Tracks, per endpoint, the last slot fully forwarded to Redis.
+///
+/// Updates at processed commitment are not ordered by slot and may belong to forks, so only the
+/// slot status updates move the checkpoint: once a slot is confirmed all its updates were
+/// received. That slot is checkpointed once the pipeline holding them, and every pipeline cut
+/// before it, was executed.
+#[derive(Debug, Default)]
+pub struct SlotTracker {
+    endpoints: Vec<EndpointSlots>,
+    next_batch: u64,
+    next_completed: u64,
+    completed: BTreeMap<u64, HashMap<u8, u64>>,
+}
+
+impl SlotTracker {
+    /// Resumes every endpoint from its stored checkpoint.
+    pub fn new(stored: impl IntoIterator<Item = Option<u64>>) -> Self {
+        Self {
+            endpoints: stored
+                .into_iter()
+                .map(|stored| EndpointSlots {
+                    confirmed: stored,
+                    stored,
+                    resumed: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn subscribed(&mut self, endpoint_index: u8) {
+        if let Some(endpoint) = self.endpoints.get_mut(endpoint_index as usize) {
+            endpoint.resumed = true;
+        }
+    }
+
+    /// Records the slot of an update, returns the inclusive range of slots that may have been
+    /// missed when it is the first update since the endpoint resubscribed.
+    pub fn observe(&mut self, endpoint_index: u8, slot: u64) -> Option<(u64, u64)> {
+        let endpoint = self.endpoints.get_mut(endpoint_index as usize)?;
+        if !std::mem::take(&mut endpoint.resumed) {
+            return None;
+        }
+        // The first slot streamed after subscribing can be partial.
+        endpoint
+            .confirmed
+            .filter(|confirmed| slot > *confirmed)
+            .map(|confirmed| (confirmed + 1, slot))
+    }
+
+    /// Records a slot confirmed or finalized by the endpoint.
+    pub fn confirmed(&mut self, endpoint_index: u8, slot: u64) {
+        if let Some(endpoint) = self.endpoints.get_mut(endpoint_index as usize) {
+            endpoint.confirmed = endpoint.confirmed.max(Some(slot));
+        }
+    }
+
+    /// Snapshots the confirmed slots for the pipeline being cut.
+    pub fn cut_batch(&mut self) -> Batch {
+        let batch = self.next_batch;
+        self.next_batch += 1;
+        let slots = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, endpoint)| Some((index as u8, endpoint.confirmed?)))
+            .collect();
+        (batch, slots)
+    }
+
+    /// Marks the pipeline executed, returns the checkpoints to store: the slots of the pipelines
+    /// executed in order that move past the stored ones.
+    pub fn complete(&mut self, (batch, slots): Batch) -> Vec<(u8, u64)> {
+        self.completed.insert(batch, slots);
+        let mut updates = BTreeMap::new();
+        while let Some(slots) = self.completed.remove(&self.next_completed) {
+            self.next_completed += 1;
+            for (index, slot) in slots {
+                let Some(endpoint) = self.endpoints.get_mut(index as usize) else {
+                    continue;
+                };
+                if endpoint.stored.map_or(true, |stored| slot > stored) {
+                    endpoint.stored = Some(slot);
+                    updates.insert(index, slot);
+                }
+            }
+        }
+        updates.into_iter().collect()
+    }
+}
@@ -0,0 +1,274 @@
+use {
+    crate::{
+        config::{ConfigWebhook, ConfigWebhooks, ConfigWebhooksDispatcher, ConfigWebhooksRedis},
+        postgres::{create_pool as pg_create_pool, metrics_pgpool},
+        prom::{webhook_delivery_status_inc, WebhookDeliveryStatusKind},
+        redis::xgroup_create,
+        util::create_shutdown,
+    },
+    futures::{future::join_all, stream::StreamExt},
+    program_transformers::events::DomainEvent,
+    redis::{
+        aio::MultiplexedConnection,
+        streams::{StreamId, StreamReadOptions, StreamReadReply},
+        AsyncCommands, Value as RedisValue,
+    },
+    reqwest::{header::CONTENT_TYPE, Client, ClientBuilder},
+    sqlx::PgPool,
+    std::sync::Arc,
+    tokio::time::{sleep, Duration},
+    tracing::{debug, info, warn},
+};
+
+const EVENT_ID_HEADER: &str = "x-das-event-id";
+const WEBHOOK_HEADER: &str = "x-das-webhook";
+
+pub async fn run(config: ConfigWebhooks) -> anyhow::Result<()> {
+    let mut shutdown = create_shutdown()?;
+
+    // connect to Redis
+    let client = redis::Client::open(config.redis.url.clone())?;
+    let mut connection = client.get_multiplexed_tokio_connection().await?;
+    xgroup_create(
+        &mut connection,
+        &config.redis.stream,
+        &config.redis.group,
+        &config.redis.consumer,
+    )
+    .await?;
+
+    // open connection to postgres
+    let pool = pg_create_pool(config.postgres).await?;
+    tokio::spawn({
+        let pool = pool.clone();
+        async move { metrics_pgpool(pool).await }
+    });
+
+    let http = ClientBuilder::new()
+        .timeout(config.dispatcher.request_timeout)
+        .build()?;
+    let webhooks = Arc::new(config.webhooks);
+    info!("dispatching events to {} webhooks", webhooks.len());
+
+    let result = tokio::select! {
+        Some(signal) = shutdown.next() => {
+            warn!("{signal} received, shutting down...");
+            Ok(())
+        },
+        result = enqueue_deliveries(
+            connection,
+            pool.clone(),
+            config.redis,
+            Arc::clone(&webhooks),
+            config.dispatcher.max_attempts,
+        ) => result,
+        result = dispatch_deliveries(pool.clone(), http, config.dispatcher) => result,
+    };
+
+    pool.close().await;
+    result
+}
+
+// Read events from the Redis stream and persist one delivery per matching webhook. Messages are
+// only acked once their deliveries are stored, so a crash re-reads them from the pending list.
+async fn enqueue_deliveries(
+    mut connection: MultiplexedConnection,
+    pool: PgPool,
+    config: ConfigWebhooksRedis,
+    webhooks: Arc<Vec<ConfigWebhook>>,
+    max_attempts: usize,
+) -> anyhow::Result<()> {
+    let max_attempts = i32::try_from(max_attempts)?;
+    let mut read_id = "0";
+    loop {
+        let opts = StreamReadOptions::default()
+            .count(config.xreadgroup_max)
+            .group(&config.group, &config.consumer);
+        let reply: StreamReadReply = connection
+            .xread_options(&[&config.stream], &[read_id], &opts)
+            .await?;
+        let ids = reply
+            .keys
+            .into_iter()
+            .flat_map(|key| key.ids)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            // pending messages are consumed, switch to new ones
+            if read_id == "0" {
+                read_id = ">";
+            } else {
+                sleep(Duration::from_millis(100)).await;
+            }
+            continue;
+        }
+
+        for StreamId { id, map } in &ids {
+            let event = match map.get("data") {
+                Some(RedisValue::Data(data)) => match serde_json::from_slice::<DomainEvent>(data) {
+                    Ok(event) => event,
+                    Err(error) => {
+                        warn!("failed to decode event {id}: {error:?}");
+                        continue;
+                    }
+                },
+                _ => {
+                    warn!("event {id} has no data");
+                    continue;
+                }
+            };
+            let payload = serde_json::to_value(&event)?;
+
+            for webhook in webhooks
+                .iter()
+                .filter(|webhook| webhook.filter.matches(&event))
+            {
+                sqlx::query(
+                    "INSERT INTO webhook_deliveries (webhook, event_id, url, payload, max_attempts) \
+                     VALUES ($1, $2, $3, $4, $5) ON CONFLICT (webhook, event_id) DO NOTHING",
+                )
+                .bind(&webhook.name)
+                .bind(id)
+                .bind(&webhook.url)
+                .bind(&payload)
+                .bind(max_attempts)
+                .execute(&pool)
+                .await?;
+                webhook_delivery_status_inc(&webhook.name, WebhookDeliveryStatusKind::Enqueued);
+            }
+        }
+
+        let ack_ids = ids.iter().map(|id| id.id.as_str()).collect::<Vec<_>>();
+        let _: usize = connection
+            .xack(&config.stream, &config.group, &ack_ids)
+            .await?;
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct Delivery {
+    id: i64,
+    webhook: String,
+    event_id: String,
+    url: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    max_attempts: i32,
+}
+
+// Claim due deliveries by pushing `next_attempt_at` past the request timeout, so a crashed
+// dispatcher only delays them and several dispatchers never send the same delivery twice at once.
+async fn dispatch_deliveries(
+    pool: PgPool,
+    http: Client,
+    config: ConfigWebhooksDispatcher,
+) -> anyhow::Result<()> {
+    let lease = (config.request_timeout * 2).as_secs_f64();
+    loop {
+        let deliveries: Vec<Delivery> = sqlx::query_as(
+            "UPDATE webhook_deliveries SET next_attempt_at = now() + make_interval(secs => $2) \
+             WHERE id IN ( \
+                 SELECT id FROM webhook_deliveries \
+                 WHERE status = 'pending' AND next_attempt_at <= now() \
+                 ORDER BY next_attempt_at LIMIT $1 FOR UPDATE SKIP LOCKED \
+             ) \
+             RETURNING id, webhook, event_id, url, payload, attempts, max_attempts",
+        )
+        .bind(config.batch_size as i64)
+        .bind(lease)
+        .fetch_all(&pool)
+        .await?;
+
+        if deliveries.is_empty() {
+            sleep(config.poll_interval).await;
+            continue;
+        }
+        debug!("dispatching {} webhook deliveries", deliveries.len());
+
+        let results = join_all(deliveries.iter().map(|delivery| {
+            deliver(
+                &http,
+                &delivery.url,
+                &delivery.webhook,
+                &delivery.event_id,
+                &delivery.payload,
+            )
+        }))
+        .await;
+
+        for (delivery, result) in deliveries.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    sqlx::query(
+                        "UPDATE webhook_deliveries SET status = 'delivered', attempts = attempts + 1, \
+                         last_error = NULL, delivered_at = now() WHERE id = $1",
+                    )
+                    .bind(delivery.id)
+                    .execute(&pool)
+                    .await?;
+                    webhook_delivery_status_inc(
+                        &delivery.webhook,
+                        WebhookDeliveryStatusKind::Delivered,
+                    );
+                }
+                Err(error) => {
+                    let attempts = delivery.attempts + 1;
+                    let (status, kind) = if attempts >= delivery.max_attempts {
+                        ("failed", WebhookDeliveryStatusKind::Failed)
+                    } else {
+                        ("pending", WebhookDeliveryStatusKind::Retry)
+                    };
+                    warn!(
+                        "webhook {} delivery {} attempt {attempts} failed: {error}",
+                        delivery.webhook, delivery.event_id
+                    );
+                    sqlx::query(
+                        "UPDATE webhook_deliveries SET status = $2, attempts = $3, last_error = $4, \
+                         next_attempt_at = now() + make_interval(secs => $5) WHERE id = $1",
+                    )
+                    .bind(delivery.id)
+                    .bind(status)
+                    .bind(attempts)
+                    .bind(error)
+                    .bind(retry_delay(&config, attempts).as_secs_f64())
+                    .execute(&pool)
+                    .await?;
+                    webhook_delivery_status_inc(&delivery.webhook, kind);
+                }
+            }
+        }
+    }
+}
+
+/// POSTs the event JSON to `url`, any status other than 2xx is a failed attempt.
+pub async fn deliver(
+    http: &Client,
+    url: &str,
+    webhook: &str,
+    event_id: &str,
+    payload: &serde_json::Value,
+) -> Result<(), String> {
+    let body = serde_json::to_vec(payload).map_err(|error| error.to_string())?;
+    let response = http
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(EVENT_ID_HEADER, event_id)
+        .header(WEBHOOK_HEADER, webhook)
+        .body(body)
+        .send()
+        .await
+        .map_err(|error| error.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("unexpected status {}", response.status()))
+    }
+}
+
+/// Delay before the next attempt, doubling from `retry_base` up to `retry_max`.
+pub fn retry_delay(config: &ConfigWebhooksDispatcher, attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    config
+        .retry_base
+        .saturating_mul(2u32.pow(exponent))
+        .min(config.retry_max)
+}
@@ -0,0 +1,312 @@
+use {
+    das_grpc_ingest::{
+        bus::{
+            ConsumerTasks, MemoryBus, MessageConsumer, MessageProducer, ProgramTransformerInfo,
+            StreamEntry, StreamMessage,
+        },
+        config::ConfigIngesterRedisStreamType,
+        ingester::consume,
+    },
+    futures::future::{ready, FutureExt},
+    program_transformers::ProgramTransformer,
+    solana_sdk::{pubkey, pubkey::Pubkey, signature::Signature},
+    sqlx::postgres::PgPoolOptions,
+    std::{sync::Arc, time::Duration},
+    tokio::time::timeout,
+    yellowstone_grpc_proto::{
+        prelude::{
+            CompiledInstruction, Message, SubscribeUpdateAccount, SubscribeUpdateAccountInfo,
+            SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, Transaction,
+            TransactionStatusMeta,
+        },
+        prost::Message as _,
+    },
+};
+
+const ACCOUNTS: &str = "ACCOUNTS";
+const TRANSACTIONS: &str = "TRANSACTIONS";
+
+fn consumer(bus: &MemoryBus) -> (Box<dyn MessageConsumer>, ConsumerTasks) {
+    let streams = [
+        (ACCOUNTS.to_owned(), ConfigIngesterRedisStreamType::Account),
+        (
+            TRANSACTIONS.to_owned(),
+            ConfigIngesterRedisStreamType::Transaction,
+        ),
+    ];
+    let (consumer, tasks) = bus.consumer(&streams, 10);
+    (Box::new(consumer), tasks)
+}
+
+fn account(pubkey: Pubkey, slot: u64) -> StreamEntry {
+    let update = SubscribeUpdateAccount {
+        account: Some(SubscribeUpdateAccountInfo {
+            pubkey: pubkey.to_bytes().to_vec(),
+            owner: Pubkey::new_unique().to_bytes().to_vec(),
+            data: vec![1, 2, 3],
+            ..Default::default()
+        }),
+        slot,
+        is_startup: false,
+    };
+    StreamEntry {
+        stream: ACCOUNTS.to_owned(),
+        data: update.encode_to_vec(),
+    }
+}
+
+fn transaction(signature: Signature, slot: u64) -> StreamEntry {
+    let update = SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: signature.as_ref().to_vec(),
+            transaction: Some(Transaction {
+                signatures: vec![signature.as_ref().to_vec()],
+                message: Some(Message::default()),
+            }),
+            meta: Some(TransactionStatusMeta::default()),
+            ..Default::default()
+        }),
+        slot,
+    };
+    StreamEntry {
+        stream: TRANSACTIONS.to_owned(),
+        data: update.encode_to_vec(),
+    }
+}
+
+async fn recv(consumer: &mut dyn MessageConsumer) -> Option<StreamMessage> {
+    timeout(Duration::from_millis(200), consumer.recv())
+        .await
+        .ok()
+        .flatten()
+}
+
+fn slot_of(message: &StreamMessage) -> u64 {
+    match message.get_data() {
+        ProgramTransformerInfo::Account(account) => account.slot,
+        ProgramTransformerInfo::Transaction(transaction) => transaction.slot,
+    }
+}
+
+#[tokio::test]
+async fn memory_bus_delivers_published_updates() -> anyhow::Result<()> {
+    let bus = MemoryBus::new(10);
+    let pubkey = Pubkey::new_unique();
+    let signature = Signature::new_unique();
+    bus.publish(vec![account(pubkey, 1), transaction(signature, 2)])
+        .await?;
+
+    let (mut consumer, tasks) = consumer(&bus);
+    let first = recv(consumer.as_mut()).await.expect("account delivered");
+    match first.get_data() {
+        ProgramTransformerInfo::Account(account) => {
+            assert_eq!((account.pubkey, account.slot), (pubkey, 1));
+            assert_eq!(account.data, vec![1, 2, 3]);
+        }
+        data => panic!("expected an account, got {data:?}"),
+    }
+    let second = recv(consumer.as_mut())
+        .await
+        .expect("transaction delivered");
+    match second.get_data() {
+        ProgramTransformerInfo::Transaction(transaction) => {
+            assert_eq!((transaction.signature, transaction.slot), (signature, 2));
+        }
+        data => panic!("expected a transaction, got {data:?}"),
+    }
+    assert!(recv(consumer.as_mut()).await.is_none());
+
+    first.ack()?;
+    second.ack()?;
+    consumer.shutdown();
+    tasks.await?;
+
+    // everything was acked, nothing is delivered again
+    let (mut consumer, tasks) = self::consumer(&bus);
+    assert!(recv(consumer.as_mut()).await.is_none());
+    consumer.shutdown();
+    tasks.await
+}
+
+#[tokio::test]
+async fn memory_bus_redelivers_unacked_messages() -> anyhow::Result<()> {
+    let bus = MemoryBus::new(10);
+    for slot in 1..=3 {
+        bus.publish(vec![account(Pubkey::new_unique(), slot)])
+            .await?;
+    }
+
+    let (mut consumer, tasks) = consumer(&bus);
+    let mut messages = vec![];
+    while let Some(message) = recv(consumer.as_mut()).await {
+        messages.push(message);
+    }
+    assert_eq!(
+        messages.iter().map(slot_of).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    let mut messages = messages.into_iter();
+    messages.next().expect("first message").ack()?;
+    drop(messages);
+    consumer.shutdown();
+    tasks.await?;
+
+    // a new consumer gets the unacked messages first, then the new ones
+    bus.publish(vec![account(Pubkey::new_unique(), 4)]).await?;
+    let (mut consumer, tasks) = self::consumer(&bus);
+    let mut slots = vec![];
+    while let Some(message) = recv(consumer.as_mut()).await {
+        slots.push(slot_of(&message));
+        message.ack()?;
+    }
+    assert_eq!(slots, vec![2, 3, 4]);
+    consumer.shutdown();
+    tasks.await
+}
+
+#[tokio::test]
+async fn memory_bus_fails_on_invalid_data() -> anyhow::Result<()> {
+    let bus = MemoryBus::new(10);
+    bus.publish(vec![StreamEntry {
+        stream: ACCOUNTS.to_owned(),
+        data: vec![0xff; 16],
+    }])
+    .await?;
+
+    let (mut consumer, tasks) = consumer(&bus);
+    let result = timeout(Duration::from_secs(5), tasks).await?;
+    assert!(result.is_err());
+    assert!(recv(consumer.as_mut()).await.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn memory_bus_publish_waits_for_room() -> anyhow::Result<()> {
+    let bus = MemoryBus::new(2);
+    for slot in 1..=2 {
+        bus.publish(vec![account(Pubkey::new_unique(), slot)])
+            .await?;
+    }
+
+    // the stream is full until the consumer takes an entry
+    let mut publish = bus.publish(vec![account(Pubkey::new_unique(), 3)]);
+    assert!(timeout(Duration::from_millis(100), &mut publish)
+        .await
+        .is_err());
+
+    let (mut consumer, tasks) = consumer(&bus);
+    timeout(Duration::from_secs(5), publish).await??;
+
+    let mut slots = vec![];
+    while let Some(message) = recv(consumer.as_mut()).await {
+        slots.push(slot_of(&message));
+        message.ack()?;
+    }
+    assert_eq!(slots, vec![1, 2, 3]);
+    timeout(Duration::from_secs(5), bus.drained(ACCOUNTS)).await?;
+    consumer.shutdown();
+    tasks.await
+}
+
+#[tokio::test]
+async fn memory_bus_counts_skipped_messages() -> anyhow::Result<()> {
+    let bus = MemoryBus::new(10);
+    for slot in 1..=2 {
+        bus.publish(vec![account(Pubkey::new_unique(), slot)])
+            .await?;
+    }
+
+    let (mut consumer, tasks) = consumer(&bus);
+    recv(consumer.as_mut())
+        .await
+        .expect("first message")
+        .ack()?;
+    recv(consumer.as_mut())
+        .await
+        .expect("second message")
+        .skip()?;
+    timeout(Duration::from_secs(5), bus.drained(ACCOUNTS)).await?;
+    assert_eq!(bus.skipped(), 1);
+
+    // skipped messages are acked, they are not delivered again
+    consumer.shutdown();
+    tasks.await?;
+    let (mut consumer, tasks) = self::consumer(&bus);
+    assert!(recv(consumer.as_mut()).await.is_none());
+    consumer.shutdown();
+    tasks.await
+}
+
+/// Bubblegum instruction reading an account the transaction doesn't have, the ingester fails
+/// to deserialize it before reaching the database.
+fn malformed_transaction(signature: Signature, slot: u64) -> StreamEntry {
+    let update = SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: signature.as_ref().to_vec(),
+            transaction: Some(Transaction {
+                signatures: vec![signature.as_ref().to_vec()],
+                message: Some(Message {
+                    account_keys: vec![pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY")
+                        .to_bytes()
+                        .to_vec()],
+                    instructions: vec![CompiledInstruction {
+                        program_id_index: 0,
+                        accounts: vec![5],
+                        data: vec![],
+                    }],
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta::default()),
+            ..Default::default()
+        }),
+        slot,
+    };
+    StreamEntry {
+        stream: TRANSACTIONS.to_owned(),
+        data: update.encode_to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn memory_bus_feeds_the_ingester() -> anyhow::Result<()> {
+    let bus = MemoryBus::new(10);
+    bus.publish(vec![
+        account(Pubkey::new_unique(), 1),
+        transaction(Signature::new_unique(), 2),
+        malformed_transaction(Signature::new_unique(), 3),
+    ])
+    .await?;
+
+    // nothing here is written, the database is never connected to
+    let pool = PgPoolOptions::new().connect_lazy("postgres://localhost/unused")?;
+    let program_transformer = || {
+        Arc::new(ProgramTransformer::new(
+            pool.clone(),
+            Box::new(|_info| ready(Ok(())).boxed()),
+            false,
+        ))
+    };
+    let (messages, tasks) = consumer(&bus);
+    timeout(
+        Duration::from_secs(5),
+        consume(
+            messages,
+            tasks,
+            program_transformer(),
+            program_transformer(),
+            10,
+            Some(Duration::from_millis(500)),
+        ),
+    )
+    .await??;
+
+    // every update is acked, the malformed transaction is counted as skipped
+    timeout(Duration::from_secs(5), bus.drained(ACCOUNTS)).await?;
+    timeout(Duration::from_secs(5), bus.drained(TRANSACTIONS)).await?;
+    assert_eq!(bus.skipped(), 1);
+    let (mut consumer, tasks) = consumer(&bus);
+    assert!(recv(consumer.as_mut()).await.is_none());
+    consumer.shutdown();
+    tasks.await
+}
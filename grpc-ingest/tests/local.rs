@@ -0,0 +1,36 @@
+use {
+    das_grpc_ingest::{local::transaction_update_from_info, redis::transaction_info_from_update},
+    program_transformers::TransactionInfo,
+    solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey, signature::Signature},
+    solana_transaction_status::{InnerInstruction, InnerInstructions},
+};
+
+#[test]
+fn polled_transactions_round_trip_through_updates() -> anyhow::Result<()> {
+    let transaction = TransactionInfo {
+        slot: 42,
+        signature: Signature::new_unique(),
+        account_keys: (0..4).map(|_| Pubkey::new_unique()).collect(),
+        message_instructions: vec![
+            CompiledInstruction::new_from_raw_parts(2, vec![1, 2, 3], vec![0, 1]),
+            CompiledInstruction::new_from_raw_parts(3, vec![], vec![1]),
+        ],
+        meta_inner_instructions: vec![InnerInstructions {
+            index: 1,
+            instructions: vec![
+                InnerInstruction {
+                    instruction: CompiledInstruction::new_from_raw_parts(2, vec![4], vec![0]),
+                    stack_height: Some(2),
+                },
+                InnerInstruction {
+                    instruction: CompiledInstruction::new_from_raw_parts(3, vec![5, 6], vec![]),
+                    stack_height: None,
+                },
+            ],
+        }],
+    };
+
+    let update = transaction_update_from_info(&transaction);
+    assert_eq!(transaction_info_from_update(update)?, transaction);
+    Ok(())
+}
@@ -0,0 +1,454 @@
+use {
+    das_grpc_ingest::{
+        config::ConfigPollSource,
+        poll::{Checkpoints, PollBatch, Poller},
+    },
+    hyper::{
+        body::to_bytes,
+        server::conn::AddrStream,
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    },
+    serde_json::{json, Value},
+    solana_sdk::{
+        hash::Hash,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        transaction::Transaction,
+    },
+    solana_transaction_status::{Encodable, UiTransactionEncoding},
+    std::{
+        convert::Infallible,
+        net::SocketAddr,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+};
+
+fn transaction_error(failed: bool) -> Value {
+    if failed {
+        json!({ "InstructionError": [0, "InvalidArgument"] })
+    } else {
+        Value::Null
+    }
+}
+
+struct MockTransaction {
+    signature: Signature,
+    slot: u64,
+    failed: bool,
+    encoded: Value,
+}
+
+/// JSON-RPC node answering `getSignaturesForAddress` and `getTransaction` for a single address.
+#[derive(Default)]
+struct MockRpc {
+    /// Newest first, like `getSignaturesForAddress`.
+    transactions: Mutex<Vec<MockTransaction>>,
+    requests: Mutex<Vec<(String, Value)>>,
+    /// Arrival of every request but `getVersion`, failed ones included.
+    received: Mutex<Vec<Instant>>,
+    /// Requests left to answer with an error.
+    failures: Mutex<usize>,
+}
+
+impl MockRpc {
+    fn push(&self, program: Pubkey, slot: u64, failed: bool) -> Signature {
+        let payer = Keypair::new();
+        let instruction = Instruction::new_with_bytes(program, &slot.to_le_bytes(), vec![]);
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        );
+        let signature = transaction.signatures[0];
+        let err = transaction_error(failed);
+        let encoded = json!({
+            "slot": slot,
+            "blockTime": null,
+            "transaction": transaction.encode(UiTransactionEncoding::Base58),
+            "meta": {
+                "err": err,
+                "status": if failed { json!({ "Err": err }) } else { json!({ "Ok": null }) },
+                "fee": 5000,
+                "preBalances": [],
+                "postBalances": [],
+                "innerInstructions": [],
+                "logMessages": [],
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": [],
+            },
+        });
+        self.transactions.lock().unwrap().insert(
+            0,
+            MockTransaction {
+                signature,
+                slot,
+                failed,
+                encoded,
+            },
+        );
+        signature
+    }
+
+    fn requests(&self, method: &str) -> Vec<Value> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name == method)
+            .map(|(_, params)| params.clone())
+            .collect()
+    }
+
+    fn result(&self, method: &str, params: &Value) -> Value {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), params.clone()));
+        let transactions = self.transactions.lock().unwrap();
+        match method {
+            "getVersion" => json!({ "solana-core": "1.17.28", "feature-set": 0 }),
+            "getSignaturesForAddress" => {
+                let config = &params[1];
+                let before = config["before"].as_str();
+                let until = config["until"].as_str();
+                let limit = config["limit"].as_u64().unwrap_or(1_000) as usize;
+                let signatures = transactions
+                    .iter()
+                    .skip_while(|tx| {
+                        before.map_or(false, |before| tx.signature.to_string() != before)
+                    })
+                    .skip(usize::from(before.is_some()))
+                    .take_while(|tx| until.map_or(true, |until| tx.signature.to_string() != until))
+                    .take(limit)
+                    .map(|tx| {
+                        json!({
+                            "signature": tx.signature.to_string(),
+                            "slot": tx.slot,
+                            "err": transaction_error(tx.failed),
+                            "memo": null,
+                            "blockTime": null,
+                            "confirmationStatus": "finalized",
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!(signatures)
+            }
+            "getTransaction" => {
+                let signature = params[0].as_str().unwrap_or_default();
+                transactions
+                    .iter()
+                    .find(|tx| tx.signature.to_string() == signature)
+                    .map_or(Value::Null, |tx| tx.encoded.clone())
+            }
+            _ => Value::Null,
+        }
+    }
+
+    async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        let body = to_bytes(request.into_body()).await.unwrap_or_default();
+        let request: Value = serde_json::from_slice(&body).unwrap_or_default();
+        let method = request["method"].as_str().unwrap_or_default();
+        // The client may check the node version first, it is never rate limited.
+        let failed = method != "getVersion" && {
+            self.received.lock().unwrap().push(Instant::now());
+            let mut failures = self.failures.lock().unwrap();
+            let failed = *failures > 0;
+            *failures = failures.saturating_sub(1);
+            failed
+        };
+        let response = if failed {
+            json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32005, "message": "node is behind" },
+            })
+        } else {
+            json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": self.result(method, &request["params"]),
+            })
+        };
+        Response::new(Body::from(response.to_string()))
+    }
+}
+
+fn serve(mock: &Arc<MockRpc>) -> SocketAddr {
+    let mock = Arc::clone(mock);
+    let make_service = make_service_fn(move |_: &AddrStream| {
+        let mock = Arc::clone(&mock);
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let mock = Arc::clone(&mock);
+                async move { Ok::<_, Infallible>(mock.handle(request).await) }
+            }))
+        }
+    });
+    let server = Server::try_bind(&"127.0.0.1:0".parse().unwrap())
+        .expect("bind mock RPC")
+        .serve(make_service);
+    let address = server.local_addr();
+    tokio::spawn(server);
+    address
+}
+
+fn checkpoint_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("das-poll-{name}-{}.json", Pubkey::new_unique()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn config(rpc: SocketAddr, address: Pubkey, checkpoint_file: PathBuf) -> ConfigPollSource {
+    ConfigPollSource {
+        rpc_url: format!("http://{rpc}"),
+        addresses: vec![address.to_string()],
+        interval: Duration::from_millis(10),
+        max_requests_per_second: 1_000,
+        checkpoint_file,
+        backfill: false,
+        signatures_page_limit: 1_000,
+    }
+}
+
+#[tokio::test]
+async fn poll_follows_signatures_from_checkpoint() -> anyhow::Result<()> {
+    let mock = Arc::new(MockRpc::default());
+    let rpc = serve(&mock);
+    let program = Pubkey::new_unique();
+    let checkpoint_file = checkpoint_file("follow");
+    let config = config(rpc, program, checkpoint_file.clone());
+
+    let old = mock.push(program, 10, false);
+    let mut poller = Poller::new(&config).await?;
+
+    // Without a checkpoint the poller starts from the latest signature.
+    let batch = poller.poll(program).await?;
+    assert!(batch.transactions.is_empty());
+    poller.commit(batch).await?;
+    let checkpoint = Checkpoints::load(&checkpoint_file).await?.get(&program);
+    assert_eq!(checkpoint.map(|c| (c.signature, c.slot)), Some((old, 10)));
+
+    let first = mock.push(program, 11, false);
+    let failed = mock.push(program, 12, true);
+    let second = mock.push(program, 13, false);
+
+    let batch = poller.poll(program).await?;
+    let signatures = batch
+        .transactions
+        .iter()
+        .map(|tx| (tx.signature, tx.slot))
+        .collect::<Vec<_>>();
+    assert_eq!(signatures, vec![(first, 11), (second, 13)]);
+    assert!(!mock
+        .requests("getTransaction")
+        .iter()
+        .any(|params| params[0] == failed.to_string()));
+    assert!(mock
+        .requests("getSignaturesForAddress")
+        .iter()
+        .all(|params| params[1]["until"] == old.to_string() || params[1]["until"].is_null()));
+
+    // Not committed yet, a restarted poller fetches the batch again.
+    let mut restarted = Poller::new(&config).await?;
+    assert_eq!(restarted.poll(program).await?.transactions.len(), 2);
+
+    poller.commit(batch).await?;
+    let checkpoint = Checkpoints::load(&checkpoint_file).await?.get(&program);
+    assert_eq!(
+        checkpoint.map(|c| (c.signature, c.slot)),
+        Some((second, 13))
+    );
+
+    let mut restarted = Poller::new(&config).await?;
+    assert!(restarted.poll(program).await?.transactions.is_empty());
+
+    let _ = std::fs::remove_file(&checkpoint_file);
+    Ok(())
+}
+
+#[tokio::test]
+async fn poll_backfills_history_oldest_first() -> anyhow::Result<()> {
+    let mock = Arc::new(MockRpc::default());
+    let rpc = serve(&mock);
+    let tree = Pubkey::new_unique();
+    let checkpoint_file = checkpoint_file("backfill");
+    let config = ConfigPollSource {
+        backfill: true,
+        ..config(rpc, tree, checkpoint_file.clone())
+    };
+
+    let signatures = (1..=5)
+        .map(|slot| mock.push(tree, slot, false))
+        .collect::<Vec<_>>();
+
+    let mut poller = Poller::new(&config).await?;
+    let batch = poller.poll(tree).await?;
+    assert_eq!(
+        batch
+            .transactions
+            .iter()
+            .map(|tx| tx.signature)
+            .collect::<Vec<_>>(),
+        signatures
+    );
+    poller.commit(batch).await?;
+    assert!(poller.poll(tree).await?.transactions.is_empty());
+
+    let _ = std::fs::remove_file(&checkpoint_file);
+    Ok(())
+}
+
+#[tokio::test]
+async fn poll_bounds_request_rate() -> anyhow::Result<()> {
+    let mock = Arc::new(MockRpc::default());
+    let rpc = serve(&mock);
+    let program = Pubkey::new_unique();
+    let checkpoint_file = checkpoint_file("rate");
+    let config = ConfigPollSource {
+        max_requests_per_second: 20,
+        backfill: true,
+        ..config(rpc, program, checkpoint_file.clone())
+    };
+    for slot in 1..=4 {
+        mock.push(program, slot, false);
+    }
+
+    // One `getSignaturesForAddress` and four `getTransaction`, 50ms apart.
+    let mut poller = Poller::new(&config).await?;
+    let started = tokio::time::Instant::now();
+    assert_eq!(poller.poll(program).await?.transactions.len(), 4);
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    let _ = std::fs::remove_file(&checkpoint_file);
+    Ok(())
+}
+
+#[tokio::test]
+async fn poll_slots_fetches_a_gap_without_moving_the_checkpoint() -> anyhow::Result<()> {
+    let mock = Arc::new(MockRpc::default());
+    let rpc = serve(&mock);
+    let program = Pubkey::new_unique();
+    let checkpoint_file = checkpoint_file("gap");
+    let config = config(rpc, program, checkpoint_file.clone());
+
+    let before = mock.push(program, 9, false);
+    let first = mock.push(program, 10, false);
+    mock.push(program, 11, true);
+    let second = mock.push(program, 12, false);
+    let after = mock.push(program, 13, false);
+
+    let mut poller = Poller::new(&config).await?;
+    let transactions = poller.poll_slots(program, 10, 12).await?;
+    assert_eq!(
+        transactions
+            .iter()
+            .map(|tx| (tx.signature, tx.slot))
+            .collect::<Vec<_>>(),
+        vec![(first, 10), (second, 12)]
+    );
+    assert!(!mock
+        .requests("getTransaction")
+        .iter()
+        .any(|params| params[0] == before.to_string() || params[0] == after.to_string()));
+    assert!(Checkpoints::load(&checkpoint_file)
+        .await?
+        .get(&program)
+        .is_none());
+
+    let _ = std::fs::remove_file(&checkpoint_file);
+    Ok(())
+}
+
+fn slots(batch: &PollBatch) -> Vec<u64> {
+    batch.transactions.iter().map(|tx| tx.slot).collect()
+}
+
+#[tokio::test]
+async fn poll_checkpoints_every_page_oldest_first() -> anyhow::Result<()> {
+    let mock = Arc::new(MockRpc::default());
+    let rpc = serve(&mock);
+    let tree = Pubkey::new_unique();
+    let checkpoint_file = checkpoint_file("pages");
+    let config = ConfigPollSource {
+        backfill: true,
+        signatures_page_limit: 2,
+        ..config(rpc, tree, checkpoint_file.clone())
+    };
+    let signatures = (1..=5)
+        .map(|slot| mock.push(tree, slot, false))
+        .collect::<Vec<_>>();
+
+    let mut poller = Poller::new(&config).await?;
+    let batch = poller.poll(tree).await?;
+    assert_eq!(slots(&batch), vec![1]);
+    assert!(batch.has_more());
+
+    // Not committed, the same page is fetched again.
+    let mut restarted = Poller::new(&config).await?;
+    assert_eq!(slots(&restarted.poll(tree).await?), vec![1]);
+
+    poller.commit(batch).await?;
+    let checkpoint = Checkpoints::load(&checkpoint_file).await?.get(&tree);
+    assert_eq!(checkpoint.map(|c| c.signature), Some(signatures[0]));
+
+    // A restarted poller walks back to the checkpoint again.
+    let mut restarted = Poller::new(&config).await?;
+    assert_eq!(slots(&restarted.poll(tree).await?), vec![2, 3]);
+
+    let batch = poller.poll(tree).await?;
+    assert_eq!(slots(&batch), vec![2, 3]);
+    assert!(batch.has_more());
+    poller.commit(batch).await?;
+    let checkpoint = Checkpoints::load(&checkpoint_file).await?.get(&tree);
+    assert_eq!(checkpoint.map(|c| c.signature), Some(signatures[2]));
+
+    let batch = poller.poll(tree).await?;
+    assert_eq!(slots(&batch), vec![4, 5]);
+    assert!(!batch.has_more());
+    poller.commit(batch).await?;
+    let checkpoint = Checkpoints::load(&checkpoint_file).await?.get(&tree);
+    assert_eq!(checkpoint.map(|c| c.signature), Some(signatures[4]));
+
+    assert!(poller.poll(tree).await?.transactions.is_empty());
+    assert!(mock
+        .requests("getSignaturesForAddress")
+        .iter()
+        .all(|params| params[1]["limit"] == 2));
+
+    let _ = std::fs::remove_file(&checkpoint_file);
+    Ok(())
+}
+
+#[tokio::test]
+async fn poll_retries_within_the_rate_limit() -> anyhow::Result<()> {
+    let mock = Arc::new(MockRpc::default());
+    let rpc = serve(&mock);
+    let program = Pubkey::new_unique();
+    let checkpoint_file = checkpoint_file("retry");
+    let config = ConfigPollSource {
+        max_requests_per_second: 20,
+        backfill: true,
+        ..config(rpc, program, checkpoint_file.clone())
+    };
+    mock.push(program, 1, false);
+    *mock.failures.lock().unwrap() = 2;
+
+    let mut poller = Poller::new(&config).await?;
+    assert_eq!(slots(&poller.poll(program).await?), vec![1]);
+
+    // Two failed `getSignaturesForAddress`, then one of each method.
+    let received = mock.received.lock().unwrap().clone();
+    assert_eq!(received.len(), 4);
+    assert!(received
+        .windows(2)
+        .all(|w| w[1] - w[0] >= Duration::from_millis(40)));
+
+    let _ = std::fs::remove_file(&checkpoint_file);
+    Ok(())
+}
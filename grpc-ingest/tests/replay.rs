@@ -0,0 +1,156 @@
+use {
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
+    das_grpc_ingest::{
+        redis::transaction_info_from_update,
+        replay::{load_updates, read_updates, ReplayFormat, ReplayUpdate},
+    },
+    serde_json::json,
+    solana_sdk::{pubkey::Pubkey, signature::Signature},
+    std::{
+        path::{Path, PathBuf},
+        str::FromStr,
+    },
+    yellowstone_grpc_proto::{
+        prelude::{subscribe_update::UpdateOneof, SubscribeUpdate},
+        prost::Message,
+    },
+};
+
+const TXN_CACHE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../tools/redis_cache/TXN_CACHE.json"
+);
+
+fn dump_file(extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("das-replay-{}.{extension}", Pubkey::new_unique()))
+}
+
+fn slots_and_signatures(updates: Vec<ReplayUpdate>) -> anyhow::Result<Vec<(u64, Signature)>> {
+    updates
+        .into_iter()
+        .map(|update| match update {
+            ReplayUpdate::Transaction(update) => {
+                let transaction = transaction_info_from_update(update)?;
+                Ok((transaction.slot, transaction.signature))
+            }
+            ReplayUpdate::Account(_) => anyhow::bail!("unexpected account update"),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn read_txn_cache_dump() -> anyhow::Result<()> {
+    let updates = read_updates(Path::new(TXN_CACHE), ReplayFormat::Json).await?;
+    let transactions = slots_and_signatures(updates)?;
+
+    assert_eq!(transactions.len(), 19);
+    assert_eq!(
+        transactions.first(),
+        Some(&(
+            537966,
+            Signature::from_str(
+                "3jFQe7GBtNU4rXASazQzHpNpLAHefNipznArZuY9cA7otgWq9Vwg9NQQL1pLvxo5br3g9josjpmrP3hjTxnAB9Du"
+            )?
+        ))
+    );
+    assert_eq!(
+        transactions.last(),
+        Some(&(
+            553071,
+            Signature::from_str(
+                "2agCzx9Dr1q8fwi1ZsS576m1G2Yk4askvbogj8nWuLnbg8zUau9oKENNyGuXd7mQmzByFSpz2R8D3R8DyFGu49Uw"
+            )?
+        ))
+    );
+    assert!(transactions.windows(2).all(|w| w[0].0 <= w[1].0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn every_format_reads_the_same_updates() -> anyhow::Result<()> {
+    let updates = read_updates(Path::new(TXN_CACHE), ReplayFormat::Json).await?;
+    let encoded = updates
+        .into_iter()
+        .map(|update| match update {
+            ReplayUpdate::Transaction(update) => Ok(update),
+            ReplayUpdate::Account(_) => anyhow::bail!("unexpected account update"),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let jsonl = encoded
+        .iter()
+        .map(|update| {
+            json!({ "type": "transaction", "data": BASE64.encode(update.encode_to_vec()) })
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut protobuf = Vec::new();
+    for update in encoded.iter().cloned() {
+        SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Transaction(update)),
+            ..Default::default()
+        }
+        .encode_length_delimited(&mut protobuf)?;
+    }
+
+    let jsonl_file = dump_file("jsonl");
+    let protobuf_file = dump_file("pb");
+    tokio::fs::write(&jsonl_file, jsonl).await?;
+    tokio::fs::write(&protobuf_file, protobuf).await?;
+
+    let expected =
+        slots_and_signatures(read_updates(Path::new(TXN_CACHE), ReplayFormat::Json).await?)?;
+    assert_eq!(
+        slots_and_signatures(load_updates(&jsonl_file, None, false).await?)?,
+        expected
+    );
+    assert_eq!(
+        slots_and_signatures(load_updates(&protobuf_file, None, false).await?)?,
+        expected
+    );
+
+    let _ = std::fs::remove_file(&jsonl_file);
+    let _ = std::fs::remove_file(&protobuf_file);
+    Ok(())
+}
+
+#[tokio::test]
+async fn sort_orders_updates_by_slot() -> anyhow::Result<()> {
+    let updates = read_updates(Path::new(TXN_CACHE), ReplayFormat::Json).await?;
+    let mut entries = Vec::new();
+    for update in updates.into_iter().rev() {
+        let ReplayUpdate::Transaction(update) = update else {
+            anyhow::bail!("unexpected account update");
+        };
+        entries
+            .push(json!({ "type": "transaction", "data": BASE64.encode(update.encode_to_vec()) }));
+    }
+    let file = dump_file("json");
+    tokio::fs::write(&file, serde_json::to_vec(&entries)?).await?;
+
+    let unsorted = slots_and_signatures(load_updates(&file, None, false).await?)?;
+    assert!(unsorted.windows(2).all(|w| w[0].0 >= w[1].0));
+    let sorted = slots_and_signatures(load_updates(&file, None, true).await?)?;
+    assert_eq!(
+        sorted,
+        slots_and_signatures(read_updates(Path::new(TXN_CACHE), ReplayFormat::Json).await?)?
+    );
+
+    let _ = std::fs::remove_file(&file);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_extension_needs_a_format() {
+    assert!(load_updates(&dump_file("txt"), None, false).await.is_err());
+}
+
+#[tokio::test]
+async fn truncated_txn_cache_entry_is_rejected() -> anyhow::Result<()> {
+    let file = dump_file("json");
+    tokio::fs::write(&file, "[[0, 10, 174]]").await?;
+    assert!(read_updates(&file, ReplayFormat::Json).await.is_err());
+    let _ = std::fs::remove_file(&file);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use das_grpc_ingest::slots::SlotTracker;
+
+#[test]
+fn checkpoints_follow_confirmed_slots_only() {
+    let mut slots = SlotTracker::new([None]);
+
+    // processed updates arrive out of order and from forks, they never move the checkpoint
+    for slot in [12, 10, 11, 15] {
+        assert_eq!(slots.observe(0, slot), None);
+    }
+    let batch = slots.cut_batch();
+    assert_eq!(slots.complete(batch), vec![]);
+
+    slots.confirmed(0, 10);
+    slots.confirmed(0, 11);
+    // a late confirmation never moves the checkpoint back
+    slots.confirmed(0, 9);
+    let batch = slots.cut_batch();
+    assert_eq!(slots.complete(batch), vec![(0, 11)]);
+
+    // nothing new was confirmed
+    let batch = slots.cut_batch();
+    assert_eq!(slots.complete(batch), vec![]);
+}
+
+#[test]
+fn checkpoints_wait_for_earlier_pipelines() {
+    let mut slots = SlotTracker::new([Some(100), None]);
+
+    slots.confirmed(0, 101);
+    let first = slots.cut_batch();
+    slots.confirmed(0, 102);
+    slots.confirmed(1, 7);
+    let second = slots.cut_batch();
+
+    // the second pipeline ran first, its slots are only stored once the first one ran too
+    assert_eq!(slots.complete(second), vec![]);
+    assert_eq!(slots.complete(first), vec![(0, 102), (1, 7)]);
+}
+
+#[test]
+fn resubscribing_reports_the_slots_missed_since_the_last_confirmed_one() {
+    let mut slots = SlotTracker::new([Some(100), None]);
+
+    // resuming from a checkpoint
+    slots.subscribed(0);
+    assert_eq!(slots.observe(0, 120), Some((101, 120)));
+    // only the first update after subscribing can reveal a gap
+    assert_eq!(slots.observe(0, 140), None);
+
+    slots.confirmed(0, 130);
+    slots.subscribed(0);
+    assert_eq!(slots.observe(0, 131), Some((131, 131)));
+
+    // updates older than the confirmed slot missed nothing
+    slots.subscribed(0);
+    assert_eq!(slots.observe(0, 125), None);
+
+    // endpoints without a checkpoint start from their first update
+    slots.subscribed(1);
+    assert_eq!(slots.observe(1, 50), None);
+
+    // unknown endpoints are ignored
+    slots.subscribed(2);
+    slots.confirmed(2, 1);
+    assert_eq!(slots.observe(2, 1), None);
+}
@@ -0,0 +1,171 @@
+use {
+    das_grpc_ingest::{
+        config::{ConfigEventFilter, ConfigWebhooksDispatcher},
+        webhooks::{deliver, retry_delay},
+    },
+    hyper::{
+        body::to_bytes,
+        server::conn::AddrStream,
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server, StatusCode,
+    },
+    program_transformers::events::DomainEvent,
+    reqwest::Client,
+    serde_json::{json, Value},
+    std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+};
+
+fn event(event_type: &str, program: &str, project: Option<&str>) -> DomainEvent {
+    DomainEvent {
+        event_type: event_type.to_owned(),
+        program: program.to_owned(),
+        project: project.map(str::to_owned),
+        subject: "subject".to_owned(),
+        slot: 1,
+        signature: None,
+        before: None,
+        after: Some(json!({ "used_by": "Staking" })),
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| (*value).to_owned()).collect()
+}
+
+#[test]
+fn event_filter_matches_projects_programs_and_event_types() {
+    let everything = ConfigEventFilter::default();
+    assert!(everything.matches(&event("Staked", "character", None)));
+
+    let filter = ConfigEventFilter {
+        projects: strings(&["game"]),
+        programs: strings(&["character"]),
+        event_types: strings(&["Staked", "UnStaked"]),
+    };
+    assert!(filter.matches(&event("Staked", "character", Some("game"))));
+    assert!(filter.matches(&event("UnStaked", "character", Some("game"))));
+    assert!(!filter.matches(&event("Wrapped", "character", Some("game"))));
+    assert!(!filter.matches(&event("Staked", "currency", Some("game"))));
+    assert!(!filter.matches(&event("Staked", "character", Some("other"))));
+    // events without a project never match a project filter
+    assert!(!filter.matches(&event("Staked", "character", None)));
+}
+
+#[test]
+fn retry_delay_doubles_up_to_the_maximum() {
+    let config = ConfigWebhooksDispatcher {
+        retry_base: Duration::from_secs(1),
+        retry_max: Duration::from_secs(10),
+        ..Default::default()
+    };
+    let delays = (1..=6)
+        .map(|attempts| retry_delay(&config, attempts).as_secs())
+        .collect::<Vec<_>>();
+    assert_eq!(delays, vec![1, 2, 4, 8, 10, 10]);
+    assert_eq!(retry_delay(&config, i32::MAX), Duration::from_secs(10));
+}
+
+/// Webhook receiver recording every request and answering with `status`.
+struct MockReceiver {
+    status: StatusCode,
+    requests: Mutex<Vec<(Vec<(String, String)>, Value)>>,
+}
+
+fn serve(receiver: &Arc<MockReceiver>) -> SocketAddr {
+    let receiver = Arc::clone(receiver);
+    let make_service = make_service_fn(move |_: &AddrStream| {
+        let receiver = Arc::clone(&receiver);
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let receiver = Arc::clone(&receiver);
+                async move {
+                    let headers = request
+                        .headers()
+                        .iter()
+                        .filter(|(name, _)| name.as_str().starts_with("x-das-"))
+                        .map(|(name, value)| (name.to_string(), value.to_str().unwrap().to_owned()))
+                        .collect();
+                    let body = to_bytes(request.into_body()).await.unwrap_or_default();
+                    let body = serde_json::from_slice(&body).unwrap_or_default();
+                    receiver.requests.lock().unwrap().push((headers, body));
+                    let mut response = Response::new(Body::empty());
+                    *response.status_mut() = receiver.status;
+                    Ok::<_, Infallible>(response)
+                }
+            }))
+        }
+    });
+    let server = Server::try_bind(&"127.0.0.1:0".parse().unwrap())
+        .expect("bind mock receiver")
+        .serve(make_service);
+    let address = server.local_addr();
+    tokio::spawn(server);
+    address
+}
+
+#[tokio::test]
+async fn deliver_posts_the_event_with_its_ids() {
+    let receiver = Arc::new(MockReceiver {
+        status: StatusCode::NO_CONTENT,
+        requests: Mutex::default(),
+    });
+    let address = serve(&receiver);
+    let payload = serde_json::to_value(event("Staked", "character", Some("game"))).unwrap();
+
+    let result = deliver(
+        &Client::new(),
+        &format!("http://{address}/hook"),
+        "game-hook",
+        "1700000000000-0",
+        &payload,
+    )
+    .await;
+    assert_eq!(result, Ok(()));
+
+    let requests = receiver.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    let (headers, body) = &requests[0];
+    assert!(headers.contains(&("x-das-webhook".to_owned(), "game-hook".to_owned())));
+    assert!(headers.contains(&("x-das-event-id".to_owned(), "1700000000000-0".to_owned())));
+    assert_eq!(body, &payload);
+}
+
+#[tokio::test]
+async fn deliver_fails_on_error_status_and_unreachable_receivers() {
+    let receiver = Arc::new(MockReceiver {
+        status: StatusCode::INTERNAL_SERVER_ERROR,
+        requests: Mutex::default(),
+    });
+    let address = serve(&receiver);
+    let payload = json!({ "event_type": "Staked" });
+
+    let result = deliver(
+        &Client::new(),
+        &format!("http://{address}/hook"),
+        "game-hook",
+        "1-0",
+        &payload,
+    )
+    .await;
+    assert!(result.unwrap_err().contains("500"));
+
+    // nothing listens on the port of a dropped listener
+    let unused = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let result = deliver(
+        &Client::new(),
+        &format!("http://{unused}/hook"),
+        "game-hook",
+        "1-0",
+        &payload,
+    )
+    .await;
+    assert!(result.is_err());
+}
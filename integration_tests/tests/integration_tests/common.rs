@@ -22,7 +22,7 @@ use plerkle_serialization::{
     serializer::{seralize_encoded_transaction_with_status, serialize_account},
     solana_geyser_plugin_interface_shims::ReplicaAccountInfoV2,
 };
-use program_transformers::ProgramTransformer;
+use program_transformers::{dry_run::DryRun, ProgramTransformer, TransactionInfo};
 
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
@@ -62,7 +62,7 @@ pub const DEFAULT_SLOT: u64 = 1;
 
 pub struct TestSetup {
     pub name: String,
-    pub client: RpcClient,
+    pub rpc: FixtureRpc,
     pub db: Arc<DatabaseConnection>,
     pub transformer: ProgramTransformer,
     pub das_api: DasApi,
@@ -86,11 +86,7 @@ impl TestSetup {
         let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
         let transformer = load_ingest_program_transformer(pool.clone()).await;
 
-        let rpc_url = match opts.network.unwrap_or_default() {
-            Network::Mainnet => std::env::var("MAINNET_RPC_URL").unwrap(),
-            Network::Devnet => std::env::var("DEVNET_RPC_URL").unwrap(),
-        };
-        let client = RpcClient::new(rpc_url.to_string());
+        let rpc = FixtureRpc::from_env(opts.network.unwrap_or_default());
 
         let das_api_config: Config = das_api::config::Config {
             database_url: database_test_url.to_string(),
@@ -102,7 +98,7 @@ impl TestSetup {
 
         TestSetup {
             name,
-            client,
+            rpc,
             db: Arc::new(db),
             transformer,
             das_api,
@@ -115,6 +111,48 @@ pub struct TestSetupOptions {
     pub network: Option<Network>,
 }
 
+/// Resolves the fixtures under `tests/data`.
+///
+/// Tests replay recorded fixtures by default and never reach the network. `RECORD_FIXTURES=missing`
+/// fetches the fixtures that aren't recorded yet and `RECORD_FIXTURES=all` re-records every
+/// fixture a test reads, from `MAINNET_RPC_URL` or `DEVNET_RPC_URL` depending on the test network.
+pub enum FixtureRpc {
+    Replay,
+    Record { client: RpcClient, overwrite: bool },
+}
+
+impl FixtureRpc {
+    pub fn from_env(network: Network) -> Self {
+        let overwrite = match std::env::var("RECORD_FIXTURES").as_deref() {
+            Err(_) | Ok("") => return Self::Replay,
+            Ok("missing") => false,
+            Ok("all") => true,
+            Ok(mode) => panic!("RECORD_FIXTURES must be `missing` or `all`, got `{mode}`"),
+        };
+        let var = network.rpc_url_var();
+        let rpc_url = std::env::var(var)
+            .unwrap_or_else(|_| panic!("RECORD_FIXTURES requires {var} to be set"));
+
+        Self::Record {
+            client: RpcClient::new(rpc_url),
+            overwrite,
+        }
+    }
+
+    /// Client to record `fixture` with, `None` when the recorded file is used as is.
+    pub fn recorder(&self, fixture: &Path) -> anyhow::Result<Option<&RpcClient>> {
+        match self {
+            Self::Record { client, overwrite } if *overwrite || !fixture.exists() => Ok(Some(client)),
+            Self::Record { .. } => Ok(None),
+            Self::Replay if fixture.exists() => Ok(None),
+            Self::Replay => anyhow::bail!(
+                "missing fixture {}, record it with RECORD_FIXTURES=missing and the RPC URL of the test network",
+                fixture.display()
+            ),
+        }
+    }
+}
+
 pub async fn setup_pg_pool(database_url: String) -> PgPool {
     let options: PgConnectOptions = database_url.parse().unwrap();
     PgPoolOptions::new()
@@ -138,7 +176,6 @@ static INIT: Lazy<Mutex<Option<()>>> = Lazy::new(|| Mutex::new(None));
 pub async fn apply_migrations_and_delete_data(db: Arc<DatabaseConnection>) {
     let mut init = INIT.lock().await;
     if init.is_none() {
-        std::env::set_var("INIT_FILE_PATH", "../init.sql");
         Migrator::fresh(&db).await.unwrap();
         *init = Some(());
         // Mutex will dropped once it goes out of scope.
@@ -212,6 +249,13 @@ pub async fn fetch_and_serialize_transaction(
     let tx: EncodedConfirmedTransactionWithStatusMeta =
         get_transaction(client, sig, max_retries).await?;
 
+    serialize_transaction(sig, tx)
+}
+
+pub fn serialize_transaction(
+    sig: Signature,
+    tx: EncodedConfirmedTransactionWithStatusMeta,
+) -> anyhow::Result<Option<Vec<u8>>> {
     // Ignore if tx failed or meta is missed
     let meta = tx.transaction.meta.as_ref();
     if meta.map(|meta| meta.status.is_err()).unwrap_or(true) {
@@ -381,54 +425,83 @@ fn get_relative_project_path(path: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path)
 }
 
+fn write_fixture(file_path: &Path, contents: &[u8]) {
+    if let Some(dir) = file_path.parent() {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(file_path, contents).unwrap();
+}
+
 async fn cached_fetch_account_with_error_handling(
     setup: &TestSetup,
     account: Pubkey,
     slot: Option<u64>,
 ) -> anyhow::Result<Vec<u8>> {
-    let dir = get_relative_project_path(&format!("tests/data/accounts/{}", setup.name));
-
-    if !Path::new(&dir).exists() {
-        std::fs::create_dir(&dir).unwrap();
+    let file_path =
+        get_relative_project_path(&format!("tests/data/accounts/{}/{}", setup.name, account));
+
+    match setup.rpc.recorder(&file_path)? {
+        Some(client) => {
+            let account_bytes = fetch_and_serialize_account(client, account, slot).await?;
+            write_fixture(&file_path, &account_bytes);
+            Ok(account_bytes)
+        }
+        None => Ok(std::fs::read(file_path)?),
     }
-    let file_path = dir.join(format!("{}", account));
+}
 
-    if file_path.exists() {
-        Ok(std::fs::read(file_path).unwrap())
+/// Transactions are recorded as their `getTransaction` JSON result in `<signature>.json`, which
+/// keeps the noop inner instructions Honeycomb programs log their compressed data through and
+/// can be reviewed or decoded with `das-ops inspect transaction --file`. Fixtures recorded
+/// before as plerkle flatbuffers, without extension, are still read as is.
+pub async fn cached_fetch_transaction_bytes(
+    rpc: &FixtureRpc,
+    name: &str,
+    sig: Signature,
+) -> anyhow::Result<Vec<u8>> {
+    let dir = get_relative_project_path(&format!("tests/data/transactions/{}", name));
+    let flatbuffer_path = dir.join(sig.to_string());
+    let file_path = dir.join(format!("{}.json", sig));
+    let recorded = if flatbuffer_path.exists() {
+        &flatbuffer_path
     } else {
-        let account_bytes = fetch_and_serialize_account(&setup.client, account, slot).await?;
-        std::fs::write(file_path, &account_bytes).unwrap();
-        Ok(account_bytes)
-    }
-}
+        &file_path
+    };
 
-async fn cached_fetch_transaction(setup: &TestSetup, sig: Signature) -> Vec<u8> {
-    let dir = get_relative_project_path(&format!("tests/data/transactions/{}", setup.name));
+    let tx = match rpc.recorder(recorded)? {
+        Some(client) => {
+            let tx = get_transaction(client, sig, 5).await?;
+            write_fixture(&file_path, &serde_json::to_vec_pretty(&tx)?);
+            if flatbuffer_path.exists() {
+                std::fs::remove_file(&flatbuffer_path)?;
+            }
+            tx
+        }
+        None if recorded == &flatbuffer_path => return Ok(std::fs::read(&flatbuffer_path)?),
+        None => serde_json::from_slice(&std::fs::read(&file_path)?)?,
+    };
 
-    if !Path::new(&dir).exists() {
-        std::fs::create_dir(&dir).unwrap();
-    }
-    let file_path = dir.join(format!("{}", sig));
+    serialize_transaction(sig, tx)?
+        .ok_or_else(|| anyhow::anyhow!("transaction {sig} failed or has no status meta"))
+}
 
-    if file_path.exists() {
-        std::fs::read(file_path).unwrap()
-    } else {
-        let txn_bytes = fetch_and_serialize_transaction(&setup.client, sig)
-            .await
-            .unwrap()
-            .unwrap();
-        std::fs::write(file_path, &txn_bytes).unwrap();
-        txn_bytes
-    }
+async fn cached_fetch_transaction(setup: &TestSetup, sig: Signature) -> Vec<u8> {
+    cached_fetch_transaction_bytes(&setup.rpc, &setup.name, sig)
+        .await
+        .unwrap()
 }
 
-pub async fn index_transaction(setup: &TestSetup, sig: Signature) {
+pub async fn transaction_info(setup: &TestSetup, sig: Signature) -> TransactionInfo {
     let txn_bytes: Vec<u8> = cached_fetch_transaction(setup, sig).await;
     let txn = root_as_transaction_info(&txn_bytes).unwrap();
 
-    let transaction_info = PlerkleTransactionInfo(txn)
+    PlerkleTransactionInfo(txn)
         .try_into()
-        .expect("failed to parse txn");
+        .expect("failed to parse txn")
+}
+
+pub async fn index_transaction(setup: &TestSetup, sig: Signature) {
+    let transaction_info = transaction_info(setup, sig).await;
 
     setup
         .transformer
@@ -437,20 +510,54 @@ pub async fn index_transaction(setup: &TestSetup, sig: Signature) {
         .unwrap();
 }
 
-async fn cached_fetch_largest_token_account_id(client: &RpcClient, mint: Pubkey) -> Pubkey {
-    let dir = get_relative_project_path(&format!("tests/data/largest_token_account_ids/{}", mint));
+pub async fn dry_run_account(setup: &TestSetup, account: Pubkey) -> DryRun {
+    let account_bytes = cached_fetch_account(setup, account, None).await;
+    let account = root_as_account_info(&account_bytes).unwrap();
 
-    if !Path::new(&dir).exists() {
-        std::fs::create_dir(&dir).unwrap();
-    }
-    let file_path = dir.join(format!("{}", mint));
+    let account = PlerkleAccountInfo(account)
+        .try_into()
+        .expect("failed to parse account info");
 
-    if file_path.exists() {
-        Pubkey::try_from(std::fs::read(file_path).unwrap()).unwrap()
-    } else {
-        let token_account = get_token_largest_account(client, mint).await.unwrap();
-        std::fs::write(file_path, token_account.to_bytes()).unwrap();
-        token_account
+    setup
+        .transformer
+        .dry_run_account_update(&account)
+        .await
+        .unwrap()
+}
+
+pub async fn dry_run_transaction(setup: &TestSetup, sig: Signature) -> DryRun {
+    let transaction_info = transaction_info(setup, sig).await;
+
+    setup
+        .transformer
+        .dry_run_transaction(&transaction_info)
+        .await
+        .unwrap()
+}
+
+/// `bytea` columns as they appear in a dry run diff.
+pub fn bytea_json(pubkey: &Pubkey) -> serde_json::Value {
+    let hex = pubkey
+        .to_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    serde_json::Value::String(format!("\\x{hex}"))
+}
+
+async fn cached_fetch_largest_token_account_id(rpc: &FixtureRpc, mint: Pubkey) -> Pubkey {
+    let file_path = get_relative_project_path(&format!(
+        "tests/data/largest_token_account_ids/{}/{}",
+        mint, mint
+    ));
+
+    match rpc.recorder(&file_path).unwrap() {
+        Some(client) => {
+            let token_account = get_token_largest_account(client, mint).await.unwrap();
+            write_fixture(&file_path, &token_account.to_bytes());
+            token_account
+        }
+        None => Pubkey::try_from(std::fs::read(file_path).unwrap()).unwrap(),
     }
 }
 
@@ -470,6 +577,15 @@ pub enum Network {
     Devnet,
 }
 
+impl Network {
+    pub const fn rpc_url_var(self) -> &'static str {
+        match self {
+            Network::Mainnet => "MAINNET_RPC_URL",
+            Network::Devnet => "DEVNET_RPC_URL",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum Order {
     Forward,
@@ -567,7 +683,7 @@ pub struct NftAccounts {
 
 pub async fn get_nft_accounts(setup: &TestSetup, mint: Pubkey) -> NftAccounts {
     let metadata_account = Metadata::find_pda(&mint).0;
-    let token_account = cached_fetch_largest_token_account_id(&setup.client, mint).await;
+    let token_account = cached_fetch_largest_token_account_id(&setup.rpc, mint).await;
     NftAccounts {
         mint,
         metadata: metadata_account,
@@ -582,7 +698,7 @@ async fn index_account_with_ordered_slot(setup: &TestSetup, account: Pubkey) {
 }
 
 async fn index_token_mint(setup: &TestSetup, mint: Pubkey) {
-    let token_account = cached_fetch_largest_token_account_id(&setup.client, mint).await;
+    let token_account = cached_fetch_largest_token_account_id(&setup.rpc, mint).await;
     index_account(setup, mint).await;
     index_account(setup, token_account).await;
 
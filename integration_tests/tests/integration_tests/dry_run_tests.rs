@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use digital_asset_types::dao::asset;
+
+use sea_orm::{EntityTrait, PaginatorTrait};
+
+use serial_test::serial;
+
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use super::common::*;
+
+// Reuses the fixtures of `test_mpl_core_get_asset` and `test_mint_transfer_burn`.
+
+#[tokio::test]
+#[serial]
+async fn test_dry_run_account_update() {
+    let setup = TestSetup::new_with_options(
+        "mpl_core_get_asset".to_string(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+    let asset_id = Pubkey::from_str("x3hJtpU4AUsGejNvxzX9TKjcyNB1eYtDdDPWdeF6opr").unwrap();
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let dry_run = dry_run_account(&setup, asset_id).await;
+
+    let assets = &dry_run.diff.tables["public.asset"];
+    assert_eq!(assets.inserted.len(), 1);
+    assert_eq!(assets.inserted[0]["id"], bytea_json(&asset_id));
+    assert!(assets.updated.is_empty());
+    assert_eq!(
+        asset::Entity::find()
+            .count(setup.db.as_ref())
+            .await
+            .unwrap(),
+        0
+    );
+
+    // Replaying an indexed update rewrites nothing
+    index_seed_events(&setup, vec![&seed_account(&asset_id.to_string())]).await;
+    let dry_run = dry_run_account(&setup, asset_id).await;
+    assert!(dry_run
+        .diff
+        .tables
+        .values()
+        .all(|table| table.inserted.is_empty()));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_dry_run_transaction() {
+    let setup = TestSetup::new_with_options(
+        "mint_transfer_burn".to_string(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+    let asset_id = Pubkey::from_str("8vw7tdLGE3FBjaetsJrZAarwsbc8UESsegiLyvWXxs5A").unwrap();
+    let mint = Signature::from_str(
+        "5coWPFty37s7haT3SVyMf6PkTaABEnhCRhfDjXeMNS58czHB5dCFPY6VrsZNwxBnqypmNic1LbLp1j5qjbdnZAc8",
+    )
+    .unwrap();
+    let transfer = Signature::from_str(
+        "k6jmJcurgBQ6F2bVa86Z1vGb7ievzxwRZ8GAqzFEG8HicDizxceYPUm1KTzWZ3QKtGgy1EuFWUGCRqBeKU9SAoJ",
+    )
+    .unwrap();
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let dry_run = dry_run_transaction(&setup, mint).await;
+
+    let assets = &dry_run.diff.tables["public.asset"];
+    assert_eq!(assets.inserted.len(), 1);
+    assert_eq!(assets.inserted[0]["id"], bytea_json(&asset_id));
+    assert!(dry_run.diff.tables.contains_key("public.cl_items"));
+    assert_eq!(
+        asset::Entity::find()
+            .count(setup.db.as_ref())
+            .await
+            .unwrap(),
+        0
+    );
+
+    index_seed_events(&setup, vec![&SeedEvent::Signature(mint)]).await;
+    let dry_run = dry_run_transaction(&setup, transfer).await;
+
+    let assets = &dry_run.diff.tables["public.asset"];
+    assert!(assets.inserted.is_empty());
+    assert_eq!(assets.updated.len(), 1);
+    assert_eq!(assets.updated[0].key["id"], bytea_json(&asset_id));
+}
@@ -0,0 +1,39 @@
+use std::str::FromStr;
+
+use plerkle_serialization::root_as_transaction_info;
+
+use solana_sdk::signature::Signature;
+
+use super::common::*;
+
+// These run without a database nor an RPC, the fixtures are replayed from `tests/data`.
+
+#[tokio::test]
+async fn test_replay_recorded_transaction() {
+    let sig = Signature::from_str(
+        "5coWPFty37s7haT3SVyMf6PkTaABEnhCRhfDjXeMNS58czHB5dCFPY6VrsZNwxBnqypmNic1LbLp1j5qjbdnZAc8",
+    )
+    .unwrap();
+
+    let txn_bytes = cached_fetch_transaction_bytes(&FixtureRpc::Replay, "mint_transfer_burn", sig)
+        .await
+        .unwrap();
+    let txn = root_as_transaction_info(&txn_bytes).unwrap();
+
+    assert_eq!(txn.signature(), Some(sig.to_string().as_str()));
+}
+
+#[tokio::test]
+async fn test_replay_missing_fixture() {
+    let sig = Signature::from_str(
+        "5coWPFty37s7haT3SVyMf6PkTaABEnhCRhfDjXeMNS58czHB5dCFPY6VrsZNwxBnqypmNic1LbLp1j5qjbdnZAc8",
+    )
+    .unwrap();
+
+    let error = cached_fetch_transaction_bytes(&FixtureRpc::Replay, "missing_fixture", sig)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().starts_with("missing fixture"));
+    assert!(error.to_string().contains("RECORD_FIXTURES=missing"));
+}
@@ -2,8 +2,11 @@ use function_name::named;
 use std::str::FromStr;
 
 use das_api::api::{self, ApiContract};
-use digital_asset_types::dao::asset_creators;
-use migration::sea_orm::{ConnectionTrait, EntityTrait};
+use digital_asset_types::dao::{
+    asset, asset_creators, asset_grouping,
+    sea_orm_active_enums::{OwnerType, RoyaltyTargetType},
+};
+use migration::sea_orm::{ActiveModelTrait, ConnectionTrait, EntityTrait};
 
 use mpl_token_metadata::types::Creator;
 use sea_orm::{DbBackend, QueryTrait, Set};
@@ -99,3 +102,88 @@ async fn test_creators_reordering() {
     let response = setup.das_api.get_asset(request.clone()).await.unwrap();
     insta::assert_json_snapshot!(name, response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_grouping_stats() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let collection = Pubkey::new_unique().to_string();
+    let holder = Pubkey::new_unique();
+    let other_holder = Pubkey::new_unique();
+    // (owner, compressed, burnt, verified, slot the row is first written with)
+    let members = [
+        (holder, true, false, true, 100),
+        (other_holder, false, false, true, 250),
+        (holder, true, true, true, 180),
+        (other_holder, true, false, false, 50),
+    ];
+    let mut ids = Vec::new();
+    for (owner, compressed, burnt, verified, slot) in members {
+        let id = Pubkey::new_unique().to_bytes().to_vec();
+        asset::ActiveModel {
+            id: Set(id.clone()),
+            owner: Set(Some(owner.to_bytes().to_vec())),
+            owner_type: Set(OwnerType::Single),
+            frozen: Set(false),
+            supply: Set(1),
+            compressed: Set(compressed),
+            compressible: Set(false),
+            royalty_target_type: Set(RoyaltyTargetType::Creators),
+            royalty_amount: Set(0),
+            burnt: Set(burnt),
+            slot_updated_cnft_transaction: Set(compressed.then_some(slot)),
+            slot_updated_mint_account: Set((!compressed).then_some(slot)),
+            slot_updated_metadata_account: Set((!compressed).then_some(slot + 10)),
+            ..Default::default()
+        }
+        .insert(setup.db.as_ref())
+        .await
+        .unwrap();
+        asset_grouping::ActiveModel {
+            asset_id: Set(id.clone()),
+            group_key: Set("collection".to_string()),
+            group_value: Set(Some(collection.clone())),
+            verified: Set(verified),
+            slot_updated: Set(Some(slot)),
+            ..Default::default()
+        }
+        .insert(setup.db.as_ref())
+        .await
+        .unwrap();
+        ids.push(id);
+    }
+
+    // A later transfer of the first member does not move its mint slot
+    asset::ActiveModel {
+        id: Set(ids[0].clone()),
+        slot_updated_cnft_transaction: Set(Some(400)),
+        ..Default::default()
+    }
+    .update(setup.db.as_ref())
+    .await
+    .unwrap();
+
+    let response = setup
+        .das_api
+        .get_grouping(api::GetGrouping {
+            group_key: "collection".to_string(),
+            group_value: collection,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(response.group_size, 3);
+    assert_eq!(response.verified, 3);
+    assert_eq!(response.unverified, 1);
+    assert_eq!(response.compressed, 2);
+    assert_eq!(response.uncompressed, 1);
+    assert_eq!(response.burnt, 1);
+    assert_eq!(response.unique_holders, 2);
+    assert_eq!(response.first_mint_slot, Some(100));
+    assert_eq!(response.last_mint_slot, Some(250));
+    assert_eq!(response.mpl_core_num_minted, None);
+}
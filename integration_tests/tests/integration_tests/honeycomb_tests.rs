@@ -0,0 +1,773 @@
+use function_name::named;
+
+use anchor_lang::{
+    prelude::{AnchorDeserialize, AnchorSerialize},
+    solana_program::keccak,
+    InstructionData,
+};
+
+use blockbuster::{
+    program_handler::ProgramParser,
+    programs::{
+        account_compression::AccountCompressionParser,
+        hpl_character_manager::{AssetCustody, HplCharacterManagerParser},
+        hpl_currency_manager::{
+            Currency, CurrencyKind, HolderAccount, HolderStatus, HplCurrencyManagerParser, TxHook,
+        },
+        hpl_hive_control::{
+            DelegateAuthority, HiveControlPermission, HplHiveControlParser, ServiceDelegation,
+        },
+        hpl_nectar_missions::{HplNectarMissionsParser, MissionPool},
+        hpl_nectar_staking::{HplNectarStakingParser, Multipliers},
+        hpl_resource_manager::{Faucet, HplResourceManagerParser},
+        noop::NoopParser,
+    },
+};
+
+use das_api::api::{self, ApiContract};
+
+use digital_asset_types::{
+    dao::{accounts, character_history, compressed_data},
+    rpc::{options::Options, CompressedData},
+};
+
+use hpl_toolkit::prelude::*;
+
+use program_transformers::{AccountInfo, TransactionInfo};
+
+use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder};
+
+use serde_json::{json, Value};
+
+use serial_test::serial;
+
+use solana_sdk::{instruction::CompiledInstruction, pubkey, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{InnerInstruction, InnerInstructions};
+use spl_account_compression::{
+    events::{
+        AccountCompressionEvent, ApplicationDataEvent, ApplicationDataEventV1, ChangeLogEvent,
+    },
+    state::PathNode,
+};
+
+use super::common::*;
+
+// The character manager emits its compressed data through noop CPIs next to the account
+// compression instruction of the leaf, the transactions below are laid out the same way.
+
+const CHARACTER_MANAGER: Pubkey = pubkey!("ChRCtrG7X5kb9YncA4wuyD68DXXL8Szt3zBCCGiioBTg");
+const TREE: Pubkey = Pubkey::new_from_array([7; 32]);
+const WALLET: Pubkey = Pubkey::new_from_array([9; 32]);
+const STAKING_POOL: Pubkey = Pubkey::new_from_array([11; 32]);
+const TREE_DEPTH: usize = 3;
+
+#[derive(AnchorSerialize, AnchorDeserialize, ToSchema, Clone)]
+struct Character {
+    owner: Pubkey,
+    used_by: CharacterUsedBy,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, ToSchema, Clone)]
+enum CharacterUsedBy {
+    None,
+    Staking { pool: Pubkey, staked_at: i64 },
+}
+
+const fn character() -> Character {
+    Character {
+        owner: WALLET,
+        used_by: CharacterUsedBy::None,
+    }
+}
+
+const fn staking() -> CharacterUsedBy {
+    CharacterUsedBy::Staking {
+        pool: STAKING_POOL,
+        staked_at: 1_700_000_000,
+    }
+}
+
+fn app_data(event: CompressedDataEvent) -> Vec<u8> {
+    AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(ApplicationDataEventV1 {
+        application_data: event.try_to_vec().unwrap(),
+    }))
+    .try_to_vec()
+    .unwrap()
+}
+
+fn tree_schema_event() -> Vec<u8> {
+    app_data(CompressedDataEvent::TreeSchemaValue {
+        discriminator: keccak::hashv(&[CHARACTER_MANAGER.as_ref(), b"Character"]).to_bytes(),
+        tree_id: TREE.to_bytes(),
+        schema: Character::schema(),
+        canopy_depth: 0,
+        program_id: CHARACTER_MANAGER.to_bytes(),
+    })
+}
+
+fn leaf_event(leaf_idx: u32, seq: u64, stream_type: CompressedDataEventStream) -> Vec<u8> {
+    app_data(CompressedDataEvent::Leaf {
+        slot: seq,
+        tree_id: TREE.to_bytes(),
+        leaf_idx,
+        seq,
+        stream_type,
+    })
+}
+
+fn full_leaf(leaf_idx: u32, seq: u64, character: &Character) -> Vec<u8> {
+    leaf_event(
+        leaf_idx,
+        seq,
+        CompressedDataEventStream::Full {
+            data: character.schema_value(),
+        },
+    )
+}
+
+fn used_by_patch(leaf_idx: u32, seq: u64, used_by: &CharacterUsedBy) -> Vec<u8> {
+    leaf_event(
+        leaf_idx,
+        seq,
+        CompressedDataEventStream::PatchChunk {
+            key: "used_by".to_string(),
+            data: used_by.schema_value(),
+        },
+    )
+}
+
+/// The account compression CPI writing `leaf`, with its change log logged through noop.
+fn append_leaf(leaf_idx: u32, seq: u64, leaf: [u8; 32]) -> Vec<(Pubkey, Vec<u8>)> {
+    let leaf_node = (1 << TREE_DEPTH) + leaf_idx;
+    let path = (0..=TREE_DEPTH)
+        .map(|level| PathNode {
+            node: match level {
+                0 => leaf,
+                _ => [level as u8 + 1; 32],
+            },
+            index: leaf_node >> level,
+        })
+        .collect();
+    let change_log =
+        AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(TREE, path, seq, leaf_idx));
+
+    vec![
+        (
+            AccountCompressionParser.key(),
+            spl_account_compression::instruction::Append { leaf }.data(),
+        ),
+        (NoopParser.key(), change_log.try_to_vec().unwrap()),
+    ]
+}
+
+/// A character manager instruction with its account compression and noop CPIs.
+fn honeycomb_transaction(slot: u64, cpis: Vec<(Pubkey, Vec<u8>)>) -> TransactionInfo {
+    let account_keys = vec![
+        WALLET,
+        CHARACTER_MANAGER,
+        AccountCompressionParser.key(),
+        NoopParser.key(),
+        TREE,
+    ];
+    let instructions = cpis
+        .into_iter()
+        .map(|(program, data)| InnerInstruction {
+            instruction: CompiledInstruction {
+                program_id_index: account_keys.iter().position(|key| key == &program).unwrap()
+                    as u8,
+                accounts: vec![4],
+                data,
+            },
+            stack_height: Some(2),
+        })
+        .collect();
+
+    TransactionInfo {
+        slot,
+        signature: Signature::new_unique(),
+        account_keys,
+        message_instructions: vec![CompiledInstruction {
+            program_id_index: 1,
+            accounts: vec![0, 4],
+            data: vec![0; 8],
+        }],
+        meta_inner_instructions: vec![InnerInstructions {
+            index: 0,
+            instructions,
+        }],
+    }
+}
+
+fn noop(event: Vec<u8>) -> (Pubkey, Vec<u8>) {
+    (NoopParser.key(), event)
+}
+
+async fn index_honeycomb_transaction(setup: &TestSetup, transaction: &TransactionInfo) {
+    setup
+        .transformer
+        .handle_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+async fn get_compressed_data(setup: &TestSetup, leaf_idx: u32) -> Option<Value> {
+    let request = api::GetCompressedData {
+        tree: TREE.to_string(),
+        leaf_idx,
+        options: None,
+    };
+    setup
+        .das_api
+        .get_compressed_data(request)
+        .await
+        .ok()
+        .map(|data| serde_json::to_value(data).unwrap())
+}
+
+async fn get_characters(setup: &TestSetup) -> Vec<CompressedData> {
+    let request = api::GetCharacters {
+        wallet: WALLET.to_string(),
+        merkle_tree: None,
+        project: None,
+        options: None,
+    };
+    setup.das_api.get_characters(request).await.unwrap()
+}
+
+async fn get_compressed_accounts(setup: &TestSetup) -> Vec<CompressedData> {
+    let request = api::GetCompressedAccounts {
+        program_id: CHARACTER_MANAGER.to_string(),
+        account_name: "Character".to_string(),
+        project: None,
+    };
+    setup
+        .das_api
+        .get_compressed_accounts(request)
+        .await
+        .unwrap()
+}
+
+/// The responses of the compressed data endpoints for a leaf, for snapshots.
+async fn api_state(setup: &TestSetup, leaf_idx: u32) -> Value {
+    json!({
+        "getCompressedData": get_compressed_data(setup, leaf_idx).await,
+        "getCharacters": get_characters(setup).await,
+        "getCompressedAccounts": get_compressed_accounts(setup).await,
+    })
+}
+
+async fn character_events(setup: &TestSetup) -> Vec<String> {
+    character_history::Entity::find()
+        .order_by_asc(character_history::Column::Id)
+        .all(setup.db.as_ref())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|history| history.event)
+        .collect()
+}
+
+fn schema_json(value: SchemaValue) -> Value {
+    value.into()
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_honeycomb_character_lifecycle() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let create = honeycomb_transaction(
+        1,
+        vec![
+            noop(tree_schema_event()),
+            noop(full_leaf(0, 1, &character())),
+        ],
+    );
+    index_honeycomb_transaction(&setup, &create).await;
+
+    let data = get_compressed_data(&setup, 0).await.unwrap();
+    assert_eq!(data["schema_validated"], true);
+    assert_eq!(data["parsed_data"], schema_json(character().schema_value()));
+    insta::assert_json_snapshot!(format!("{name}-created"), api_state(&setup, 0).await);
+
+    let stake = honeycomb_transaction(2, vec![noop(used_by_patch(0, 2, &staking()))]);
+    index_honeycomb_transaction(&setup, &stake).await;
+
+    let data = get_compressed_data(&setup, 0).await.unwrap();
+    assert_eq!(
+        data["parsed_data"]["used_by"],
+        schema_json(staking().schema_value())
+    );
+    assert_eq!(character_events(&setup).await, ["NewCharacter", "Staked"]);
+    insta::assert_json_snapshot!(format!("{name}-staked"), api_state(&setup, 0).await);
+
+    let burn = honeycomb_transaction(
+        3,
+        vec![noop(leaf_event(0, 3, CompressedDataEventStream::Empty))],
+    );
+    index_honeycomb_transaction(&setup, &burn).await;
+
+    assert_eq!(get_compressed_data(&setup, 0).await, None);
+    assert!(get_characters(&setup).await.is_empty());
+    insta::assert_json_snapshot!(format!("{name}-burned"), api_state(&setup, 0).await);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_honeycomb_get_proof() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let leaf = [42; 32];
+    let mut cpis = append_leaf(1, 1, leaf);
+    cpis.push(noop(tree_schema_event()));
+    cpis.push(noop(full_leaf(1, 1, &character())));
+    index_honeycomb_transaction(&setup, &honeycomb_transaction(1, cpis)).await;
+
+    let request = api::LeafTreePayload {
+        tree: TREE.to_string(),
+        leaf_idx: 1,
+    };
+    let proof = setup.das_api.get_proof(request).await.unwrap();
+    assert_eq!(proof.leaf, bs58::encode(leaf).into_string());
+    assert_eq!(proof.proof.len(), TREE_DEPTH);
+
+    insta::assert_json_snapshot!(
+        name,
+        json!({
+            "getProof": proof,
+            "getCompressedData": get_compressed_data(&setup, 1).await,
+        })
+    );
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_honeycomb_duplicate_delivery() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let create = honeycomb_transaction(
+        1,
+        vec![
+            noop(tree_schema_event()),
+            noop(full_leaf(0, 1, &character())),
+        ],
+    );
+    let stake = honeycomb_transaction(2, vec![noop(used_by_patch(0, 2, &staking()))]);
+
+    for transaction in [&create, &stake, &create, &stake, &stake] {
+        index_honeycomb_transaction(&setup, transaction).await;
+    }
+
+    let data = get_compressed_data(&setup, 0).await.unwrap();
+    assert_eq!(
+        data["parsed_data"]["used_by"],
+        schema_json(staking().schema_value())
+    );
+    // a replayed patch must not read as a reward claim
+    assert_eq!(character_events(&setup).await, ["NewCharacter", "Staked"]);
+    insta::assert_json_snapshot!(name, api_state(&setup, 0).await);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_honeycomb_out_of_order_delivery() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    // the leaf arrives before the tree, it can't be validated against its schema
+    let create = honeycomb_transaction(1, vec![noop(full_leaf(0, 1, &character()))]);
+    let tree = honeycomb_transaction(1, vec![noop(tree_schema_event())]);
+    index_honeycomb_transaction(&setup, &create).await;
+    index_honeycomb_transaction(&setup, &tree).await;
+
+    let data = get_compressed_data(&setup, 0).await.unwrap();
+    assert_eq!(data["schema_validated"], false);
+
+    // a newer version of the leaf is validated
+    let staked = Character {
+        used_by: staking(),
+        ..character()
+    };
+    let update = honeycomb_transaction(3, vec![noop(full_leaf(0, 3, &staked))]);
+    index_honeycomb_transaction(&setup, &update).await;
+
+    let data = get_compressed_data(&setup, 0).await.unwrap();
+    assert_eq!(data["schema_validated"], true);
+    assert_eq!(data["parsed_data"], schema_json(staked.schema_value()));
+
+    // stale versions of the leaf delivered late are ignored
+    let stale_patch = honeycomb_transaction(2, vec![noop(used_by_patch(0, 2, &staking()))]);
+    index_honeycomb_transaction(&setup, &stale_patch).await;
+    index_honeycomb_transaction(&setup, &create).await;
+
+    let data = get_compressed_data(&setup, 0).await.unwrap();
+    assert_eq!(data["parsed_data"], schema_json(staked.schema_value()));
+    assert_eq!(data["slot_updated"], 3);
+    // the character created before its tree keeps both its creation and its staking
+    assert_eq!(character_events(&setup).await, ["NewCharacter", "Staked"]);
+    insta::assert_json_snapshot!(name, api_state(&setup, 0).await);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_honeycomb_account_parsers() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let project = Pubkey::new_from_array([13; 32]);
+    let holder = HolderAccount {
+        bump: 255,
+        currency: Pubkey::new_from_array([14; 32]),
+        owner: WALLET,
+        token_account: Pubkey::new_from_array([15; 32]),
+        status: HolderStatus::Active,
+        created_at: 1_700_000_000,
+    };
+    let custody = AssetCustody {
+        bump: 255,
+        wallet: WALLET,
+        character_model: None,
+        source: None,
+        character: None,
+    };
+    let delegate = DelegateAuthority {
+        bump: 255,
+        project,
+        authority: WALLET,
+        delegations: vec![ServiceDelegation::HiveControl {
+            permission: HiveControlPermission::ManageProfiles,
+        }],
+    };
+    let multipliers = Multipliers {
+        bump: 255,
+        staking_pool: STAKING_POOL,
+        decimals: 2,
+        duration_multipliers: vec![],
+        count_multipliers: vec![],
+        creator_multipliers: vec![],
+        collection_multipliers: vec![],
+    };
+    let mission_pool = MissionPool {
+        bump: 255,
+        project,
+        name: "Expeditions".to_string(),
+        factions_merkle_root: [0; 32],
+        randomizer_round: 0,
+        character_models: vec![],
+        guild_kits: vec![],
+    };
+    let faucet = Faucet {
+        project,
+        resource: Pubkey::new_from_array([16; 32]),
+        amount: 10,
+        repeat_interval: 3_600,
+        last_claimed: 0,
+    };
+
+    let expected = [
+        (
+            HplCurrencyManagerParser.key(),
+            HolderAccount::DISCRIMINATOR,
+            holder.try_to_vec().unwrap(),
+            schema_json(holder.schema_value()),
+        ),
+        (
+            HplCharacterManagerParser.key(),
+            AssetCustody::DISCRIMINATOR,
+            custody.try_to_vec().unwrap(),
+            schema_json(custody.schema_value()),
+        ),
+        (
+            HplHiveControlParser.key(),
+            DelegateAuthority::DISCRIMINATOR,
+            delegate.try_to_vec().unwrap(),
+            schema_json(delegate.schema_value()),
+        ),
+        (
+            HplNectarStakingParser.key(),
+            Multipliers::DISCRIMINATOR,
+            multipliers.try_to_vec().unwrap(),
+            schema_json(multipliers.schema_value()),
+        ),
+        (
+            HplNectarMissionsParser.key(),
+            MissionPool::DISCRIMINATOR,
+            mission_pool.try_to_vec().unwrap(),
+            schema_json(mission_pool.schema_value()),
+        ),
+        (
+            HplResourceManagerParser.key(),
+            Faucet::DISCRIMINATOR,
+            faucet.try_to_vec().unwrap(),
+            schema_json(faucet.schema_value()),
+        ),
+    ];
+
+    for (index, (owner, discriminator, data, _)) in expected.iter().enumerate() {
+        let account = AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: Pubkey::new_from_array([100 + index as u8; 32]),
+            owner: *owner,
+            data: [&discriminator[..], &data[..]].concat(),
+        };
+        // an update delivered twice is idempotent
+        for _ in 0..2 {
+            setup
+                .transformer
+                .handle_account_update(&account)
+                .await
+                .unwrap();
+        }
+    }
+
+    for (index, (owner, discriminator, _, parsed_data)) in expected.iter().enumerate() {
+        let account = accounts::Entity::find_by_id(vec![100 + index as u8; 32])
+            .one(setup.db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.program_id, owner.to_bytes());
+        assert_eq!(account.discriminator, discriminator);
+        assert_eq!(&account.parsed_data, parsed_data);
+        assert_eq!(account.slot_updated, DEFAULT_SLOT as i64);
+    }
+
+    let request = api::GetCurrencyHoldings {
+        owner_address: WALLET.to_string(),
+        project: None,
+    };
+    let holdings = setup.das_api.get_currency_holdings(request).await.unwrap();
+    let request = api::GetDelegateAuthorities {
+        project: project.to_string(),
+    };
+    let delegates = setup
+        .das_api
+        .get_delegate_authorities(request)
+        .await
+        .unwrap();
+    insta::assert_json_snapshot!(
+        name,
+        json!({
+            "getCurrencyHoldings": holdings,
+            "getDelegateAuthorities": delegates,
+        })
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_honeycomb_account_project_backfill() {
+    let setup = TestSetup::new("honeycomb_account_project_backfill".to_string()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let project = Pubkey::new_from_array([13; 32]);
+    let currency_address = Pubkey::new_from_array([14; 32]);
+    let holder_address = Pubkey::new_from_array([17; 32]);
+    let currency = Currency {
+        bump: 255,
+        project,
+        mint: Pubkey::new_from_array([18; 32]),
+        kind: CurrencyKind::Wrapped,
+        tx_hook: TxHook::User,
+    };
+    let holder = HolderAccount {
+        bump: 255,
+        currency: currency_address,
+        owner: WALLET,
+        token_account: Pubkey::new_from_array([15; 32]),
+        status: HolderStatus::Active,
+        created_at: 1_700_000_000,
+    };
+    let holder_update = AccountInfo {
+        slot: DEFAULT_SLOT,
+        pubkey: holder_address,
+        owner: HplCurrencyManagerParser.key(),
+        data: [
+            &HolderAccount::DISCRIMINATOR[..],
+            &holder.try_to_vec().unwrap(),
+        ]
+        .concat(),
+    };
+    let currency_update = AccountInfo {
+        slot: DEFAULT_SLOT,
+        pubkey: currency_address,
+        owner: HplCurrencyManagerParser.key(),
+        data: [
+            &Currency::DISCRIMINATOR[..],
+            &currency.try_to_vec().unwrap(),
+        ]
+        .concat(),
+    };
+
+    // the holder account is indexed before its currency
+    setup
+        .transformer
+        .handle_account_update(&holder_update)
+        .await
+        .unwrap();
+    let db = setup.db.as_ref();
+    let account_project = || async move {
+        accounts::Entity::find_by_id(holder_address.to_bytes().to_vec())
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+            .project
+    };
+    assert_eq!(account_project().await, None);
+
+    setup
+        .transformer
+        .handle_account_update(&currency_update)
+        .await
+        .unwrap();
+    assert_eq!(account_project().await, Some(project.to_bytes().to_vec()));
+
+    // events of the holder account carry the project of its currency
+    let inactive = HolderAccount {
+        status: HolderStatus::Inactive,
+        ..holder
+    };
+    let inactive_update = AccountInfo {
+        data: [
+            &HolderAccount::DISCRIMINATOR[..],
+            &inactive.try_to_vec().unwrap(),
+        ]
+        .concat(),
+        ..holder_update
+    };
+    let dry_run = setup
+        .transformer
+        .dry_run_account_update(&inactive_update)
+        .await
+        .unwrap();
+    assert_eq!(
+        dry_run
+            .events
+            .iter()
+            .map(|event| event.event_type.as_str())
+            .collect::<Vec<_>>(),
+        ["HolderAccountUpdated", "HolderStatusChanged"]
+    );
+    for event in dry_run.events {
+        assert_eq!(event.project, Some(project.to_string()));
+    }
+}
+
+/// Stores a character leaf wrapping `mint` the way the character manager schema lays it out.
+async fn insert_wrapped_character(setup: &TestSetup, leaf_idx: i64, mint: Pubkey) {
+    compressed_data::ActiveModel::from(compressed_data::Model {
+        id: vec![leaf_idx as u8; 32],
+        tree_id: TREE.to_bytes().to_vec(),
+        leaf_idx,
+        seq: 1,
+        schema_validated: true,
+        raw_data: vec![],
+        parsed_data: json!({
+            "owner": format!("pubkey:{WALLET}"),
+            "source": {
+                "__kind": "Wrapped",
+                "params": { "mint": format!("pubkey:{mint}"), "is_compressed": false },
+            },
+        }),
+        created_at: None,
+        slot_updated: 1,
+    })
+    .insert(setup.db.as_ref())
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_honeycomb_character_source_asset() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let mint = pubkey!("CMVuYDS9nTeujfTPJb8ik7CRhAqZv4DfjfdamFLkJgxE");
+    index_nft(&setup, mint).await;
+    insert_wrapped_character(&setup, 0, mint).await;
+    // the source of this one was never indexed
+    insert_wrapped_character(&setup, 1, Pubkey::new_unique()).await;
+    let show_character = Some(Options {
+        show_character: true,
+        ..Default::default()
+    });
+
+    let characters = |options: Option<Options>| api::GetCharacters {
+        wallet: WALLET.to_string(),
+        merkle_tree: Some(TREE.to_string()),
+        project: None,
+        options,
+    };
+    let source_assets = |characters: Vec<CompressedData>| {
+        characters
+            .into_iter()
+            .map(|character| character.source_asset.map(|asset| asset.id))
+            .collect::<Vec<_>>()
+    };
+    let hidden = setup
+        .das_api
+        .get_characters(characters(None))
+        .await
+        .unwrap();
+    assert_eq!(source_assets(hidden), [None, None]);
+    let shown = setup
+        .das_api
+        .get_characters(characters(show_character.clone()))
+        .await
+        .unwrap();
+    assert_eq!(source_assets(shown), [Some(mint.to_string()), None]);
+
+    let compressed_data = |options: Option<Options>| api::GetCompressedData {
+        tree: TREE.to_string(),
+        leaf_idx: 0,
+        options,
+    };
+    let data = setup
+        .das_api
+        .get_compressed_data(compressed_data(None))
+        .await
+        .unwrap();
+    assert_eq!(data.source_asset, None);
+    let data = setup
+        .das_api
+        .get_compressed_data(compressed_data(show_character.clone()))
+        .await
+        .unwrap();
+    let source_asset = data.source_asset.unwrap();
+    assert_eq!(source_asset.id, mint.to_string());
+    // the character is not repeated inside its own source asset
+    assert_eq!(source_asset.character, None);
+
+    let request = api::GetCharacterByAsset {
+        asset_id: mint.to_string(),
+        options: None,
+    };
+    let character = setup
+        .das_api
+        .get_character_by_asset(request)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(character.leaf_idx, 0);
+    assert_eq!(
+        character.source_asset.map(|asset| asset.id),
+        Some(mint.to_string())
+    );
+}
@@ -0,0 +1,111 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use das_grpc_ingest::{
+    bus::{MemoryBus, MessageProducer},
+    config::{ConfigIngesterRedisStreamType, REDIS_STREAM_TRANSACTIONS},
+    ingester::consume,
+    local::transaction_entry,
+};
+
+use digital_asset_types::dao::asset;
+
+use futures::future::{ready, FutureExt};
+
+use program_transformers::ProgramTransformer;
+
+use sea_orm::EntityTrait;
+
+use serial_test::serial;
+
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use tokio::time::timeout;
+
+use super::common::*;
+
+// Reuses the fixtures of `test_mint_transfer_burn`.
+
+/// The indexed asset without `created_at`, which is the time it was inserted.
+async fn indexed_asset(setup: &TestSetup, asset_id: Pubkey) -> Option<asset::Model> {
+    asset::Entity::find_by_id(asset_id.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .map(|asset| asset::Model {
+            created_at: None,
+            ..asset
+        })
+}
+
+#[tokio::test]
+#[serial]
+async fn test_local_ingests_through_memory_bus() {
+    let setup = TestSetup::new_with_options(
+        "mint_transfer_burn".to_string(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+    let asset_id = Pubkey::from_str("8vw7tdLGE3FBjaetsJrZAarwsbc8UESsegiLyvWXxs5A").unwrap();
+    let signatures = [
+        "5coWPFty37s7haT3SVyMf6PkTaABEnhCRhfDjXeMNS58czHB5dCFPY6VrsZNwxBnqypmNic1LbLp1j5qjbdnZAc8",
+        "k6jmJcurgBQ6F2bVa86Z1vGb7ievzxwRZ8GAqzFEG8HicDizxceYPUm1KTzWZ3QKtGgy1EuFWUGCRqBeKU9SAoJ",
+    ]
+    .map(|signature| Signature::from_str(signature).unwrap());
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let bus = MemoryBus::new(10);
+    let mut entries = vec![];
+    for signature in signatures {
+        entries.push(transaction_entry(
+            &transaction_info(&setup, signature).await,
+        ));
+    }
+    bus.publish(entries).await.unwrap();
+
+    let (messages, messages_tasks) = bus.consumer(
+        &[(
+            REDIS_STREAM_TRANSACTIONS.to_owned(),
+            ConfigIngesterRedisStreamType::Transaction,
+        )],
+        10,
+    );
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let program_transformer = || {
+        Arc::new(ProgramTransformer::new(
+            pool.clone(),
+            Box::new(|_info| ready(Ok(())).boxed()),
+            false,
+        ))
+    };
+    // the ingester stops once the bus has been idle for a second
+    consume(
+        Box::new(messages),
+        messages_tasks,
+        program_transformer(),
+        program_transformer(),
+        10,
+        Some(Duration::from_secs(1)),
+    )
+    .await
+    .unwrap();
+
+    timeout(
+        Duration::from_secs(5),
+        bus.drained(REDIS_STREAM_TRANSACTIONS),
+    )
+    .await
+    .unwrap();
+    assert_eq!(bus.skipped(), 0);
+    let consumed = indexed_asset(&setup, asset_id).await;
+    assert!(consumed.is_some());
+
+    // the same rows as indexing the transactions directly
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    for signature in signatures {
+        index_transaction(&setup, signature).await;
+    }
+    assert_eq!(consumed, indexed_asset(&setup, asset_id).await);
+}
@@ -1,6 +1,10 @@
 mod account_update_tests;
 mod cnft_tests;
 mod common;
+mod dry_run_tests;
+mod fixture_tests;
 mod general_scenario_tests;
+mod honeycomb_tests;
+mod local_tests;
 mod mpl_core_tests;
 mod regular_nft_tests;
@@ -1,4 +1,5 @@
 pub use sea_orm_migration::prelude::*;
+use std::{path::PathBuf, sync::OnceLock};
 
 mod m20220101_000001_init;
 mod m20221020_052135_add_asset_hashes;
@@ -48,9 +49,29 @@ mod m20240509_213421_add_character_history_table;
 mod m20240522_085331_create_compressed_data_changelog_table;
 mod m20240805_095941_add_canopy_depth_in_merkle_tree;
 mod m20240219_115532_add_extensions_column;
+mod m20241018_120101_create_api_keys_table;
+mod m20241018_130101_add_change_notify_triggers;
+mod m20241018_140101_create_webhook_deliveries_table;
+mod m20241018_150101_add_token_accounts_owner_index;
+mod m20241018_160101_add_holder_account_owner_index;
+mod m20241018_170101_add_character_source_index;
+mod m20241018_180101_add_project_columns;
+mod m20241018_190101_add_compressed_data_signatures;
+mod m20241018_200101_add_asset_search_indexes;
+mod m20241018_210101_add_accounts_merkle_trees_indexes;
+mod m20241018_220101_add_accounts_project_references_indexes;
+mod m20241018_230101_add_asset_mint_slot;
 
 pub mod model;
 
+static INIT_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Reads the schema applied by the first migration from `path` instead of the embedded one or
+/// `INIT_FILE_PATH`, returns false if it was already set.
+pub fn set_init_file(path: impl Into<PathBuf>) -> bool {
+    INIT_FILE.set(path.into()).is_ok()
+}
+
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -105,6 +126,18 @@ impl MigratorTrait for Migrator {
             Box::new(m20240522_085331_create_compressed_data_changelog_table::Migration),
             // Box::new(m20240805_095941_add_canopy_depth_in_merkle_tree::Migration),
             Box::new(m20240219_115532_add_extensions_column::Migration),
+            Box::new(m20241018_120101_create_api_keys_table::Migration),
+            Box::new(m20241018_130101_add_change_notify_triggers::Migration),
+            Box::new(m20241018_140101_create_webhook_deliveries_table::Migration),
+            Box::new(m20241018_150101_add_token_accounts_owner_index::Migration),
+            Box::new(m20241018_160101_add_holder_account_owner_index::Migration),
+            Box::new(m20241018_170101_add_character_source_index::Migration),
+            Box::new(m20241018_180101_add_project_columns::Migration),
+            Box::new(m20241018_190101_add_compressed_data_signatures::Migration),
+            Box::new(m20241018_200101_add_asset_search_indexes::Migration),
+            Box::new(m20241018_210101_add_accounts_merkle_trees_indexes::Migration),
+            Box::new(m20241018_220101_add_accounts_project_references_indexes::Migration),
+            Box::new(m20241018_230101_add_asset_mint_slot::Migration),
         ]
     }
 }
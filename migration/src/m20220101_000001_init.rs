@@ -4,6 +4,10 @@ use sea_orm_migration::sea_orm::ConnectionTrait;
 use std::env;
 use std::fs::File;
 use std::io::Read;
+use std::path::PathBuf;
+
+/// Schema applied when no file is given, so the migrator works outside of a checkout.
+const INIT_SQL: &str = include_str!("../../init.sql");
 
 #[derive(DeriveMigrationName)]
 pub struct Migration;
@@ -11,11 +15,20 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let init_file_path = env::var("INIT_FILE_PATH").expect("INIT_FILE_PATH must be set");
-        let mut file = File::open(init_file_path).map_err(|e| DbErr::Custom(e.to_string()))?;
+        let init_file_path = crate::INIT_FILE
+            .get()
+            .cloned()
+            .or_else(|| env::var_os("INIT_FILE_PATH").map(PathBuf::from));
         let mut sql = String::new();
-        file.read_to_string(&mut sql)
-            .map_err(|e| DbErr::Custom(e.to_string()))?;
+        match init_file_path {
+            Some(init_file_path) => {
+                let mut file =
+                    File::open(init_file_path).map_err(|e| DbErr::Custom(e.to_string()))?;
+                file.read_to_string(&mut sql)
+                    .map_err(|e| DbErr::Custom(e.to_string()))?;
+            }
+            None => sql.push_str(INIT_SQL),
+        }
         let sqls: Vec<&str> = sql.split("-- @@@@@@").collect();
         for sqlst in sqls {
             let stmt = Statement::from_string(manager.get_database_backend(), sqlst.to_string());
@@ -0,0 +1,38 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE TABLE IF NOT EXISTS api_keys (
+                    key TEXT PRIMARY KEY,
+                    id TEXT NOT NULL UNIQUE,
+                    capacity BIGINT,
+                    refill_per_second BIGINT,
+                    allowed_methods TEXT[],
+                    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );"
+                .to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP TABLE IF EXISTS api_keys;".to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+}
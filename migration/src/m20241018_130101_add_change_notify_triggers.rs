@@ -0,0 +1,134 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Every statement notifies the `das_changes` channel. Notifications are only delivered once the
+// ingester's transaction commits, so listeners never observe rolled back changes. The triggers
+// are created disabled so ingesting costs nothing extra until `das-ops subscriptions enable`
+// calls `set_change_notifications(true)`.
+const UP: &[&str] = &[
+    "CREATE OR REPLACE FUNCTION notify_compressed_data_change() RETURNS trigger AS $$
+    DECLARE
+        rec compressed_data%ROWTYPE;
+    BEGIN
+        IF TG_OP = 'DELETE' THEN
+            rec := OLD;
+        ELSE
+            rec := NEW;
+        END IF;
+        PERFORM pg_notify('das_changes', json_build_object(
+            'table', 'compressed_data',
+            'op', lower(TG_OP),
+            'id', encode(rec.id, 'hex'),
+            'tree_id', encode(rec.tree_id, 'hex'),
+            'leaf_idx', rec.leaf_idx,
+            'owner', rec.parsed_data->>'owner'
+        )::text);
+        RETURN NULL;
+    END;
+    $$ LANGUAGE plpgsql;",
+    "CREATE TRIGGER compressed_data_notify_insert_delete AFTER INSERT OR DELETE ON compressed_data
+        FOR EACH ROW EXECUTE PROCEDURE notify_compressed_data_change();",
+    "CREATE TRIGGER compressed_data_notify_update AFTER UPDATE ON compressed_data
+        FOR EACH ROW WHEN (OLD.* IS DISTINCT FROM NEW.*)
+        EXECUTE PROCEDURE notify_compressed_data_change();",
+    "CREATE OR REPLACE FUNCTION notify_character_history_change() RETURNS trigger AS $$
+    BEGIN
+        PERFORM pg_notify('das_changes', json_build_object(
+            'table', 'character_history',
+            'id', NEW.id
+        )::text);
+        RETURN NULL;
+    END;
+    $$ LANGUAGE plpgsql;",
+    "CREATE TRIGGER character_history_notify_insert AFTER INSERT ON character_history
+        FOR EACH ROW EXECUTE PROCEDURE notify_character_history_change();",
+    "CREATE OR REPLACE FUNCTION notify_asset_change() RETURNS trigger AS $$
+    BEGIN
+        PERFORM pg_notify('das_changes', json_build_object(
+            'table', 'asset',
+            'id', encode(NEW.id, 'hex')
+        )::text);
+        RETURN NULL;
+    END;
+    $$ LANGUAGE plpgsql;",
+    "CREATE TRIGGER asset_notify_insert AFTER INSERT ON asset
+        FOR EACH ROW EXECUTE PROCEDURE notify_asset_change();",
+    "CREATE TRIGGER asset_notify_update AFTER UPDATE ON asset
+        FOR EACH ROW WHEN (OLD.* IS DISTINCT FROM NEW.*)
+        EXECUTE PROCEDURE notify_asset_change();",
+    "CREATE OR REPLACE FUNCTION notify_asset_grouping_change() RETURNS trigger AS $$
+    BEGIN
+        PERFORM pg_notify('das_changes', json_build_object(
+            'table', 'asset',
+            'id', encode(NEW.asset_id, 'hex')
+        )::text);
+        RETURN NULL;
+    END;
+    $$ LANGUAGE plpgsql;",
+    "CREATE TRIGGER asset_grouping_notify_insert AFTER INSERT ON asset_grouping
+        FOR EACH ROW EXECUTE PROCEDURE notify_asset_grouping_change();",
+    "CREATE TRIGGER asset_grouping_notify_update AFTER UPDATE ON asset_grouping
+        FOR EACH ROW WHEN (OLD.* IS DISTINCT FROM NEW.*)
+        EXECUTE PROCEDURE notify_asset_grouping_change();",
+    "CREATE OR REPLACE FUNCTION set_change_notifications(enabled boolean) RETURNS void AS $$
+    DECLARE
+        action text := CASE WHEN enabled THEN 'ENABLE' ELSE 'DISABLE' END;
+    BEGIN
+        EXECUTE format('ALTER TABLE compressed_data %s TRIGGER compressed_data_notify_insert_delete', action);
+        EXECUTE format('ALTER TABLE compressed_data %s TRIGGER compressed_data_notify_update', action);
+        EXECUTE format('ALTER TABLE character_history %s TRIGGER character_history_notify_insert', action);
+        EXECUTE format('ALTER TABLE asset %s TRIGGER asset_notify_insert', action);
+        EXECUTE format('ALTER TABLE asset %s TRIGGER asset_notify_update', action);
+        EXECUTE format('ALTER TABLE asset_grouping %s TRIGGER asset_grouping_notify_insert', action);
+        EXECUTE format('ALTER TABLE asset_grouping %s TRIGGER asset_grouping_notify_update', action);
+    END;
+    $$ LANGUAGE plpgsql;",
+    "SELECT set_change_notifications(false);",
+];
+
+const DOWN: &[&str] = &[
+    "DROP FUNCTION IF EXISTS set_change_notifications(boolean);",
+    "DROP TRIGGER IF EXISTS asset_grouping_notify_update ON asset_grouping;",
+    "DROP TRIGGER IF EXISTS asset_grouping_notify_insert ON asset_grouping;",
+    "DROP FUNCTION IF EXISTS notify_asset_grouping_change();",
+    "DROP TRIGGER IF EXISTS asset_notify_update ON asset;",
+    "DROP TRIGGER IF EXISTS asset_notify_insert ON asset;",
+    "DROP FUNCTION IF EXISTS notify_asset_change();",
+    "DROP TRIGGER IF EXISTS character_history_notify_insert ON character_history;",
+    "DROP FUNCTION IF EXISTS notify_character_history_change();",
+    "DROP TRIGGER IF EXISTS compressed_data_notify_update ON compressed_data;",
+    "DROP TRIGGER IF EXISTS compressed_data_notify_insert_delete ON compressed_data;",
+    "DROP FUNCTION IF EXISTS notify_compressed_data_change();",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                    id BIGSERIAL PRIMARY KEY,
+                    webhook TEXT NOT NULL,
+                    event_id TEXT NOT NULL,
+                    url TEXT NOT NULL,
+                    payload JSONB NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    attempts INT NOT NULL DEFAULT 0,
+                    max_attempts INT NOT NULL,
+                    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    last_error TEXT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    delivered_at TIMESTAMPTZ,
+                    UNIQUE (webhook, event_id)
+                );"
+                .to_string(),
+            ))
+            .await?;
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX IF NOT EXISTS webhook_deliveries_pending_idx ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';"
+                    .to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP TABLE IF EXISTS webhook_deliveries;".to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+}
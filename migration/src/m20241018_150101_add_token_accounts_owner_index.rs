@@ -0,0 +1,45 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        // `getTokenAccounts` pages by pubkey and `getTokenBalances` groups by mint for one owner
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX IF NOT EXISTS ta_owner_pubkey ON token_accounts (owner, pubkey);"
+                    .to_string(),
+            ))
+            .await?;
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX IF NOT EXISTS ta_owner_mint ON token_accounts (owner, mint);"
+                    .to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP INDEX IF EXISTS ta_owner_mint;".to_string(),
+            ))
+            .await?;
+        connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP INDEX IF EXISTS ta_owner_pubkey;".to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `getCurrencyHoldings` looks up HPL `HolderAccount`s by owner
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX IF NOT EXISTS accounts_holder_account_owner_idx ON accounts ((parsed_data->>'owner')) WHERE discriminator = '\\xa45f46f891eea9b0'::bytea;"
+                    .to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP INDEX IF EXISTS accounts_holder_account_owner_idx;".to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // characters are linked to the NFT or cNFT they wrap through `source.params.mint`
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX IF NOT EXISTS compressed_data_source_mint_idx ON compressed_data ((parsed_data->'source'->'params'->>'mint')) WHERE (parsed_data->'source'->'params'->>'mint') IS NOT NULL;"
+                    .to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "DROP INDEX IF EXISTS compressed_data_source_mint_idx;".to_string(),
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Accounts are backfilled from their `project` field, `Project` accounts from their own address
+// and pools, holders and custodies from the account they reference. Trees take the project of the
+// account listing them in its `ControlledMerkleTrees`. The ingester keeps both columns up to date
+// afterwards.
+const UP: &[&str] = &[
+    "ALTER TABLE accounts ADD COLUMN IF NOT EXISTS project bytea;",
+    "ALTER TABLE merkle_tree ADD COLUMN IF NOT EXISTS project bytea;",
+    "CREATE INDEX IF NOT EXISTS accounts_project_idx ON accounts (project, discriminator) WHERE project IS NOT NULL;",
+    "CREATE INDEX IF NOT EXISTS merkle_tree_project_idx ON merkle_tree (project) WHERE project IS NOT NULL;",
+    "CREATE OR REPLACE FUNCTION das_base58_decode(input text) RETURNS bytea AS $$
+    DECLARE
+        alphabet text := '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+        num numeric := 0;
+        result bytea := ''::bytea;
+        digit int;
+    BEGIN
+        FOR i IN 1..length(input) LOOP
+            digit := position(substr(input, i, 1) IN alphabet) - 1;
+            IF digit < 0 THEN
+                RETURN NULL;
+            END IF;
+            num := num * 58 + digit;
+        END LOOP;
+        WHILE num > 0 LOOP
+            result := set_byte('\\x00'::bytea, 0, mod(num, 256)::int) || result;
+            num := div(num, 256);
+        END LOOP;
+        FOR i IN 1..length(input) LOOP
+            EXIT WHEN substr(input, i, 1) <> '1';
+            result := '\\x00'::bytea || result;
+        END LOOP;
+        RETURN result;
+    END;
+    $$ LANGUAGE plpgsql IMMUTABLE STRICT;",
+    "UPDATE accounts SET project = das_base58_decode(substr(parsed_data->>'project', 8)) WHERE parsed_data->>'project' LIKE 'pubkey:%';",
+    "UPDATE accounts SET project = id WHERE discriminator = '\\xcda8bdcab5f78e13'::bytea;",
+    "UPDATE accounts a SET project = r.project FROM accounts r
+    WHERE a.project IS NULL AND r.project IS NOT NULL AND r.id = das_base58_decode(substr(coalesce(
+        a.parsed_data->>'staking_pool',
+        a.parsed_data->>'currency',
+        a.parsed_data->>'character_model'
+    ), 8));",
+    "UPDATE merkle_tree t SET project = s.project FROM (
+        SELECT a.project, das_base58_decode(substr(tree, 8)) AS tree_id
+        FROM accounts a, jsonb_array_elements_text(coalesce(
+            a.parsed_data->'merkle_trees'->'merkle_trees',
+            a.parsed_data->'profile_trees'->'merkle_trees',
+            a.parsed_data->'storage'->'params'->'merkle_trees'->'merkle_trees',
+            '[]'::jsonb
+        )) AS tree
+        WHERE a.project IS NOT NULL
+    ) s
+    WHERE t.id = s.tree_id AND t.project IS NULL;",
+    "DROP FUNCTION IF EXISTS das_base58_decode(text);",
+];
+
+const DOWN: &[&str] = &[
+    "DROP INDEX IF EXISTS merkle_tree_project_idx;",
+    "DROP INDEX IF EXISTS accounts_project_idx;",
+    "ALTER TABLE merkle_tree DROP COLUMN IF EXISTS project;",
+    "ALTER TABLE accounts DROP COLUMN IF EXISTS project;",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
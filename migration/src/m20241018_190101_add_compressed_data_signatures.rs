@@ -0,0 +1,55 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Entries recorded before this migration have no signature. Their event type is inferred from
+// the patch key, empty leaves were not recorded. Duplicates left by replays are dropped so each
+// change is recorded once.
+const UP: &[&str] = &[
+    "ALTER TABLE compressed_data_changelog ADD COLUMN IF NOT EXISTS signature bytea;",
+    "ALTER TABLE compressed_data_changelog ADD COLUMN IF NOT EXISTS event_type varchar(16) NOT NULL DEFAULT 'Full';",
+    "UPDATE compressed_data_changelog SET event_type = 'Patch' WHERE key IS NOT NULL;",
+    "ALTER TABLE character_history ADD COLUMN IF NOT EXISTS signature bytea;",
+    "CREATE INDEX IF NOT EXISTS compressed_data_changelog_tree_leaf_idx ON compressed_data_changelog (tree_id, leaf_idx, id);",
+    "DELETE FROM compressed_data_changelog a USING compressed_data_changelog b WHERE a.id > b.id AND a.tree_id = b.tree_id AND a.leaf_idx = b.leaf_idx AND a.seq = b.seq AND a.event_type = b.event_type AND COALESCE(a.key, '') = COALESCE(b.key, '');",
+    "CREATE UNIQUE INDEX IF NOT EXISTS compressed_data_changelog_change_idx ON compressed_data_changelog (tree_id, leaf_idx, seq, event_type, COALESCE(key, ''));",
+];
+
+const DOWN: &[&str] = &[
+    "DROP INDEX IF EXISTS compressed_data_changelog_change_idx;",
+    "DROP INDEX IF EXISTS compressed_data_changelog_tree_leaf_idx;",
+    "ALTER TABLE character_history DROP COLUMN IF EXISTS signature;",
+    "ALTER TABLE compressed_data_changelog DROP COLUMN IF EXISTS event_type;",
+    "ALTER TABLE compressed_data_changelog DROP COLUMN IF EXISTS signature;",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Trigram indexes serve both the substring `ILIKE` and the `%` similarity searches on name and
+// symbol, the btree index serves sorting by name.
+const UP: &[&str] = &[
+    "CREATE EXTENSION IF NOT EXISTS pg_trgm;",
+    "CREATE INDEX IF NOT EXISTS asset_data_name_trgm_idx ON asset_data USING GIN ((chain_data->>'name') gin_trgm_ops);",
+    "CREATE INDEX IF NOT EXISTS asset_data_symbol_trgm_idx ON asset_data USING GIN ((chain_data->>'symbol') gin_trgm_ops);",
+    "CREATE INDEX IF NOT EXISTS asset_data_name_idx ON asset_data ((chain_data->>'name'));",
+    "CREATE INDEX IF NOT EXISTS asset_data_attributes_idx ON asset_data USING GIN ((metadata->'attributes') jsonb_path_ops);",
+];
+
+const DOWN: &[&str] = &[
+    "DROP INDEX IF EXISTS asset_data_attributes_idx;",
+    "DROP INDEX IF EXISTS asset_data_name_idx;",
+    "DROP INDEX IF EXISTS asset_data_symbol_trgm_idx;",
+    "DROP INDEX IF EXISTS asset_data_name_trgm_idx;",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// The ingester looks up the project of a new tree by the accounts listing it under one of these
+// paths, with `@>` and `project IS NOT NULL`.
+const UP: &[&str] = &[
+    "CREATE INDEX IF NOT EXISTS accounts_merkle_trees_idx ON accounts USING GIN ((parsed_data->'merkle_trees'->'merkle_trees') jsonb_path_ops) WHERE project IS NOT NULL;",
+    "CREATE INDEX IF NOT EXISTS accounts_profile_trees_idx ON accounts USING GIN ((parsed_data->'profile_trees'->'merkle_trees') jsonb_path_ops) WHERE project IS NOT NULL;",
+    "CREATE INDEX IF NOT EXISTS accounts_storage_merkle_trees_idx ON accounts USING GIN ((parsed_data->'storage'->'params'->'merkle_trees'->'merkle_trees') jsonb_path_ops) WHERE project IS NOT NULL;",
+];
+
+const DOWN: &[&str] = &[
+    "DROP INDEX IF EXISTS accounts_storage_merkle_trees_idx;",
+    "DROP INDEX IF EXISTS accounts_profile_trees_idx;",
+    "DROP INDEX IF EXISTS accounts_merkle_trees_idx;",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Saving a staking pool, currency or character model back-fills the project of the accounts
+// referencing it that were indexed first, by equality on these fields and `project IS NULL`.
+const UP: &[&str] = &[
+    "CREATE INDEX IF NOT EXISTS accounts_staking_pool_idx ON accounts ((parsed_data->>'staking_pool')) WHERE project IS NULL;",
+    "CREATE INDEX IF NOT EXISTS accounts_currency_idx ON accounts ((parsed_data->>'currency')) WHERE project IS NULL;",
+    "CREATE INDEX IF NOT EXISTS accounts_character_model_idx ON accounts ((parsed_data->>'character_model')) WHERE project IS NULL;",
+];
+
+const DOWN: &[&str] = &[
+    "DROP INDEX IF EXISTS accounts_character_model_idx;",
+    "DROP INDEX IF EXISTS accounts_currency_idx;",
+    "DROP INDEX IF EXISTS accounts_staking_pool_idx;",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// `mint_slot` is set once, when the asset row is first inserted, to the lowest slot the row was
+// written with; upserts never touch it. That is the mint transaction for bubblegum and the first
+// account update seen for the rest. Rows indexed before this migration stay NULL until reindexed.
+const UP: &[&str] = &[
+    "ALTER TABLE asset ADD COLUMN IF NOT EXISTS mint_slot BIGINT;",
+    "CREATE OR REPLACE FUNCTION set_asset_mint_slot() RETURNS trigger AS $$
+    BEGIN
+        NEW.mint_slot := COALESCE(
+            NEW.mint_slot,
+            LEAST(
+                NEW.slot_updated_cnft_transaction,
+                NEW.slot_updated_mint_account,
+                NEW.slot_updated_metadata_account,
+                NEW.slot_updated_token_account,
+                NEW.slot_updated
+            )
+        );
+        RETURN NEW;
+    END;
+    $$ LANGUAGE plpgsql;",
+    "DROP TRIGGER IF EXISTS asset_mint_slot ON asset;",
+    "CREATE TRIGGER asset_mint_slot BEFORE INSERT ON asset FOR EACH ROW EXECUTE PROCEDURE set_asset_mint_slot();",
+];
+
+const DOWN: &[&str] = &[
+    "DROP TRIGGER IF EXISTS asset_mint_slot ON asset;",
+    "DROP FUNCTION IF EXISTS set_asset_mint_slot();",
+    "ALTER TABLE asset DROP COLUMN IF EXISTS mint_slot;",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in UP {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        for statement in DOWN {
+            connection
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    statement.to_string(),
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
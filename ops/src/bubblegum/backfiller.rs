@@ -8,16 +8,10 @@ use digital_asset_types::dao::cl_audits_v2;
 use futures::{stream::FuturesUnordered, StreamExt};
 use indicatif::HumanDuration;
 use log::{debug, error, info};
-use program_transformers::{ProgramTransformer, TransactionInfo};
+use program_transformers::{rpc::transaction_info_from_rpc, ProgramTransformer, TransactionInfo};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, SqlxPostgresConnector};
-use solana_sdk::instruction::CompiledInstruction;
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
-use solana_transaction_status::option_serializer::OptionSerializer;
-use solana_transaction_status::{
-    InnerInstruction, InnerInstructions, UiInnerInstructions, UiInstruction,
-};
 use sqlx::PgPool;
-use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::{sync::mpsc, task::JoinHandle};
@@ -321,63 +315,16 @@ async fn fetch_and_parse_transaction<'a>(
     let transaction_raw: solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta =
         client.get_transaction(&signature).await?;
 
-    if transaction_raw.transaction.meta.is_none() {
+    let Some(meta) = transaction_raw.transaction.meta.as_ref() else {
         debug!("Skipping Tx {:?} because no meta", signature);
         return Ok(None);
-    }
-
-    let meta = transaction_raw.transaction.meta.unwrap();
+    };
     debug!("tx status {:?} {:?}", meta.status, meta.err);
     if meta.status.is_err() {
         return Ok(None);
     }
 
-    let decoded_tx = transaction_raw.transaction.transaction.decode().unwrap();
-
-    let inner_instructions = Into::<Option<Vec<UiInnerInstructions>>>::into(
-        meta.inner_instructions,
-    )
-    .map(|inner_instructions| {
-        inner_instructions
-            .into_iter()
-            .map(|ix| InnerInstructions {
-                index: ix.index,
-                instructions: ix
-                    .instructions
-                    .into_iter()
-                    .map(|ix| match ix {
-                        UiInstruction::Compiled(ix) => InnerInstruction {
-                            instruction: CompiledInstruction {
-                                program_id_index: ix.program_id_index,
-                                accounts: ix.accounts,
-                                data: bs58::decode(ix.data).into_vec().unwrap(),
-                            },
-                            stack_height: ix.stack_height,
-                        },
-                        _ => todo!(),
-                    })
-                    .collect(),
-            })
-            .collect()
-    });
-    let mut account_keys = decoded_tx.message.static_account_keys().to_vec();
-    if decoded_tx.message.address_table_lookups().is_some() {
-        if let OptionSerializer::Some(ad) = &meta.loaded_addresses {
-            for i in &ad.writable {
-                account_keys.push(Pubkey::from_str(i).unwrap());
-            }
-            for i in &ad.readonly {
-                account_keys.push(Pubkey::from_str(i).unwrap());
-            }
-        }
-    }
-    Ok(Some(TransactionInfo {
-        slot: transaction_raw.slot,
-        signature: decoded_tx.signatures[0],
-        account_keys: account_keys,
-        message_instructions: decoded_tx.message.instructions().to_vec(),
-        meta_inner_instructions: inner_instructions.unwrap_or_default(),
-    }))
+    Ok(Some(transaction_info_from_rpc(transaction_raw)?))
 }
 
 fn spawn_transaction_worker(
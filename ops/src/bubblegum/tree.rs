@@ -47,6 +47,8 @@ pub enum TreeErrorKind {
     TryFromPubkey,
     #[error("try from signature")]
     TryFromSignature,
+    #[error("program transformer")]
+    ProgramTransformer(#[from] program_transformers::error::ProgramTransformerError),
 }
 
 const TREE_GAP_SQL: &str = r#"
@@ -0,0 +1,141 @@
+use super::render;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use das_core::{Rpc, SolanaRpcArgs};
+use plerkle_serialization::{deserializer::*, root_as_account_info};
+use program_transformers::{program_parsers, AccountInfo};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccount;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser, Clone)]
+pub struct Args {
+    /// The public key of the account to fetch from the Solana RPC
+    #[arg(value_parser = parse_pubkey, required_unless_present = "file")]
+    pub pubkey: Option<Pubkey>,
+
+    /// Read the account from a file instead, either a `getAccountInfo` JSON result or a
+    /// `{"pubkey", "account"}` keyed account with a binary encoding, or a flatbuffers
+    /// `AccountInfo` like the integration test fixtures
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    /// Solana RPC used to fetch the account by public key
+    #[arg(long, env)]
+    pub solana_rpc_url: Option<String>,
+}
+
+fn parse_pubkey(s: &str) -> Result<Pubkey, &'static str> {
+    Pubkey::try_from(s).map_err(|_| "Failed to parse public key")
+}
+
+pub async fn run(config: Args) -> Result<()> {
+    let account = match (config.file, config.pubkey) {
+        (Some(file), pubkey) => {
+            let bytes = tokio::fs::read(&file)
+                .await
+                .with_context(|| format!("failed to read {}", file.display()))?;
+            if bytes.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'{') {
+                from_json(serde_json::from_slice(&bytes)?, pubkey)?
+            } else {
+                from_flatbuffers(&bytes)?
+            }
+        }
+        (None, Some(pubkey)) => {
+            let solana_rpc_url = config
+                .solana_rpc_url
+                .context("--solana-rpc-url is required to fetch an account")?;
+            let rpc = Rpc::from_config(SolanaRpcArgs { solana_rpc_url });
+            let response = rpc.get_account(&pubkey).await?;
+            let account = response.value.context("account not found")?;
+            AccountInfo {
+                slot: response.context.slot,
+                pubkey,
+                owner: account.owner,
+                data: account.data,
+            }
+        }
+        (None, None) => bail!("either a public key or --file is required"),
+    };
+
+    let report = inspect(&account);
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    Ok(())
+}
+
+/// Runs the account data through the parser registered for its owner.
+fn inspect(account: &AccountInfo) -> Value {
+    let parsers = program_parsers();
+    let parser = parsers.get(&account.owner);
+
+    json!({
+        "pubkey": account.pubkey.to_string(),
+        "owner": account.owner.to_string(),
+        "slot": account.slot,
+        "data_len": account.data.len(),
+        "registered": parser.is_some(),
+        "parsed": parser.map(|parser| render::parsed(parser.handle_account(&account.data))),
+    })
+}
+
+/// Accepts the `getAccountInfo` result, a keyed account as returned by `getProgramAccounts`, or
+/// either of them wrapped in the whole JSON-RPC response.
+fn from_json(value: Value, pubkey: Option<Pubkey>) -> Result<AccountInfo> {
+    let mut value = match value {
+        Value::Object(mut object) if object.contains_key("result") => object
+            .remove("result")
+            .filter(|result| !result.is_null())
+            .context("account not found")?,
+        value => value,
+    };
+
+    let slot = value["context"]["slot"].as_u64().unwrap_or_default();
+    let (pubkey, account) = if let Some(account) = value.get_mut("account") {
+        let account = account.take();
+        let pubkey = value["pubkey"]
+            .as_str()
+            .context("keyed account without a pubkey")?
+            .parse::<Pubkey>()?;
+        (pubkey, account)
+    } else {
+        let account = value
+            .get_mut("value")
+            .map(Value::take)
+            .filter(|account| !account.is_null())
+            .context("account not found")?;
+        (pubkey.unwrap_or_default(), account)
+    };
+
+    let account: UiAccount = serde_json::from_value(account)?;
+    let account: Account = account
+        .decode()
+        .context("failed to decode account, fetch it with a base58 or base64 encoding")?;
+
+    Ok(AccountInfo {
+        slot,
+        pubkey,
+        owner: account.owner,
+        data: account.data,
+    })
+}
+
+/// Same conversion as the ingester.
+fn from_flatbuffers(bytes: &[u8]) -> Result<AccountInfo> {
+    let account =
+        root_as_account_info(bytes).map_err(|err| anyhow!("invalid flatbuffer: {err:?}"))?;
+    let pubkey = TryInto::<Pubkey>::try_into(account.pubkey().context("missing pubkey")?)
+        .map_err(|err| anyhow!("invalid pubkey: {err:?}"))?;
+    let owner = TryInto::<Pubkey>::try_into(account.owner().context("missing owner")?)
+        .map_err(|err| anyhow!("invalid owner: {err:?}"))?;
+    let data = TryInto::<Vec<u8>>::try_into(PlerkleOptionalU8Vector(account.data()))
+        .map_err(|err| anyhow!("invalid data: {err:?}"))?;
+
+    Ok(AccountInfo {
+        slot: account.slot(),
+        pubkey,
+        owner,
+        data,
+    })
+}
@@ -0,0 +1,32 @@
+use super::{account, transaction};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Commands {
+    /// The 'transaction' command decodes a transaction with every registered program parser.
+    #[clap(name = "transaction")]
+    Transaction(transaction::Args),
+    /// The 'account' command decodes an account with the parser of its owner.
+    #[clap(name = "account")]
+    Account(account::Args),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct InspectCommand {
+    #[clap(subcommand)]
+    pub action: Commands,
+}
+
+pub async fn subcommand(subcommand: InspectCommand) -> Result<()> {
+    match subcommand.action {
+        Commands::Transaction(args) => {
+            transaction::run(args).await?;
+        }
+        Commands::Account(args) => {
+            account::run(args).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+mod account;
+mod cmd;
+mod render;
+mod transaction;
+
+pub use cmd::*;
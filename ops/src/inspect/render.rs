@@ -0,0 +1,380 @@
+use anchor_lang::AnchorDeserialize;
+use blockbuster::error::BlockbusterError;
+use blockbuster::program_handler::ParseResult;
+use blockbuster::programs::{
+    bubblegum::{InstructionName, LeafSchema, LeafSchemaEvent, Payload},
+    hpl_character_manager::HplCharacterManagerAccount,
+    hpl_currency_manager::HplCurrencyManagerAccount,
+    hpl_hive_control::HplHiveControlAccount,
+    hpl_nectar_missions::HplNectarMissionsAccount,
+    hpl_nectar_staking::HplNectarStakingAccount,
+    hpl_resource_manager::HplResourceManagerAccount,
+    mpl_core_program::MplCoreAccountData,
+    noop::{ApplicationDataEventV1, ChangeLogEventV1},
+    token_account::TokenProgramAccount,
+    token_extensions::TokenExtensionsProgramAccount,
+    token_metadata::TokenMetadataAccountData,
+    ProgramParseResult,
+};
+use borsh::BorshDeserialize;
+use hpl_toolkit::prelude::{CompressedDataEvent, CompressedDataEventStream};
+use hpl_toolkit::schema::ToSchema;
+use serde_json::{json, Value};
+
+fn base58(bytes: impl AsRef<[u8]>) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+/// Decoded parser output, or the `BlockbusterError` the parser returned.
+pub fn parsed(result: Result<Box<dyn ParseResult>, BlockbusterError>) -> Value {
+    match result {
+        Ok(result) => parse_result(result.result_type()),
+        Err(error) => json!({ "error": error.to_string() }),
+    }
+}
+
+fn parse_result(result: ProgramParseResult) -> Value {
+    match result {
+        ProgramParseResult::Unknown => json!({ "type": "Unknown" }),
+        ProgramParseResult::Bubblegum(ix) => json!({
+            "type": "Bubblegum",
+            "instruction": bubblegum_instruction_name(&ix.instruction),
+            "tree_update": ix.tree_update.as_ref().map(change_log),
+            "leaf_update": ix.leaf_update.as_ref().map(leaf_schema_event),
+            "payload": ix.payload.as_ref().map(payload),
+        }),
+        ProgramParseResult::AccountCompression(ix) => json!({
+            "type": "AccountCompression",
+            "instruction": format!("{:?}", ix.instruction),
+            "tree_update": ix.tree_update.as_ref().map(change_log),
+            "leaf_update": ix.leaf_update.as_ref().map(application_data),
+        }),
+        ProgramParseResult::Noop(ix) => json!({
+            "type": "Noop",
+            "tree_update": ix.tree_update.as_ref().map(change_log),
+            "application_data": ix.application_data.as_ref().map(application_data),
+        }),
+        ProgramParseResult::MplCore(state) => {
+            let data = match &state.data {
+                MplCoreAccountData::Asset(asset) | MplCoreAccountData::Collection(asset) => {
+                    json!({
+                        "name": asset.name,
+                        "uri": asset.uri,
+                        "owner": asset.owner.map(|owner| owner.to_string()),
+                    })
+                }
+                MplCoreAccountData::HashedAsset | MplCoreAccountData::EmptyAccount => Value::Null,
+            };
+            let kind = match &state.data {
+                MplCoreAccountData::Asset(_) => "Asset",
+                MplCoreAccountData::Collection(_) => "Collection",
+                MplCoreAccountData::HashedAsset => "HashedAsset",
+                MplCoreAccountData::EmptyAccount => "EmptyAccount",
+            };
+            json!({ "type": "MplCore", "account": kind, "data": data })
+        }
+        ProgramParseResult::TokenMetadata(state) => {
+            let (kind, data) = match &state.data {
+                TokenMetadataAccountData::MetadataV1(metadata) => (
+                    "MetadataV1",
+                    json!({
+                        "mint": metadata.mint.to_string(),
+                        "update_authority": metadata.update_authority.to_string(),
+                        "name": metadata.name,
+                        "symbol": metadata.symbol,
+                        "uri": metadata.uri,
+                    }),
+                ),
+                TokenMetadataAccountData::EditionV1(_) => ("EditionV1", Value::Null),
+                TokenMetadataAccountData::MasterEditionV1(_) => ("MasterEditionV1", Value::Null),
+                TokenMetadataAccountData::MasterEditionV2(_) => ("MasterEditionV2", Value::Null),
+                TokenMetadataAccountData::EditionMarker(_) => ("EditionMarker", Value::Null),
+                TokenMetadataAccountData::UseAuthorityRecord(_) => {
+                    ("UseAuthorityRecord", Value::Null)
+                }
+                TokenMetadataAccountData::CollectionAuthorityRecord(_) => {
+                    ("CollectionAuthorityRecord", Value::Null)
+                }
+                TokenMetadataAccountData::EmptyAccount => ("EmptyAccount", Value::Null),
+            };
+            json!({ "type": "TokenMetadata", "account": kind, "data": data })
+        }
+        ProgramParseResult::TokenProgramAccount(account) => match account {
+            TokenProgramAccount::Mint(mint) => json!({
+                "type": "TokenProgramAccount",
+                "account": "Mint",
+                "data": { "supply": mint.supply, "decimals": mint.decimals },
+            }),
+            TokenProgramAccount::TokenAccount(ta) => json!({
+                "type": "TokenProgramAccount",
+                "account": "TokenAccount",
+                "data": {
+                    "mint": ta.mint.to_string(),
+                    "owner": ta.owner.to_string(),
+                    "amount": ta.amount,
+                },
+            }),
+        },
+        ProgramParseResult::TokenExtensionsProgramAccount(account) => match account {
+            TokenExtensionsProgramAccount::MintAccount(mint) => json!({
+                "type": "TokenExtensionsProgramAccount",
+                "account": "MintAccount",
+                "data": {
+                    "supply": mint.account.supply,
+                    "decimals": mint.account.decimals,
+                    "extensions": serde_json::to_value(&mint.extensions).unwrap_or_default(),
+                },
+            }),
+            TokenExtensionsProgramAccount::TokenAccount(ta) => json!({
+                "type": "TokenExtensionsProgramAccount",
+                "account": "TokenAccount",
+                "data": {
+                    "mint": ta.account.mint.to_string(),
+                    "owner": ta.account.owner.to_string(),
+                    "amount": ta.account.amount,
+                },
+            }),
+            TokenExtensionsProgramAccount::EmptyAccount => json!({
+                "type": "TokenExtensionsProgramAccount",
+                "account": "EmptyAccount",
+            }),
+        },
+        ProgramParseResult::HplHiveControl(account) => {
+            let (kind, data) = match account {
+                HplHiveControlAccount::Global(data) => ("Global", schema(data)),
+                HplHiveControlAccount::Project(data) => ("Project", schema(data)),
+                HplHiveControlAccount::DelegateAuthority(data) => {
+                    ("DelegateAuthority", schema(data))
+                }
+                HplHiveControlAccount::Uninitialized => ("Uninitialized", Value::Null),
+                HplHiveControlAccount::Unknown => ("Unknown", Value::Null),
+            };
+            hpl_account("HplHiveControl", kind, data)
+        }
+        ProgramParseResult::HplCharacterManager(account) => {
+            let (kind, data) = match account {
+                HplCharacterManagerAccount::AssemblerConfig(data) => {
+                    ("AssemblerConfig", schema(data))
+                }
+                HplCharacterManagerAccount::CharacterModel(data) => {
+                    ("CharacterModel", schema(data))
+                }
+                HplCharacterManagerAccount::AssetCustody(data) => ("AssetCustody", schema(data)),
+                HplCharacterManagerAccount::Uninitialized => ("Uninitialized", Value::Null),
+                HplCharacterManagerAccount::Unknown => ("Unknown", Value::Null),
+            };
+            hpl_account("HplCharacterManager", kind, data)
+        }
+        ProgramParseResult::HplCurrencyManager(account) => {
+            let (kind, data) = match account {
+                HplCurrencyManagerAccount::Currency(data) => ("Currency", schema(data)),
+                HplCurrencyManagerAccount::HolderAccount(data) => ("HolderAccount", schema(data)),
+                HplCurrencyManagerAccount::Uninitialized => ("Uninitialized", Value::Null),
+                HplCurrencyManagerAccount::Unknown => ("Unknown", Value::Null),
+            };
+            hpl_account("HplCurrencyManager", kind, data)
+        }
+        ProgramParseResult::HplResourceManager(account) => {
+            let (kind, data) = match account {
+                HplResourceManagerAccount::Resource(data) => ("Resource", schema(data)),
+                HplResourceManagerAccount::Recipe(data) => ("Recipe", schema(data)),
+                HplResourceManagerAccount::Faucet(data) => ("Faucet", schema(data)),
+                HplResourceManagerAccount::Uninitialized => ("Uninitialized", Value::Null),
+                HplResourceManagerAccount::Unknown => ("Unknown", Value::Null),
+            };
+            hpl_account("HplResourceManager", kind, data)
+        }
+        ProgramParseResult::HplNectarMissions(account) => {
+            let (kind, data) = match account {
+                HplNectarMissionsAccount::MissionPool(data) => ("MissionPool", schema(data)),
+                HplNectarMissionsAccount::Mission(data) => ("Mission", schema(data)),
+                HplNectarMissionsAccount::Uninitialized => ("Uninitialized", Value::Null),
+                HplNectarMissionsAccount::Unknown => ("Unknown", Value::Null),
+            };
+            hpl_account("HplNectarMissions", kind, data)
+        }
+        ProgramParseResult::HplNectarStaking(account) => {
+            let (kind, data) = match account {
+                HplNectarStakingAccount::StakingPool(data) => ("StakingPool", schema(data)),
+                HplNectarStakingAccount::Multipliers(data) => ("Multipliers", schema(data)),
+                HplNectarStakingAccount::Staker(data) => ("Staker", schema(data)),
+                HplNectarStakingAccount::Uninitialized => ("Uninitialized", Value::Null),
+                HplNectarStakingAccount::Unknown => ("Unknown", Value::Null),
+            };
+            hpl_account("HplNectarStaking", kind, data)
+        }
+    }
+}
+
+fn schema(data: &impl ToSchema) -> Value {
+    data.schema_value().into()
+}
+
+fn hpl_account(program: &str, kind: &str, data: Value) -> Value {
+    json!({ "type": program, "account": kind, "data": data })
+}
+
+fn change_log(event: &ChangeLogEventV1) -> Value {
+    json!({
+        "id": event.id.to_string(),
+        "seq": event.seq,
+        "index": event.index,
+        "path": event
+            .path
+            .iter()
+            .map(|node| json!({ "node": base58(node.node), "index": node.index }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Application data is either a Honeycomb `CompressedDataEvent` or a Bubblegum `LeafSchemaEvent`.
+fn application_data(event: &ApplicationDataEventV1) -> Value {
+    let data = event.application_data.as_slice();
+    let error = match <CompressedDataEvent as AnchorDeserialize>::try_from_slice(data) {
+        Ok(event) => return compressed_data_event(event),
+        Err(error) => error.to_string(),
+    };
+    if let Ok(event) = <LeafSchemaEvent as BorshDeserialize>::try_from_slice(data) {
+        return leaf_schema_event(&event);
+    }
+    json!({
+        "type": "Unknown",
+        "data": base58(data),
+        "compressed_data_error": error,
+    })
+}
+
+fn compressed_data_event(event: CompressedDataEvent) -> Value {
+    match event {
+        CompressedDataEvent::TreeSchemaValue {
+            discriminator,
+            tree_id,
+            schema,
+            canopy_depth,
+            program_id,
+        } => json!({
+            "type": "CompressedDataEvent::TreeSchemaValue",
+            "tree_id": base58(tree_id),
+            "program_id": base58(program_id),
+            "discriminator": base58(discriminator),
+            "canopy_depth": canopy_depth,
+            "schema": Value::from(schema.schema_value()),
+        }),
+        CompressedDataEvent::Leaf {
+            slot,
+            tree_id,
+            leaf_idx,
+            seq,
+            stream_type,
+        } => {
+            let stream = match stream_type {
+                CompressedDataEventStream::Full { data } => {
+                    json!({ "type": "Full", "data": Value::from(data) })
+                }
+                CompressedDataEventStream::PatchChunk { key, data } => {
+                    json!({ "type": "PatchChunk", "key": key, "data": Value::from(data) })
+                }
+                CompressedDataEventStream::Empty => json!({ "type": "Empty" }),
+            };
+            json!({
+                "type": "CompressedDataEvent::Leaf",
+                "tree_id": base58(tree_id),
+                "leaf_idx": leaf_idx,
+                "seq": seq,
+                "slot": slot,
+                "stream": stream,
+            })
+        }
+    }
+}
+
+fn leaf_schema_event(event: &LeafSchemaEvent) -> Value {
+    let LeafSchema::V1 {
+        id,
+        owner,
+        delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+    } = &event.schema;
+    json!({
+        "type": "LeafSchemaEvent",
+        "id": id.to_string(),
+        "owner": owner.to_string(),
+        "delegate": delegate.to_string(),
+        "nonce": nonce,
+        "data_hash": base58(data_hash),
+        "creator_hash": base58(creator_hash),
+        "leaf_hash": base58(event.leaf_hash),
+    })
+}
+
+fn payload(payload: &Payload) -> Value {
+    match payload {
+        Payload::Unknown => json!({ "type": "Unknown" }),
+        Payload::MintV1 {
+            args,
+            authority,
+            tree_id,
+        } => json!({
+            "type": "MintV1",
+            "name": args.name,
+            "uri": args.uri,
+            "authority": authority.to_string(),
+            "tree_id": tree_id.to_string(),
+        }),
+        Payload::Decompress { args } => json!({
+            "type": "Decompress",
+            "name": args.name,
+            "uri": args.uri,
+        }),
+        Payload::CancelRedeem { root } => json!({
+            "type": "CancelRedeem",
+            "root": root.to_string(),
+        }),
+        Payload::CreatorVerification {
+            creator, verify, ..
+        } => json!({
+            "type": "CreatorVerification",
+            "creator": creator.to_string(),
+            "verify": verify,
+        }),
+        Payload::CollectionVerification { collection, verify } => json!({
+            "type": "CollectionVerification",
+            "collection": collection.to_string(),
+            "verify": verify,
+        }),
+        Payload::UpdateMetadata {
+            current_metadata,
+            tree_id,
+            ..
+        } => json!({
+            "type": "UpdateMetadata",
+            "name": current_metadata.name,
+            "tree_id": tree_id.to_string(),
+        }),
+    }
+}
+
+const fn bubblegum_instruction_name(name: &InstructionName) -> &'static str {
+    match name {
+        InstructionName::Unknown => "Unknown",
+        InstructionName::MintV1 => "MintV1",
+        InstructionName::MintToCollectionV1 => "MintToCollectionV1",
+        InstructionName::Redeem => "Redeem",
+        InstructionName::CancelRedeem => "CancelRedeem",
+        InstructionName::Transfer => "Transfer",
+        InstructionName::Delegate => "Delegate",
+        InstructionName::DecompressV1 => "DecompressV1",
+        InstructionName::Compress => "Compress",
+        InstructionName::Burn => "Burn",
+        InstructionName::CreateTree => "CreateTree",
+        InstructionName::VerifyCreator => "VerifyCreator",
+        InstructionName::UnverifyCreator => "UnverifyCreator",
+        InstructionName::VerifyCollection => "VerifyCollection",
+        InstructionName::UnverifyCollection => "UnverifyCollection",
+        InstructionName::SetAndVerifyCollection => "SetAndVerifyCollection",
+        InstructionName::SetDecompressibleState => "SetDecompressibleState",
+        InstructionName::UpdateMetadata => "UpdateMetadata",
+    }
+}
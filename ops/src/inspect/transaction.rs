@@ -0,0 +1,216 @@
+use super::render;
+use anyhow::{anyhow, bail, Context, Result};
+use blockbuster::instruction::{order_instructions, InstructionBundle};
+use clap::Parser;
+use das_core::{Rpc, SolanaRpcArgs};
+use plerkle_serialization::{deserializer::*, root_as_transaction_info};
+use program_transformers::{program_parsers, rpc::transaction_info_from_rpc, TransactionInfo};
+use serde_json::{json, Value};
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, InnerInstructions};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug, Parser, Clone)]
+pub struct Args {
+    /// The signature of the transaction to fetch from the Solana RPC
+    #[arg(value_parser = parse_signature, required_unless_present = "file")]
+    pub signature: Option<Signature>,
+
+    /// Read the transaction from a file instead, either a `getTransaction` JSON result with a
+    /// binary encoding or a flatbuffers `TransactionInfo` like the integration test fixtures
+    #[arg(long, conflicts_with = "signature")]
+    pub file: Option<PathBuf>,
+
+    /// Solana RPC used to fetch the transaction by signature
+    #[arg(long, env)]
+    pub solana_rpc_url: Option<String>,
+}
+
+fn parse_signature(s: &str) -> Result<Signature, &'static str> {
+    Signature::from_str(s).map_err(|_| "Failed to parse signature")
+}
+
+pub async fn run(config: Args) -> Result<()> {
+    let (transaction, status) = match (config.file, config.signature) {
+        (Some(file), _) => {
+            let bytes = tokio::fs::read(&file)
+                .await
+                .with_context(|| format!("failed to read {}", file.display()))?;
+            if bytes.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'{') {
+                from_rpc(rpc_result(serde_json::from_slice(&bytes)?)?)?
+            } else {
+                (from_flatbuffers(&bytes)?, Value::Null)
+            }
+        }
+        (None, Some(signature)) => {
+            let solana_rpc_url = config
+                .solana_rpc_url
+                .context("--solana-rpc-url is required to fetch a transaction")?;
+            let rpc = Rpc::from_config(SolanaRpcArgs { solana_rpc_url });
+            from_rpc(rpc.get_transaction(&signature).await?)?
+        }
+        (None, None) => bail!("either a signature or --file is required"),
+    };
+
+    let report = inspect(&transaction, status)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    Ok(())
+}
+
+/// Key of the account at `index`, the transaction is malformed when it is out of range.
+fn account_key(transaction: &TransactionInfo, index: u8) -> Result<&Pubkey> {
+    transaction
+        .account_keys
+        .get(index as usize)
+        .with_context(|| {
+            format!(
+                "account index {index} out of range, the transaction has {} keys",
+                transaction.account_keys.len()
+            )
+        })
+}
+
+/// Runs the transaction through `order_instructions` and the parser of every ordered instruction.
+fn inspect(transaction: &TransactionInfo, status: Value) -> Result<Value> {
+    let parsers = program_parsers();
+    let programs = parsers.keys().copied().collect::<HashSet<_>>();
+    let signature = transaction.signature.to_string();
+
+    let invoked = transaction
+        .message_instructions
+        .iter()
+        .chain(
+            transaction
+                .meta_inner_instructions
+                .iter()
+                .flat_map(|inner| inner.instructions.iter().map(|ix| &ix.instruction)),
+        )
+        .map(|ix| account_key(transaction, ix.program_id_index))
+        .collect::<Result<HashSet<_>>>()?;
+    let mut invoked = invoked
+        .into_iter()
+        .map(|program| {
+            json!({
+                "program": program.to_string(),
+                "registered": programs.contains(program),
+            })
+        })
+        .collect::<Vec<_>>();
+    invoked.sort_by_key(|program| program["program"].to_string());
+
+    let instructions = order_instructions(
+        &programs,
+        &transaction.account_keys,
+        &transaction.message_instructions,
+        &transaction.meta_inner_instructions,
+    );
+    let instructions = instructions
+        .into_iter()
+        .enumerate()
+        .map(|(index, ((program, instruction), inner_ix))| {
+            let keys = instruction
+                .accounts
+                .iter()
+                .map(|account| account_key(transaction, *account).copied())
+                .collect::<Result<Vec<Pubkey>>>()?;
+            let bundle = InstructionBundle {
+                txn_id: &signature,
+                program,
+                instruction: Some(instruction),
+                inner_ix: inner_ix.as_deref(),
+                keys: &keys,
+                slot: transaction.slot,
+            };
+            let parsed = parsers
+                .get(&program)
+                .map(|parser| render::parsed(parser.handle_instruction(&bundle)));
+            Ok(json!({
+                "index": index,
+                "program": program.to_string(),
+                "data": bs58::encode(&instruction.data).into_string(),
+                "inner_instructions": inner_ix
+                    .iter()
+                    .flatten()
+                    .map(|(program, ix)| json!({
+                        "program": program.to_string(),
+                        "data": bs58::encode(&ix.data).into_string(),
+                    }))
+                    .collect::<Vec<_>>(),
+                "parsed": parsed,
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "signature": signature,
+        "slot": transaction.slot,
+        "status": status,
+        "invoked_programs": invoked,
+        "instructions": instructions,
+    }))
+}
+
+/// Accepts either the `getTransaction` result or the whole JSON-RPC response.
+fn rpc_result(value: Value) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let value = match value {
+        Value::Object(mut object) if object.contains_key("result") => object
+            .remove("result")
+            .filter(|result| !result.is_null())
+            .context("transaction not found")?,
+        value => value,
+    };
+    Ok(serde_json::from_value(value)?)
+}
+
+fn from_rpc(
+    transaction: EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<(TransactionInfo, Value)> {
+    let status = match &transaction.transaction.meta {
+        Some(meta) => match &meta.err {
+            Some(err) => json!({ "error": format!("{err:?}") }),
+            None => json!("ok"),
+        },
+        None => Value::Null,
+    };
+    Ok((transaction_info_from_rpc(transaction)?, status))
+}
+
+/// Same conversion as the ingester, flatbuffers only hold successful transactions.
+fn from_flatbuffers(bytes: &[u8]) -> Result<TransactionInfo> {
+    let transaction =
+        root_as_transaction_info(bytes).map_err(|err| anyhow!("invalid flatbuffer: {err:?}"))?;
+    let meta_inner_instructions = match transaction.compiled_inner_instructions() {
+        Some(compiled) => TryInto::<Vec<InnerInstructions>>::try_into(
+            PlerkleCompiledInnerInstructionVector(compiled),
+        ),
+        None => TryInto::<Vec<InnerInstructions>>::try_into(PlerkleInnerInstructionsVector(
+            transaction
+                .inner_instructions()
+                .context("missing inner instructions")?,
+        )),
+    }
+    .map_err(|err| anyhow!("invalid inner instructions: {err:?}"))?;
+    let signature = TryInto::<Signature>::try_into(PlerkleOptionalStr(transaction.signature()))
+        .map_err(|err| anyhow!("invalid signature: {err:?}"))?;
+    let account_keys =
+        TryInto::<Vec<Pubkey>>::try_into(PlerkleOptionalPubkeyVector(transaction.account_keys()))
+            .map_err(|err| anyhow!("invalid account keys: {err:?}"))?;
+    let message_instructions =
+        TryInto::<Vec<CompiledInstruction>>::try_into(PlerkleCompiledInstructionVector(
+            transaction
+                .outer_instructions()
+                .context("missing outer instructions")?,
+        ))
+        .map_err(|err| anyhow!("invalid instructions: {err:?}"))?;
+
+    Ok(TransactionInfo {
+        slot: transaction.slot(),
+        signature,
+        account_keys,
+        message_instructions,
+        meta_inner_instructions,
+    })
+}
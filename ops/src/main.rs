@@ -1,10 +1,14 @@
 mod account;
 mod bubblegum;
+mod inspect;
+mod subscriptions;
 
 use account::{subcommand as account_subcommand, AccountCommand};
 use anyhow::Result;
 use bubblegum::{subcommand as bubblegum_subcommand, BubblegumCommand};
 use clap::{Parser, Subcommand};
+use inspect::{subcommand as inspect_subcommand, InspectCommand};
+use subscriptions::{subcommand as subscriptions_subcommand, SubscriptionsCommand};
 
 #[derive(Debug, Parser)]
 #[clap(author, version)]
@@ -19,6 +23,10 @@ enum Command {
     Bubblegum(BubblegumCommand),
     #[clap(name = "account")]
     Account(AccountCommand),
+    #[clap(name = "inspect")]
+    Inspect(InspectCommand),
+    #[clap(name = "subscriptions")]
+    Subscriptions(SubscriptionsCommand),
 }
 
 #[tokio::main]
@@ -30,6 +38,8 @@ async fn main() -> Result<()> {
     match args.command {
         Command::Bubblegum(subcommand) => bubblegum_subcommand(subcommand).await?,
         Command::Account(subcommand) => account_subcommand(subcommand).await?,
+        Command::Inspect(subcommand) => inspect_subcommand(subcommand).await?,
+        Command::Subscriptions(subcommand) => subscriptions_subcommand(subcommand).await?,
     }
 
     Ok(())
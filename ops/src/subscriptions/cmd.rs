@@ -0,0 +1,46 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use das_core::{connect_db, PoolArgs};
+use log::info;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Commands {
+    /// The 'enable' command turns on the `das_changes` triggers the API subscriptions listen to.
+    #[clap(name = "enable")]
+    Enable(ToggleArgs),
+    /// The 'disable' command turns the `das_changes` triggers off again.
+    #[clap(name = "disable")]
+    Disable(ToggleArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ToggleArgs {
+    /// Database configuration
+    #[clap(flatten)]
+    pub database: PoolArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SubscriptionsCommand {
+    #[clap(subcommand)]
+    pub action: Commands,
+}
+
+pub async fn subcommand(subcommand: SubscriptionsCommand) -> Result<()> {
+    let (args, enabled) = match subcommand.action {
+        Commands::Enable(args) => (args, true),
+        Commands::Disable(args) => (args, false),
+    };
+    let pool = connect_db(args.database).await?;
+    // altering the triggers locks the tables, run it next to migrations rather than from the API
+    sqlx::query("SELECT set_change_notifications($1)")
+        .bind(enabled)
+        .execute(&pool)
+        .await?;
+    info!(
+        "Change notifications {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+
+    Ok(())
+}
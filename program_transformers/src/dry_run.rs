@@ -0,0 +1,230 @@
+use {
+    crate::events::DomainEvent,
+    sea_orm::{
+        ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, Statement,
+        TransactionTrait,
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::{Map, Value as JsonValue},
+    std::collections::BTreeMap,
+};
+
+/// Outcome of handling a transaction or an account update without persisting it.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRun {
+    pub diff: DatabaseDiff,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Rows that would have been written, keyed by schema qualified table name.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseDiff {
+    pub tables: BTreeMap<String, TableDiff>,
+}
+
+impl DatabaseDiff {
+    pub const fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDiff {
+    pub inserted: Vec<JsonValue>,
+    pub updated: Vec<RowUpdate>,
+    pub deleted: Vec<JsonValue>,
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Primary key of an updated row and its changed columns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowUpdate {
+    pub key: JsonValue,
+    pub changes: BTreeMap<String, ColumnChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnChange {
+    pub before: JsonValue,
+    pub after: JsonValue,
+}
+
+/// Runs handlers inside a savepoint of a `REPEATABLE READ` transaction and rolls both back.
+///
+/// Rows are attributed to the session through their `xmin`/`xmax` system columns: written rows
+/// carry an xid of the session after the savepoint, replaced and deleted rows keep it as `xmax`
+/// once it is rolled back. Those are compared with `age`, which is relative to the session's
+/// top level xid and wraps like the xids themselves, so nothing breaks past 2^32 transactions.
+/// Every table touched by the handlers is scanned, so this is meant for debugging against a
+/// development database rather than for the ingest path.
+pub struct DryRunSession {
+    outer: DatabaseTransaction,
+    savepoint: DatabaseTransaction,
+}
+
+impl DryRunSession {
+    pub async fn begin(conn: &DatabaseConnection) -> Result<Self, DbErr> {
+        let outer = conn.begin().await?;
+        outer
+            .execute(Statement::from_string(
+                DbBackend::Postgres,
+                "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ".to_owned(),
+            ))
+            .await?;
+        // `age` is measured from the top level xid, assign it before any subtransaction so the
+        // rows written through the savepoint are the only ones at or past it
+        outer
+            .execute(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT pg_current_xact_id()".to_owned(),
+            ))
+            .await?;
+        let savepoint = outer.begin().await?;
+        Ok(Self { outer, savepoint })
+    }
+
+    /// Connection the handlers write through.
+    pub const fn connection(&self) -> &DatabaseTransaction {
+        &self.savepoint
+    }
+
+    /// Discards everything, used when a handler failed and the savepoint can't be queried.
+    pub async fn rollback(self) -> Result<(), DbErr> {
+        self.savepoint.rollback().await?;
+        self.outer.rollback().await
+    }
+
+    /// Collects the rows written through [`Self::connection`], then discards them.
+    pub async fn finish(self) -> Result<DatabaseDiff, DbErr> {
+        let tables = self
+            .savepoint
+            .query_all(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT format('%I.%I', schemaname, relname) AS name FROM pg_stat_xact_user_tables \
+                 WHERE n_tup_ins + n_tup_upd + n_tup_del > 0 ORDER BY 1"
+                    .to_owned(),
+            ))
+            .await?
+            .into_iter()
+            .map(|row| row.try_get::<String>("", "name"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // rows locked by the session are included so they pair with their `before` version
+        let mut after = Vec::with_capacity(tables.len());
+        for table in &tables {
+            after.push(
+                query_rows(
+                    &self.savepoint,
+                    table,
+                    "age(t.xmin) <= 0 OR age(t.xmax) <= 0",
+                )
+                .await?,
+            );
+        }
+        self.savepoint.rollback().await?;
+
+        let mut diff = DatabaseDiff::default();
+        for (table, after) in tables.into_iter().zip(after) {
+            let primary_key = primary_key(&self.outer, &table).await?;
+            let before = query_rows(&self.outer, &table, "age(t.xmax) <= 0").await?;
+            let table_diff = diff_rows(&primary_key, before, after);
+            if !table_diff.is_empty() {
+                diff.tables.insert(table, table_diff);
+            }
+        }
+        self.outer.rollback().await?;
+        Ok(diff)
+    }
+}
+
+async fn query_rows(
+    conn: &DatabaseTransaction,
+    table: &str,
+    filter: &str,
+) -> Result<Vec<JsonValue>, DbErr> {
+    conn.query_all(Statement::from_string(
+        DbBackend::Postgres,
+        format!("SELECT to_jsonb(t) AS row FROM {table} t WHERE {filter}"),
+    ))
+    .await?
+    .into_iter()
+    .map(|row| row.try_get::<JsonValue>("", "row"))
+    .collect()
+}
+
+async fn primary_key(conn: &DatabaseTransaction, table: &str) -> Result<Vec<String>, DbErr> {
+    conn.query_all(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT a.attname::text AS name FROM pg_index i \
+         JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey) \
+         WHERE i.indrelid = $1::regclass AND i.indisprimary \
+         ORDER BY array_position(i.indkey::int2[], a.attnum)",
+        vec![table.into()],
+    ))
+    .await?
+    .into_iter()
+    .map(|row| row.try_get::<String>("", "name"))
+    .collect()
+}
+
+/// Pairs the versions of a row by primary key, or by the whole row for tables without one.
+pub fn diff_rows(
+    primary_key: &[String],
+    before: Vec<JsonValue>,
+    after: Vec<JsonValue>,
+) -> TableDiff {
+    let row_key = |row: &JsonValue| -> JsonValue {
+        if primary_key.is_empty() {
+            return row.clone();
+        }
+        let key = primary_key
+            .iter()
+            .map(|column| (column.clone(), row.get(column).cloned().unwrap_or_default()))
+            .collect::<Map<_, _>>();
+        JsonValue::Object(key)
+    };
+
+    let mut before = before
+        .into_iter()
+        .map(|row| (row_key(&row).to_string(), row))
+        .collect::<BTreeMap<_, _>>();
+    let after = after
+        .into_iter()
+        .map(|row| (row_key(&row).to_string(), row))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut diff = TableDiff::default();
+    for (key, row) in after {
+        let Some(previous) = before.remove(&key) else {
+            diff.inserted.push(row);
+            continue;
+        };
+        let changes = match (&previous, &row) {
+            (JsonValue::Object(previous), JsonValue::Object(current)) => current
+                .iter()
+                .filter(|(column, value)| previous.get(*column) != Some(*value))
+                .map(|(column, value)| {
+                    let change = ColumnChange {
+                        before: previous.get(column).cloned().unwrap_or_default(),
+                        after: value.clone(),
+                    };
+                    (column.clone(), change)
+                })
+                .collect::<BTreeMap<_, _>>(),
+            _ => BTreeMap::new(),
+        };
+        if !changes.is_empty() {
+            diff.updated.push(RowUpdate {
+                key: row_key(&row),
+                changes,
+            });
+        }
+    }
+    diff.deleted = before.into_values().collect();
+    diff
+}
@@ -0,0 +1,92 @@
+use {
+    futures::future::BoxFuture,
+    serde::{Deserialize, Serialize},
+    serde_json::Value as JsonValue,
+    std::sync::Mutex,
+};
+
+/// A normalized fact derived while indexing, e.g. a character being staked or a currency holder
+/// changing status. `before` and `after` hold the indexed values around the change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainEvent {
+    pub event_type: String,
+    pub program: String,
+    pub project: Option<String>,
+    pub subject: String,
+    pub slot: u64,
+    pub signature: Option<String>,
+    pub before: Option<JsonValue>,
+    pub after: Option<JsonValue>,
+}
+
+pub type DomainEventNotifier = Box<
+    dyn Fn(
+            Vec<DomainEvent>,
+        ) -> BoxFuture<'static, Result<(), Box<dyn std::error::Error + Send + Sync>>>
+        + Sync
+        + Send,
+>;
+
+/// Collects the events produced while handling one transaction or account update so they are
+/// only published once everything has been written.
+#[derive(Debug, Default)]
+pub struct DomainEvents {
+    enabled: bool,
+    signature: Option<String>,
+    events: Mutex<Vec<DomainEvent>>,
+}
+
+impl DomainEvents {
+    pub fn new(enabled: bool, signature: Option<String>) -> Self {
+        Self {
+            enabled,
+            signature,
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Handlers skip the extra reads needed for `before` values when nobody consumes events.
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn push(
+        &self,
+        event_type: impl Into<String>,
+        program: impl Into<String>,
+        project: Option<String>,
+        subject: impl Into<String>,
+        slot: u64,
+        before: Option<JsonValue>,
+        after: Option<JsonValue>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let event = DomainEvent {
+            event_type: event_type.into(),
+            program: program.into(),
+            project,
+            subject: subject.into(),
+            slot,
+            signature: self.signature.clone(),
+            before,
+            after,
+        };
+        self.events
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(event);
+    }
+
+    pub fn into_inner(self) -> Vec<DomainEvent> {
+        self.events
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
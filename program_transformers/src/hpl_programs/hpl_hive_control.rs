@@ -1,19 +1,24 @@
 use {
     crate::{
         error::{ProgramTransformerError, ProgramTransformerResult},
+        events::DomainEvents,
         AccountInfo,
     },
     blockbuster::programs::hpl_hive_control::{
         DelegateAuthority, Global, HplHiveControlAccount, Project,
     },
-    sea_orm::DatabaseConnection,
+    sea_orm::{ConnectionTrait, TransactionTrait},
 };
 
-pub async fn handle_hpl_hive_control_account<'a, 'b, 'c>(
+pub async fn handle_hpl_hive_control_account<'a, 'b, 'c, T>(
     account_info: &AccountInfo,
     parsing_result: &'a HplHiveControlAccount,
-    db: &'b DatabaseConnection,
-) -> ProgramTransformerResult<()> {
+    db: &'b T,
+    events: &DomainEvents,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     match &parsing_result {
         HplHiveControlAccount::Global(account) => {
             super::save_account(
@@ -23,6 +28,7 @@ pub async fn handle_hpl_hive_control_account<'a, 'b, 'c>(
                 Global::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
@@ -34,6 +40,7 @@ pub async fn handle_hpl_hive_control_account<'a, 'b, 'c>(
                 Project::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
@@ -45,6 +52,7 @@ pub async fn handle_hpl_hive_control_account<'a, 'b, 'c>(
                 DelegateAuthority::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
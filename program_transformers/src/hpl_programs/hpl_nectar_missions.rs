@@ -1,17 +1,22 @@
 use {
     crate::{
         error::{ProgramTransformerError, ProgramTransformerResult},
+        events::DomainEvents,
         AccountInfo,
     },
     blockbuster::programs::hpl_nectar_missions::{HplNectarMissionsAccount, Mission, MissionPool},
-    sea_orm::DatabaseConnection,
+    sea_orm::{ConnectionTrait, TransactionTrait},
 };
 
-pub async fn handle_hpl_nectar_missions_account<'a, 'b, 'c>(
+pub async fn handle_hpl_nectar_missions_account<'a, 'b, 'c, T>(
     account_info: &AccountInfo,
     parsing_result: &'a HplNectarMissionsAccount,
-    db: &'b DatabaseConnection,
-) -> ProgramTransformerResult<()> {
+    db: &'b T,
+    events: &DomainEvents,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     match &parsing_result {
         HplNectarMissionsAccount::MissionPool(account) => {
             super::save_account(
@@ -21,6 +26,7 @@ pub async fn handle_hpl_nectar_missions_account<'a, 'b, 'c>(
                 MissionPool::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
@@ -32,6 +38,7 @@ pub async fn handle_hpl_nectar_missions_account<'a, 'b, 'c>(
                 Mission::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
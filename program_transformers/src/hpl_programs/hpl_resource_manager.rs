@@ -1,19 +1,24 @@
 use {
     crate::{
         error::{ProgramTransformerError, ProgramTransformerResult},
+        events::DomainEvents,
         AccountInfo,
     },
     blockbuster::programs::hpl_resource_manager::{
         Faucet, HplResourceManagerAccount, Recipe, Resource,
     },
-    sea_orm::DatabaseConnection,
+    sea_orm::{ConnectionTrait, TransactionTrait},
 };
 
-pub async fn handle_hpl_resource_manager_account<'a, 'b, 'c>(
+pub async fn handle_hpl_resource_manager_account<'a, 'b, 'c, T>(
     account_info: &AccountInfo,
     parsing_result: &'a HplResourceManagerAccount,
-    db: &'b DatabaseConnection,
-) -> ProgramTransformerResult<()> {
+    db: &'b T,
+    events: &DomainEvents,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     match &parsing_result {
         HplResourceManagerAccount::Resource(account) => {
             super::save_account(
@@ -23,6 +28,7 @@ pub async fn handle_hpl_resource_manager_account<'a, 'b, 'c>(
                 Resource::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
@@ -34,6 +40,7 @@ pub async fn handle_hpl_resource_manager_account<'a, 'b, 'c>(
                 Recipe::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
@@ -45,6 +52,7 @@ pub async fn handle_hpl_resource_manager_account<'a, 'b, 'c>(
                 Faucet::DISCRIMINATOR.to_vec(),
                 account,
                 account_info.slot as i64,
+                events,
             )
             .await
         }
@@ -1,8 +1,18 @@
-use crate::error::{ProgramTransformerError, ProgramTransformerResult};
+use crate::{
+    error::{ProgramTransformerError, ProgramTransformerResult},
+    events::DomainEvents,
+};
+use digital_asset_types::{
+    dao::{accounts, merkle_tree},
+    dapi::common::{json_account_trees, json_pubkey_bytes},
+};
 use sea_orm::{
-    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
-    DbBackend, EntityTrait, ExecResult,
+    query::*,
+    sea_query::{Expr, OnConflict},
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, ExecResult, TransactionTrait,
 };
+use serde_json::Value as JsonValue;
 
 mod hpl_character_manager;
 mod hpl_currency_manager;
@@ -18,20 +28,39 @@ pub use hpl_nectar_missions::handle_hpl_nectar_missions_account;
 pub use hpl_nectar_staking::handle_hpl_nectar_staking_account;
 pub use hpl_resource_manager::handle_hpl_resource_manager_account;
 
-pub async fn save_account<'a, Data: hpl_toolkit::schema::ToSchema>(
-    db: &'a DatabaseConnection,
+pub async fn save_account<'a, T, Data: hpl_toolkit::schema::ToSchema>(
+    db: &'a T,
     address: Vec<u8>,
     program_id: Vec<u8>,
     discriminator: Vec<u8>,
     data: &'a Data,
     slot: i64,
-) -> ProgramTransformerResult<ExecResult> {
+    events: &DomainEvents,
+) -> ProgramTransformerResult<ExecResult>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let parsed_data: JsonValue = data.schema_value().into();
+    let before = if events.enabled() {
+        digital_asset_types::dao::accounts::Entity::find_by_id(address.clone())
+            .one(db)
+            .await
+            .map_err(|db_err| ProgramTransformerError::StorageReadError(db_err.to_string()))?
+            .map(|account| account.parsed_data)
+    } else {
+        None
+    };
+    let subject = bs58::encode(&address).into_string();
+    let program = bs58::encode(&program_id).into_string();
+    let project = account_project::<Data>(db, &address, &parsed_data).await?;
+
     let account = digital_asset_types::dao::accounts::ActiveModel {
         id: Set(address),
         program_id: Set(program_id),
         discriminator: Set(discriminator),
-        parsed_data: Set(data.schema_value().into()),
+        parsed_data: Set(parsed_data.clone()),
         slot_updated: Set(slot),
+        project: Set(project.clone()),
         ..Default::default()
     };
 
@@ -41,12 +70,133 @@ pub async fn save_account<'a, Data: hpl_toolkit::schema::ToSchema>(
                 .update_columns([
                     digital_asset_types::dao::accounts::Column::ParsedData,
                     digital_asset_types::dao::accounts::Column::SlotUpdated,
+                    digital_asset_types::dao::accounts::Column::Project,
                 ])
                 .to_owned(),
         )
         .build(DbBackend::Postgres);
 
-    db.execute(query)
+    let result = db
+        .execute(query)
         .await
-        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    let trees = json_account_trees(&parsed_data)
+        .iter()
+        .filter_map(|tree| bs58::decode(tree).into_vec().ok())
+        .collect::<Vec<_>>();
+    if let (Some(project), false) = (&project, trees.is_empty()) {
+        merkle_tree::Entity::update_many()
+            .col_expr(merkle_tree::Column::Project, Expr::value(project.clone()))
+            .filter(merkle_tree::Column::Id.is_in(trees))
+            .exec(db)
+            .await
+            .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+    }
+
+    // The accounts referencing this one may have been indexed before it.
+    let reference = PROJECT_REFERENCES
+        .iter()
+        .find(|(parent, _field)| *parent == account_name::<Data>());
+    if let (Some(project), Some((_parent, field))) = (&project, reference) {
+        let query = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            &format!(
+                "UPDATE accounts SET project = $1
+                WHERE project IS NULL AND parsed_data->>'{field}' IN ($2, $3)"
+            ),
+            vec![
+                project.clone().into(),
+                format!("pubkey:{subject}").into(),
+                subject.clone().into(),
+            ],
+        );
+        db.execute(query)
+            .await
+            .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+    }
+
+    if events.enabled() && before.as_ref() != Some(&parsed_data) {
+        let project = project.map(|project| bs58::encode(project).into_string());
+        push_account_events::<Data>(events, program, project, subject, slot, before, parsed_data);
+    }
+    Ok(result)
+}
+
+/// Accounts that don't store their project inherit it from the account they reference, by
+/// account name of the reference and field referencing it.
+const PROJECT_REFERENCES: [(&str, &str); 3] = [
+    ("StakingPool", "staking_pool"),
+    ("Currency", "currency"),
+    ("CharacterModel", "character_model"),
+];
+
+/// Returns the project an account belongs to: its own address for a `Project`, its `project`
+/// field, or else the project of the pool, currency or model it references.
+async fn account_project<Data>(
+    db: &impl ConnectionTrait,
+    address: &[u8],
+    parsed_data: &JsonValue,
+) -> ProgramTransformerResult<Option<Vec<u8>>> {
+    if account_name::<Data>() == "Project" {
+        return Ok(Some(address.to_vec()));
+    }
+    if let Some(project) = json_pubkey_bytes(parsed_data.get("project")) {
+        return Ok(Some(project));
+    }
+    let Some(reference) = PROJECT_REFERENCES
+        .iter()
+        .find_map(|(_parent, field)| json_pubkey_bytes(parsed_data.get(*field)))
+    else {
+        return Ok(None);
+    };
+    Ok(accounts::Entity::find_by_id(reference)
+        .one(db)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageReadError(db_err.to_string()))?
+        .and_then(|account| account.project))
+}
+
+fn account_name<Data>() -> &'static str {
+    let name = std::any::type_name::<Data>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn push_account_events<Data>(
+    events: &DomainEvents,
+    program: String,
+    project: Option<String>,
+    subject: String,
+    slot: i64,
+    before: Option<JsonValue>,
+    after: JsonValue,
+) {
+    let name = account_name::<Data>();
+
+    let mut event_types = vec![format!(
+        "{}{}",
+        name,
+        if before.is_some() {
+            "Updated"
+        } else {
+            "Created"
+        }
+    )];
+    if let Some(before) = &before {
+        if name == "HolderAccount" && before.get("status") != after.get("status") {
+            event_types.push("HolderStatusChanged".to_string());
+        }
+    }
+
+    for event_type in event_types {
+        events.push(
+            event_type,
+            program.clone(),
+            project.clone(),
+            subject.clone(),
+            slot as u64,
+            before.clone(),
+            Some(after.clone()),
+        );
+    }
 }
@@ -2,7 +2,9 @@ use {
     crate::{
         account_compression::handle_account_compression_instruction,
         bubblegum::handle_bubblegum_instruction,
+        dry_run::{DryRun, DryRunSession},
         error::{ProgramTransformerError, ProgramTransformerResult},
+        events::{DomainEventNotifier, DomainEvents},
         hpl_programs::{
             handle_hpl_character_manager_account, handle_hpl_currency_manager_account,
             handle_hpl_hive_control_account, handle_hpl_nectar_missions_account,
@@ -46,10 +48,13 @@ use {
 mod account_compression;
 mod asset_upserts;
 mod bubblegum;
+pub mod dry_run;
 pub mod error;
+pub mod events;
 mod hpl_programs;
 mod mpl_core_program;
 mod noop;
+pub mod rpc;
 mod token;
 mod token_extensions;
 mod token_metadata;
@@ -106,6 +111,44 @@ pub struct ProgramTransformer {
     parsers: HashMap<Pubkey, Box<dyn ProgramParser>>,
     key_set: HashSet<Pubkey>,
     cl_audits: bool,
+    event_notifier: Option<DomainEventNotifier>,
+}
+
+/// Every parser the transformer dispatches to, keyed by program id.
+pub fn program_parsers() -> HashMap<Pubkey, Box<dyn ProgramParser>> {
+    let mut parsers: HashMap<Pubkey, Box<dyn ProgramParser>> = HashMap::with_capacity(3);
+    let bgum = BubblegumParser {};
+    let token_metadata = TokenMetadataParser {};
+    let token = TokenAccountParser {};
+    let mpl_core = MplCoreParser {};
+    let account_compression = AccountCompressionParser {};
+    let noop = NoopParser {};
+    let hpl_character_manager = HplCharacterManagerParser {};
+    let hpl_currency_manager = HplCurrencyManagerParser {};
+    let hpl_hive_control = HplHiveControlParser {};
+    let hpl_nectar_staking = HplNectarStakingParser {};
+    let hpl_nectar_missions = HplNectarMissionsParser {};
+    let hpl_resource_manager = HplResourceManagerParser {};
+    let token_2022_manager = Token2022AccountParser {};
+
+    parsers.insert(bgum.key(), Box::new(bgum));
+    parsers.insert(token_metadata.key(), Box::new(token_metadata));
+    parsers.insert(token.key(), Box::new(token));
+    parsers.insert(mpl_core.key(), Box::new(mpl_core));
+    parsers.insert(account_compression.key(), Box::new(account_compression));
+    parsers.insert(noop.key(), Box::new(noop));
+    parsers.insert(hpl_character_manager.key(), Box::new(hpl_character_manager));
+    parsers.insert(hpl_currency_manager.key(), Box::new(hpl_currency_manager));
+    parsers.insert(hpl_hive_control.key(), Box::new(hpl_hive_control.clone()));
+    parsers.insert(
+        solana_sdk::pubkey!("7fkoi6JB8izj9mAousMhUkavcX9UjigZ7tr7yNRfkkEq"),
+        Box::new(hpl_hive_control),
+    );
+    parsers.insert(hpl_nectar_staking.key(), Box::new(hpl_nectar_staking));
+    parsers.insert(hpl_nectar_missions.key(), Box::new(hpl_nectar_missions));
+    parsers.insert(hpl_resource_manager.key(), Box::new(hpl_resource_manager));
+    parsers.insert(token_2022_manager.key(), Box::new(token_2022_manager));
+    parsers
 }
 
 impl ProgramTransformer {
@@ -114,38 +157,7 @@ impl ProgramTransformer {
         download_metadata_notifier: DownloadMetadataNotifier,
         cl_audits: bool,
     ) -> Self {
-        let mut parsers: HashMap<Pubkey, Box<dyn ProgramParser>> = HashMap::with_capacity(3);
-        let bgum = BubblegumParser {};
-        let token_metadata = TokenMetadataParser {};
-        let token = TokenAccountParser {};
-        let mpl_core = MplCoreParser {};
-        let account_compression = AccountCompressionParser {};
-        let noop = NoopParser {};
-        let hpl_character_manager = HplCharacterManagerParser {};
-        let hpl_currency_manager = HplCurrencyManagerParser {};
-        let hpl_hive_control = HplHiveControlParser {};
-        let hpl_nectar_staking = HplNectarStakingParser {};
-        let hpl_nectar_missions = HplNectarMissionsParser {};
-        let hpl_resource_manager = HplResourceManagerParser {};
-        let token_2022_manager = Token2022AccountParser {};
-
-        parsers.insert(bgum.key(), Box::new(bgum));
-        parsers.insert(token_metadata.key(), Box::new(token_metadata));
-        parsers.insert(token.key(), Box::new(token));
-        parsers.insert(mpl_core.key(), Box::new(mpl_core));
-        parsers.insert(account_compression.key(), Box::new(account_compression));
-        parsers.insert(noop.key(), Box::new(noop));
-        parsers.insert(hpl_character_manager.key(), Box::new(hpl_character_manager));
-        parsers.insert(hpl_currency_manager.key(), Box::new(hpl_currency_manager));
-        parsers.insert(hpl_hive_control.key(), Box::new(hpl_hive_control.clone()));
-        parsers.insert(
-            solana_sdk::pubkey!("7fkoi6JB8izj9mAousMhUkavcX9UjigZ7tr7yNRfkkEq"),
-            Box::new(hpl_hive_control),
-        );
-        parsers.insert(hpl_nectar_staking.key(), Box::new(hpl_nectar_staking));
-        parsers.insert(hpl_nectar_missions.key(), Box::new(hpl_nectar_missions));
-        parsers.insert(hpl_resource_manager.key(), Box::new(hpl_resource_manager));
-        parsers.insert(token_2022_manager.key(), Box::new(token_2022_manager));
+        let parsers = program_parsers();
         let hs = parsers.iter().fold(HashSet::new(), |mut acc, (k, _)| {
             acc.insert(*k);
             acc
@@ -157,6 +169,25 @@ impl ProgramTransformer {
             parsers,
             key_set: hs,
             cl_audits,
+            event_notifier: None,
+        }
+    }
+
+    /// Publishes the domain events derived from every handled transaction and account update.
+    pub fn with_event_notifier(mut self, event_notifier: DomainEventNotifier) -> Self {
+        self.event_notifier = Some(event_notifier);
+        self
+    }
+
+    async fn publish_events(&self, events: DomainEvents) {
+        if let Some(notifier) = &self.event_notifier {
+            let events = events.into_inner();
+            if events.is_empty() {
+                return;
+            }
+            if let Err(err) = notifier(events).await {
+                error!("Failed to publish domain events: {:?}", err);
+            }
         }
     }
 
@@ -182,6 +213,55 @@ impl ProgramTransformer {
         tx_info: &TransactionInfo,
     ) -> ProgramTransformerResult<()> {
         info!("Handling Transaction: {:?}", tx_info.signature);
+        let events = DomainEvents::new(
+            self.event_notifier.is_some(),
+            Some(tx_info.signature.to_string()),
+        );
+        let result = self
+            .transform_transaction(
+                tx_info,
+                &self.storage,
+                &self.download_metadata_notifier,
+                &events,
+            )
+            .await;
+        if matches!(
+            result,
+            Ok(()) | Err(ProgramTransformerError::NotImplemented)
+        ) {
+            self.publish_events(events).await;
+        }
+        result
+    }
+
+    /// Handles the transaction in a rolled back transaction and returns the rows it would write.
+    pub async fn dry_run_transaction(
+        &self,
+        tx_info: &TransactionInfo,
+    ) -> ProgramTransformerResult<DryRun> {
+        let session = DryRunSession::begin(&self.storage).await?;
+        let events = DomainEvents::new(true, Some(tx_info.signature.to_string()));
+        let result = self
+            .transform_transaction(
+                tx_info,
+                session.connection(),
+                &skip_download_metadata(),
+                &events,
+            )
+            .await;
+        Self::finish_dry_run(session, result, events).await
+    }
+
+    async fn transform_transaction<T>(
+        &self,
+        tx_info: &TransactionInfo,
+        db: &T,
+        download_metadata_notifier: &DownloadMetadataNotifier,
+        events: &DomainEvents,
+    ) -> ProgramTransformerResult<()>
+    where
+        T: ConnectionTrait + TransactionTrait,
+    {
         let instructions = self.break_transaction(tx_info);
         let mut not_impl = 0;
         let ixlen = instructions.len();
@@ -228,8 +308,8 @@ impl ProgramTransformer {
                         handle_bubblegum_instruction(
                             parsing_result,
                             &ix,
-                            &self.storage,
-                            &self.download_metadata_notifier,
+                            db,
+                            download_metadata_notifier,
                             self.cl_audits,
                         )
                         .await
@@ -245,7 +325,7 @@ impl ProgramTransformer {
                         handle_account_compression_instruction(
                             parsing_result,
                             &ix,
-                            &self.storage,
+                            db,
                             self.cl_audits,
                         )
                         .await
@@ -262,8 +342,9 @@ impl ProgramTransformer {
                         match handle_noop_instruction(
                             parsing_result,
                             &ix,
-                            &self.storage,
+                            db,
                             self.cl_audits,
+                            events,
                         )
                         .await
                         {
@@ -294,6 +375,61 @@ impl ProgramTransformer {
         &self,
         account_info: &AccountInfo,
     ) -> ProgramTransformerResult<()> {
+        let events = DomainEvents::new(self.event_notifier.is_some(), None);
+        self.transform_account_update(
+            account_info,
+            &self.storage,
+            &self.download_metadata_notifier,
+            &events,
+        )
+        .await?;
+        self.publish_events(events).await;
+        Ok(())
+    }
+
+    /// Handles the account update in a rolled back transaction and returns the rows it would write.
+    pub async fn dry_run_account_update(
+        &self,
+        account_info: &AccountInfo,
+    ) -> ProgramTransformerResult<DryRun> {
+        let session = DryRunSession::begin(&self.storage).await?;
+        let events = DomainEvents::new(true, None);
+        let result = self
+            .transform_account_update(
+                account_info,
+                session.connection(),
+                &skip_download_metadata(),
+                &events,
+            )
+            .await;
+        Self::finish_dry_run(session, result, events).await
+    }
+
+    async fn finish_dry_run(
+        session: DryRunSession,
+        result: ProgramTransformerResult<()>,
+        events: DomainEvents,
+    ) -> ProgramTransformerResult<DryRun> {
+        if let Err(err) = result {
+            session.rollback().await?;
+            return Err(err);
+        }
+        Ok(DryRun {
+            diff: session.finish().await?,
+            events: events.into_inner(),
+        })
+    }
+
+    async fn transform_account_update<T>(
+        &self,
+        account_info: &AccountInfo,
+        db: &T,
+        download_metadata_notifier: &DownloadMetadataNotifier,
+        events: &DomainEvents,
+    ) -> ProgramTransformerResult<()>
+    where
+        T: ConnectionTrait + TransactionTrait,
+    {
         if let Some(program) = self.match_program(&account_info.owner) {
             let result = program.handle_account(&account_info.data)?;
             match result.result_type() {
@@ -301,8 +437,8 @@ impl ProgramTransformer {
                     handle_token_metadata_account(
                         account_info,
                         parsing_result,
-                        &self.storage,
-                        &self.download_metadata_notifier,
+                        db,
+                        download_metadata_notifier,
                     )
                     .await
                 }
@@ -310,8 +446,8 @@ impl ProgramTransformer {
                     handle_token_program_account(
                         account_info,
                         parsing_result,
-                        &self.storage,
-                        &self.download_metadata_notifier,
+                        db,
+                        download_metadata_notifier,
                     )
                     .await
                 }
@@ -319,8 +455,8 @@ impl ProgramTransformer {
                     handle_token_extensions_program_account(
                         account_info,
                         parsing_result,
-                        &self.storage,
-                        &self.download_metadata_notifier,
+                        db,
+                        download_metadata_notifier,
                     )
                     .await
                 }
@@ -328,37 +464,32 @@ impl ProgramTransformer {
                     handle_mpl_core_account(
                         account_info,
                         parsing_result,
-                        &self.storage,
-                        &self.download_metadata_notifier,
+                        db,
+                        download_metadata_notifier,
                     )
                     .await
                 }
                 ProgramParseResult::HplCharacterManager(parsing_result) => {
-                    handle_hpl_character_manager_account(
-                        account_info,
-                        parsing_result,
-                        &self.storage,
-                    )
-                    .await
+                    handle_hpl_character_manager_account(account_info, parsing_result, db, events)
+                        .await
                 }
                 ProgramParseResult::HplCurrencyManager(parsing_result) => {
-                    handle_hpl_currency_manager_account(account_info, parsing_result, &self.storage)
+                    handle_hpl_currency_manager_account(account_info, parsing_result, db, events)
                         .await
                 }
                 ProgramParseResult::HplResourceManager(parsing_result) => {
-                    handle_hpl_resource_manager_account(account_info, parsing_result, &self.storage)
+                    handle_hpl_resource_manager_account(account_info, parsing_result, db, events)
                         .await
                 }
                 ProgramParseResult::HplHiveControl(parsing_result) => {
-                    handle_hpl_hive_control_account(account_info, parsing_result, &self.storage)
-                        .await
+                    handle_hpl_hive_control_account(account_info, parsing_result, db, events).await
                 }
                 ProgramParseResult::HplNectarStaking(parsing_result) => {
-                    handle_hpl_nectar_staking_account(account_info, parsing_result, &self.storage)
+                    handle_hpl_nectar_staking_account(account_info, parsing_result, db, events)
                         .await
                 }
                 ProgramParseResult::HplNectarMissions(parsing_result) => {
-                    handle_hpl_nectar_missions_account(account_info, parsing_result, &self.storage)
+                    handle_hpl_nectar_missions_account(account_info, parsing_result, db, events)
                         .await
                 }
                 _ => Err(ProgramTransformerError::NotImplemented),
@@ -368,6 +499,16 @@ impl ProgramTransformer {
     }
 }
 
+/// Dry runs don't download metadata for the assets they would have written.
+fn skip_download_metadata() -> DownloadMetadataNotifier {
+    Box::new(
+        |_info: DownloadMetadataInfo| -> BoxFuture<
+            'static,
+            Result<(), Box<dyn std::error::Error + Send + Sync>>,
+        > { Box::pin(async { Ok(()) }) },
+    )
+}
+
 pub async fn find_model_with_retry<T: ConnectionTrait + TransactionTrait, K: EntityTrait>(
     conn: &T,
     model_name: &str,
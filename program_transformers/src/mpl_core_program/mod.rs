@@ -5,17 +5,20 @@ use {
         AccountInfo, DownloadMetadataNotifier,
     },
     blockbuster::programs::mpl_core_program::{MplCoreAccountData, MplCoreAccountState},
-    sea_orm::DatabaseConnection,
+    sea_orm::{ConnectionTrait, TransactionTrait},
 };
 
 mod v1_asset;
 
-pub async fn handle_mpl_core_account<'a, 'b, 'c>(
+pub async fn handle_mpl_core_account<'a, 'b, 'c, T>(
     account_info: &AccountInfo,
     parsing_result: &'a MplCoreAccountState,
-    db: &'b DatabaseConnection,
+    db: &'b T,
     download_metadata_notifier: &DownloadMetadataNotifier,
-) -> ProgramTransformerResult<()> {
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     match &parsing_result.data {
         MplCoreAccountData::EmptyAccount => {
             burn_v1_asset(db, account_info.pubkey, account_info.slot).await?;
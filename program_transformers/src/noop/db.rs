@@ -1,7 +1,11 @@
-use crate::error::{ProgramTransformerError, ProgramTransformerResult};
+use crate::{
+    error::{ProgramTransformerError, ProgramTransformerResult},
+    events::DomainEvents,
+};
 use anchor_lang::{AnchorDeserialize, AnchorSerialize};
-use digital_asset_types::dao::{
-    accounts, character_history, compressed_data, compressed_data_changelog, merkle_tree,
+use digital_asset_types::{
+    dao::{accounts, character_history, compressed_data, compressed_data_changelog, merkle_tree},
+    dapi::common::calculate_reward,
 };
 use hpl_toolkit::prelude::*;
 use log::{debug, error, info};
@@ -16,6 +20,8 @@ use solana_sdk::pubkey::Pubkey;
 use spl_account_compression::events::ApplicationDataEventV1;
 use std::str::FromStr;
 
+const CHARACTER_MANAGER_PROGRAM_ID: &str = "ChRCtrG7X5kb9YncA4wuyD68DXXL8Szt3zBCCGiioBTg";
+
 async fn exec_query<'c, T: ConnectionTrait + TransactionTrait>(
     txn: &T,
     query: Statement,
@@ -31,20 +37,31 @@ async fn exec_query<'c, T: ConnectionTrait + TransactionTrait>(
     Ok(())
 }
 
+/// Project of a tree in the base58 form events carry.
+fn tree_project(tree: &merkle_tree::Model) -> Option<String> {
+    tree.project
+        .as_ref()
+        .map(|project| bs58::encode(project).into_string())
+}
+
 pub async fn save_applicationdata_event<'c, T>(
     application_data: &ApplicationDataEventV1,
     txn: &T,
+    signature: Option<&[u8]>,
+    events: &DomainEvents,
 ) -> Result<u64, ProgramTransformerError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
-    handle_application_data(application_data, txn).await?;
+    handle_application_data(application_data, txn, signature, events).await?;
     Ok(0)
 }
 
 pub async fn handle_application_data<'c, T>(
     application_data: &ApplicationDataEventV1,
     txn: &T,
+    signature: Option<&[u8]>,
+    events: &DomainEvents,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -78,7 +95,19 @@ where
             leaf_idx,
             seq,
             stream_type,
-        } => handle_leaf(txn, tree_id, leaf_idx, stream_type, seq, slot).await?,
+        } => {
+            handle_leaf(
+                txn,
+                tree_id,
+                leaf_idx,
+                stream_type,
+                seq,
+                slot,
+                signature,
+                events,
+            )
+            .await?
+        }
     }
     Ok(())
 }
@@ -115,6 +144,19 @@ async fn handle_tree<'c, T: ConnectionTrait + TransactionTrait>(
                 .to_owned(),
         )
         .build(DbBackend::Postgres);
+    exec_query(txn, query).await?;
+
+    // The account listing the tree may have been indexed before the tree itself.
+    let listed = json!([format!("pubkey:{}", bs58::encode(tree_id).into_string())]);
+    let query = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE merkle_tree SET project = a.project FROM accounts a
+        WHERE merkle_tree.id = $1 AND merkle_tree.project IS NULL AND a.project IS NOT NULL
+        AND (a.parsed_data->'merkle_trees'->'merkle_trees' @> $2
+            OR a.parsed_data->'profile_trees'->'merkle_trees' @> $2
+            OR a.parsed_data->'storage'->'params'->'merkle_trees'->'merkle_trees' @> $2)",
+        vec![tree_id.to_vec().into(), listed.into()],
+    );
     exec_query(txn, query).await
 }
 
@@ -125,41 +167,75 @@ async fn handle_leaf<'c, T: ConnectionTrait + TransactionTrait>(
     stream_type: CompressedDataEventStream,
     seq: u64,
     slot: u64,
+    signature: Option<&[u8]>,
+    events: &DomainEvents,
 ) -> ProgramTransformerResult<()> {
     let compressed_data_id = anchor_lang::solana_program::keccak::hashv(
         &[&tree_id[..], &leaf_idx.to_le_bytes()[..]][..],
     )
     .to_bytes()
     .to_vec();
-    let patch_key: Option<String>;
-    let patch_data: Option<SchemaValue>;
+    let change_type: &str;
+    let change_key: Option<String>;
+    let change_data: JsonValue;
 
     let leaf_idx = leaf_idx as i64;
     let seq = seq as i64;
     let slot = slot as i64;
     match stream_type {
         CompressedDataEventStream::Full { data } => {
-            patch_key = None;
-            patch_data = Some(data.clone());
-            handle_full_leaf(txn, compressed_data_id, tree_id, leaf_idx, data, seq, slot).await?;
+            change_type = "Full";
+            change_key = None;
+            change_data = data.clone().into();
+            handle_full_leaf(
+                txn,
+                compressed_data_id,
+                tree_id,
+                leaf_idx,
+                data,
+                seq,
+                slot,
+                signature,
+                events,
+            )
+            .await?;
         }
         CompressedDataEventStream::PatchChunk { key, data } => {
-            patch_key = Some(key.clone());
-            patch_data = Some(data.clone());
-            handle_leaf_patch(txn, compressed_data_id, key, data, slot).await?;
+            change_type = "Patch";
+            change_key = Some(key.clone());
+            change_data = data.clone().into();
+            handle_leaf_patch(
+                txn,
+                compressed_data_id,
+                key,
+                data,
+                seq,
+                slot,
+                signature,
+                events,
+            )
+            .await?;
         }
         CompressedDataEventStream::Empty => {
-            patch_key = None;
-            patch_data = None;
-            handle_empty_leaf(txn, compressed_data_id).await?;
+            change_type = "Empty";
+            change_key = None;
+            change_data = JsonValue::Null;
+            handle_empty_leaf(txn, compressed_data_id, seq).await?;
         }
     }
 
-    if let Some(data) = patch_data {
-        handle_change_log(txn, tree_id, leaf_idx, patch_key, data, seq, slot).await?;
-    }
-
-    Ok(())
+    handle_change_log(
+        txn,
+        tree_id,
+        leaf_idx,
+        change_type,
+        change_key,
+        change_data,
+        seq,
+        slot,
+        signature,
+    )
+    .await
 }
 
 async fn handle_full_leaf<'c, T: ConnectionTrait + TransactionTrait>(
@@ -170,6 +246,8 @@ async fn handle_full_leaf<'c, T: ConnectionTrait + TransactionTrait>(
     mut data: SchemaValue,
     seq: i64,
     slot: i64,
+    signature: Option<&[u8]>,
+    events: &DomainEvents,
 ) -> ProgramTransformerResult<()> {
     let tree = merkle_tree::Entity::find_by_id(tree_id.to_vec())
         .one(txn)
@@ -178,9 +256,17 @@ async fn handle_full_leaf<'c, T: ConnectionTrait + TransactionTrait>(
 
     debug!("Find tree query executed successfully");
 
+    // The version being replaced, its character history continues from it
+    let previous = compressed_data::Entity::find_by_id(id.clone())
+        .one(txn)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageReadError(db_err.to_string()))?;
+
     let mut schema_validated: bool = false;
     let mut program_id: Option<Pubkey> = None;
+    let mut project: Option<String> = None;
     if let Some(tree) = tree {
+        project = tree_project(&tree);
         debug!("Parsing tree data schema");
         let schema = Schema::deserialize(&mut &tree.data_schema[..]).map_err(|db_err| {
             ProgramTransformerError::CompressedDataParseError(db_err.to_string())
@@ -223,7 +309,7 @@ async fn handle_full_leaf<'c, T: ConnectionTrait + TransactionTrait>(
         slot_updated: Set(slot),
         ..Default::default()
     };
-    let query = compressed_data::Entity::insert(item)
+    let mut query = compressed_data::Entity::insert(item)
         .on_conflict(
             OnConflict::columns([
                 compressed_data::Column::TreeId,
@@ -241,21 +327,79 @@ async fn handle_full_leaf<'c, T: ConnectionTrait + TransactionTrait>(
             .to_owned(),
         )
         .build(DbBackend::Postgres);
-    exec_query(txn, query).await?;
+    // A leaf replayed or delivered late must not overwrite a newer version
+    query.sql = format!("{} WHERE excluded.seq >= compressed_data.seq", query.sql);
+    let result = txn
+        .execute(query)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+    if result.rows_affected() == 0 {
+        debug!(
+            "Skipping leaf {} seq {}, a newer version is indexed",
+            leaf_idx, seq
+        );
+        return Ok(());
+    }
 
     if let Some(program_id) = program_id {
         if program_id == Pubkey::from_str("ChRCtrG7X5kb9YncA4wuyD68DXXL8Szt3zBCCGiioBTg").unwrap() {
             if let SchemaValue::Object(character) = data {
                 if let Some(kind_obj) = character.get(&"used_by".to_string()) {
-                    new_character_event(
-                        txn,
-                        id,
-                        kind_obj.clone(),
-                        ("NewCharacter").to_string(),
-                        slot as i64,
-                        // Some(false),
-                    )
-                    .await?;
+                    // A character indexed before its tree has no history yet
+                    if !has_character_history(txn, &id).await? {
+                        let (created, created_slot, created_signature) = match &previous {
+                            Some(previous) => {
+                                (previous.parsed_data.clone(), previous.slot_updated, None)
+                            }
+                            None => (
+                                SchemaValue::Object(character.clone()).into(),
+                                slot,
+                                signature,
+                            ),
+                        };
+                        let created_used_by = created
+                            .get("used_by")
+                            .map_or_else(|| kind_obj.clone(), |used_by| used_by.to_owned().into());
+                        let inserted = new_character_event(
+                            txn,
+                            id.clone(),
+                            created_used_by,
+                            ("NewCharacter").to_string(),
+                            created_slot,
+                            created_signature,
+                        )
+                        .await?;
+                        if inserted {
+                            events.push(
+                                "NewCharacter",
+                                program_id.to_string(),
+                                project.clone(),
+                                bs58::encode(&id).into_string(),
+                                created_slot as u64,
+                                None,
+                                Some(created),
+                            );
+                        }
+                    }
+
+                    // A newer version logs the transition from the one it replaces
+                    let pre_used_by = previous
+                        .as_ref()
+                        .filter(|previous| previous.seq < seq)
+                        .and_then(|previous| previous.parsed_data.get("used_by"));
+                    if let Some(pre_used_by) = pre_used_by {
+                        log_character_history(
+                            txn,
+                            id.clone(),
+                            project,
+                            pre_used_by.to_owned().into(),
+                            kind_obj.clone(),
+                            slot,
+                            signature,
+                            events,
+                        )
+                        .await?;
+                    }
                 }
             }
         }
@@ -268,7 +412,10 @@ async fn handle_leaf_patch<'c, T: ConnectionTrait + TransactionTrait>(
     id: Vec<u8>,
     key: String,
     data: SchemaValue,
+    seq: i64,
     slot: i64,
+    signature: Option<&[u8]>,
+    events: &DomainEvents,
 ) -> ProgramTransformerResult<()> {
     let found = compressed_data::Entity::find()
         .filter(compressed_data::Column::Id.eq(id.to_owned()))
@@ -283,7 +430,18 @@ async fn handle_leaf_patch<'c, T: ConnectionTrait + TransactionTrait>(
             "Could not find old data in db".to_string(),
         ));
     }
-    let mut db_data: compressed_data::ActiveModel = found.unwrap().into();
+    let found = found.unwrap();
+    // Chunks of one update share its seq, a chunk already applied is a replay
+    let value: JsonValue = data.clone().into();
+    let patched = found.seq == seq && found.parsed_data.get(&key) == Some(&value);
+    if found.seq > seq || patched {
+        debug!(
+            "Skipping patch {} seq {}, leaf is at seq {}",
+            key, seq, found.seq
+        );
+        return Ok(());
+    }
+    let mut db_data: compressed_data::ActiveModel = found.into();
     debug!("Found old_data {:?}", db_data);
 
     let tree = merkle_tree::Entity::find_by_id(db_data.tree_id.clone().unwrap())
@@ -294,7 +452,9 @@ async fn handle_leaf_patch<'c, T: ConnectionTrait + TransactionTrait>(
     debug!("Find tree query executed successfully");
 
     let mut program_id: Option<Pubkey> = None;
+    let mut project: Option<String> = None;
     if let Some(tree) = tree {
+        project = tree_project(&tree);
         program_id = Some(Pubkey::try_from(tree.program.unwrap()).unwrap());
         debug!("Parsing tree data schema");
     }
@@ -317,9 +477,12 @@ async fn handle_leaf_patch<'c, T: ConnectionTrait + TransactionTrait>(
                             log_character_history(
                                 txn,
                                 id.to_owned(),
+                                project.clone(),
                                 used_by.to_owned().into(),
                                 data.to_owned(),
                                 slot as i64,
+                                signature,
+                                events,
                             )
                             .await?;
                         }
@@ -335,6 +498,7 @@ async fn handle_leaf_patch<'c, T: ConnectionTrait + TransactionTrait>(
     db_data.parsed_data = Set(parsed_data);
     debug!("Data updated in object");
 
+    db_data.seq = Set(seq);
     db_data.slot_updated = Set(slot);
 
     let query: Statement = compressed_data::Entity::update(db_data)
@@ -346,6 +510,7 @@ async fn handle_leaf_patch<'c, T: ConnectionTrait + TransactionTrait>(
 async fn handle_empty_leaf<'c, T: ConnectionTrait + TransactionTrait>(
     txn: &T,
     id: Vec<u8>,
+    seq: i64,
 ) -> ProgramTransformerResult<()> {
     let found = compressed_data::Entity::find()
         .filter(compressed_data::Column::Id.eq(id.clone()))
@@ -361,11 +526,21 @@ async fn handle_empty_leaf<'c, T: ConnectionTrait + TransactionTrait>(
         ));
     }
 
-    let db_data: compressed_data::ActiveModel = found.unwrap().into();
+    let found = found.unwrap();
+    // A leaf emptied and filled again must not be deleted by the stale event
+    if found.seq > seq {
+        debug!(
+            "Skipping empty leaf seq {}, leaf is at seq {}",
+            seq, found.seq
+        );
+        return Ok(());
+    }
+    let db_data: compressed_data::ActiveModel = found.into();
     debug!("Found old_data {:?}", db_data);
 
     let query: Statement = compressed_data::Entity::delete(db_data)
         .filter(compressed_data::Column::Id.eq(id))
+        .filter(compressed_data::Column::Seq.lte(seq))
         .build(DbBackend::Postgres);
     exec_query(txn, query).await
 }
@@ -374,37 +549,48 @@ async fn handle_change_log<'c, T: ConnectionTrait + TransactionTrait>(
     txn: &T,
     tree_id: [u8; 32],
     leaf_idx: i64,
+    event_type: &str,
     key: Option<String>,
-    data: SchemaValue,
+    data: JsonValue,
     seq: i64,
     slot: i64,
+    signature: Option<&[u8]>,
 ) -> ProgramTransformerResult<()> {
     let change_log = compressed_data_changelog::ActiveModel {
         tree_id: Set(tree_id.to_vec()),
         leaf_idx: Set(leaf_idx),
         key: Set(key),
-        data: Set(data.into()),
+        data: Set(data),
         seq: Set(seq),
         slot: Set(slot),
+        signature: Set(signature.map(<[u8]>::to_vec)),
+        event_type: Set(event_type.to_string()),
         ..Default::default()
     };
 
-    let query = compressed_data_changelog::Entity::insert(change_log).build(DbBackend::Postgres);
+    let mut query =
+        compressed_data_changelog::Entity::insert(change_log).build(DbBackend::Postgres);
+    // Replayed transactions must not record the same change twice
+    query.sql = format!("{} ON CONFLICT DO NOTHING", query.sql);
     exec_query(txn, query).await
 }
 
 pub async fn log_character_history<T>(
     txn: &T,
     character_id: Vec<u8>,
+    project: Option<String>,
     pre_used_by: SchemaValue,
     mut new_used_by: SchemaValue,
     slot: i64,
+    signature: Option<&[u8]>,
+    events: &DomainEvents,
 ) -> Result<(), ProgramTransformerError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     debug!("pre_used by {:?}", pre_used_by.to_string());
     debug!("new_used by {:?}", new_used_by.to_string());
+    let before: JsonValue = pre_used_by.clone().into();
 
     // Extract the kind from pre_used_by and new_used_by
     let pre_used_by_kind = match pre_used_by.clone() {
@@ -503,7 +689,41 @@ where
         }
     }
 
-    new_character_event(txn, character_id, new_used_by, event, slot as i64).await
+    let after: JsonValue = new_used_by.clone().into();
+    let subject = bs58::encode(&character_id).into_string();
+    let inserted = new_character_event(
+        txn,
+        character_id,
+        new_used_by,
+        event.clone(),
+        slot as i64,
+        signature,
+    )
+    .await?;
+    if inserted {
+        events.push(
+            event,
+            CHARACTER_MANAGER_PROGRAM_ID,
+            project,
+            subject,
+            slot as u64,
+            Some(json!({ "used_by": before })),
+            Some(json!({ "used_by": after })),
+        );
+    }
+    Ok(())
+}
+
+async fn has_character_history<T>(txn: &T, character_id: &[u8]) -> ProgramTransformerResult<bool>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let found = character_history::Entity::find()
+        .filter(character_history::Column::CharacterId.eq(character_id.to_vec()))
+        .one(txn)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageReadError(db_err.to_string()))?;
+    Ok(found.is_some())
 }
 
 pub async fn new_character_event<T>(
@@ -512,8 +732,9 @@ pub async fn new_character_event<T>(
     event_data: SchemaValue,
     event: String,
     slot: i64,
+    signature: Option<&[u8]>,
     // fetch_history: Option<bool>,
-) -> Result<(), ProgramTransformerError>
+) -> Result<bool, ProgramTransformerError>
 where
     T: ConnectionTrait + TransactionTrait,
 {
@@ -531,6 +752,7 @@ where
             event_data: Set(event_data.into()),
             character_id: Set(character_id),
             slot_updated: Set(slot),
+            signature: Set(signature.map(<[u8]>::to_vec)),
             ..Default::default()
         };
         let query = character_history::Entity::insert(new_history)
@@ -542,8 +764,9 @@ where
             .build(DbBackend::Postgres);
 
         exec_query(txn, query).await?;
+        return Ok(true);
     }
-    Ok(())
+    Ok(false)
 }
 
 async fn update_new_used_by<T>(
@@ -572,7 +795,7 @@ where
                     if let Some(JsonValue::Object(params)) = object.get("params") {
                         if let Some(JsonValue::String(id)) = params.get("mission_id") {
                             debug!("params = {:?} mission_id = {:?}", object, id);
-                            
+
                             // Remove the "pubkey:" prefix and convert the remaining part into a vector
                             let stripped_id = id.strip_prefix("pubkey:").ok_or_else(|| {
                                 ProgramTransformerError::ParsingError(
@@ -698,45 +921,6 @@ fn create_params(
     JsonValue::Object(new_map)
 }
 
-fn calculate_reward(
-    event_reward: &JsonValue,
-    parsed_data_rewards: &[JsonValue],
-) -> Option<JsonValue> {
-    // Ensure the event_reward is an object and check delta, reward_idx, and collected fields
-    if let JsonValue::Object(event_reward_obj) = event_reward {
-        let delta = event_reward_obj.get("delta")?.as_u64()?;
-        let reward_idx = event_reward_obj.get("reward_idx")?.as_u64()? as usize;
-        let collected = event_reward_obj.get("collected")?.as_bool()?;
-
-        // Early return if not collected
-        if !collected {
-            return None;
-        }
-
-        // Access the parsed_data_rewards based on reward_idx
-        if let Some(JsonValue::Object(min_max)) = parsed_data_rewards.get(reward_idx) {
-            let min = min_max.get("min")?.as_u64()?;
-            let max = min_max.get("max")?.as_u64()?;
-            let reward_type = min_max.get("reward_type")?.clone();
-
-            // Calculate the result based on delta, min, and max values
-            let result = get_result_from_delta(min, max, delta);
-
-            // Return the new reward JSON object
-            return Some(json!({
-                "reward": result,
-                "reward_type": reward_type
-            }));
-        }
-    }
-    None
-}
-
-fn get_result_from_delta(min: u64, max: u64, delta: u64) -> u64 {
-    let range = max - min;
-    min + ((delta * range) / 100)
-}
-
 fn is_any_reward_collected(params: &SchemaValue) -> bool {
     if let SchemaValue::Object(object) = params {
         if let Some(rewards_value) = object.get(&"rewards".to_string()) {
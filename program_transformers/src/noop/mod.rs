@@ -1,4 +1,4 @@
-use crate::error::ProgramTransformerResult;
+use crate::{error::ProgramTransformerResult, events::DomainEvents};
 use blockbuster::{self, instruction::InstructionBundle, programs::noop::NoopInstruction};
 use log::debug;
 use sea_orm::{ConnectionTrait, TransactionTrait};
@@ -8,9 +8,10 @@ pub use db::*;
 
 pub async fn handle_noop_instruction<'c, T>(
     parsing_result: &'c NoopInstruction,
-    _bundle: &'c InstructionBundle<'c>,
+    bundle: &'c InstructionBundle<'c>,
     txn: &T,
     _cl_audits: bool,
+    events: &DomainEvents,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -18,7 +19,8 @@ where
     debug!("Handling NOOP Ix");
     if let Some(app) = &parsing_result.application_data {
         debug!("Found AppData Event");
-        let _seq = save_applicationdata_event(app, txn).await?;
+        let signature = bs58::decode(bundle.txn_id).into_vec().ok();
+        let _seq = save_applicationdata_event(app, txn, signature.as_deref(), events).await?;
     }
     Ok(())
 }
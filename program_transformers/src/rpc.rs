@@ -0,0 +1,87 @@
+use {
+    crate::{
+        error::{ProgramTransformerError, ProgramTransformerResult},
+        TransactionInfo,
+    },
+    solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey},
+    solana_transaction_status::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+        InnerInstruction, InnerInstructions, UiInstruction,
+    },
+    std::str::FromStr,
+};
+
+fn deserialization_error(message: impl ToString) -> ProgramTransformerError {
+    ProgramTransformerError::DeserializationError(message.to_string())
+}
+
+/// Converts a `getTransaction` result fetched with a binary encoding. The status is not checked,
+/// callers skip failed transactions themselves.
+pub fn transaction_info_from_rpc(
+    transaction: EncodedConfirmedTransactionWithStatusMeta,
+) -> ProgramTransformerResult<TransactionInfo> {
+    let meta = transaction
+        .transaction
+        .meta
+        .ok_or_else(|| deserialization_error("transaction has no status meta"))?;
+    let decoded = transaction
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| {
+            deserialization_error("failed to decode transaction, fetch it with a binary encoding")
+        })?;
+
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            account_keys.push(Pubkey::from_str(key).map_err(|error| {
+                deserialization_error(format!("invalid loaded address {key}: {error}"))
+            })?);
+        }
+    }
+
+    let inner_instructions: Option<Vec<_>> = meta.inner_instructions.into();
+    let meta_inner_instructions = inner_instructions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|inner| {
+            let instructions = inner
+                .instructions
+                .into_iter()
+                .map(|ix| {
+                    let UiInstruction::Compiled(ix) = ix else {
+                        return Err(deserialization_error(
+                            "parsed inner instructions are not supported, use a binary encoding",
+                        ));
+                    };
+                    Ok(InnerInstruction {
+                        instruction: CompiledInstruction {
+                            program_id_index: ix.program_id_index,
+                            accounts: ix.accounts,
+                            data: bs58::decode(ix.data)
+                                .into_vec()
+                                .map_err(deserialization_error)?,
+                        },
+                        stack_height: ix.stack_height,
+                    })
+                })
+                .collect::<ProgramTransformerResult<Vec<_>>>()?;
+            Ok(InnerInstructions {
+                index: inner.index,
+                instructions,
+            })
+        })
+        .collect::<ProgramTransformerResult<Vec<_>>>()?;
+
+    Ok(TransactionInfo {
+        slot: transaction.slot,
+        signature: *decoded
+            .signatures
+            .first()
+            .ok_or_else(|| deserialization_error("transaction has no signature"))?,
+        account_keys,
+        message_instructions: decoded.message.instructions().to_vec(),
+        meta_inner_instructions,
+    })
+}
@@ -13,7 +13,7 @@ use {
         entity::{ActiveValue, ColumnTrait},
         query::{QueryFilter, QueryTrait},
         sea_query::query::OnConflict,
-        ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, TransactionTrait,
+        ConnectionTrait, DbBackend, EntityTrait, TransactionTrait,
     },
     solana_sdk::program_option::COption,
     spl_token::state::AccountState,
@@ -76,12 +76,15 @@ where
     Ok(())
 }
 
-pub async fn handle_token_program_account<'a, 'b>(
+pub async fn handle_token_program_account<'a, 'b, T>(
     account_info: &AccountInfo,
     parsing_result: &'a TokenProgramAccount,
-    db: &'b DatabaseConnection,
+    db: &'b T,
     _download_metadata_notifier: &DownloadMetadataNotifier,
-) -> ProgramTransformerResult<()> {
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     let account_key = account_info.pubkey.to_bytes().to_vec();
     let account_owner = account_info.owner.to_bytes().to_vec();
     match &parsing_result {
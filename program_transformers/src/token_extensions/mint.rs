@@ -14,17 +14,20 @@ use digital_asset_types::dao::{
 use log::warn;
 use sea_orm::{
     entity::*, query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait,
-    DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, EntityTrait,
+    DatabaseTransaction, DbBackend, DbErr, EntityTrait, TransactionTrait,
 };
 use solana_sdk::{program_option::COption, pubkey::Pubkey};
 
 const RETRY_INTERVALS: &[u64] = &[0, 5, 10];
 
-pub async fn handle_token2022_mint_account<'a, 'b, 'c>(
+pub async fn handle_token2022_mint_account<'a, 'b, 'c, T>(
     m: &MintAccount,
     account_update: &AccountInfo,
-    db: &'c DatabaseConnection,
-) -> ProgramTransformerResult<Option<DownloadMetadataInfo>> {
+    db: &'c T,
+) -> ProgramTransformerResult<Option<DownloadMetadataInfo>>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     let key = account_update.pubkey;
     let key_bytes = key.to_bytes().to_vec();
     let spl_token_program = account_update.owner.to_bytes().to_vec();
@@ -175,13 +178,16 @@ async fn upsert_asset_data(
     Ok(())
 }
 
-async fn upsert_asset(
+async fn upsert_asset<T>(
     m: &MintAccount,
     key_bytes: Vec<u8>,
     slot: i64,
-    db: &DatabaseConnection,
+    db: &T,
     txn: &DatabaseTransaction,
-) -> Result<(), ProgramTransformerError> {
+) -> Result<(), ProgramTransformerError>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     let extensions = serde_json::to_value(m.extensions.clone())
         .map_err(|e| ProgramTransformerError::SerializatonError(e.to_string()))?;
 
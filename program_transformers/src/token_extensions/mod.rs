@@ -6,16 +6,19 @@ use crate::{
     AccountInfo, DownloadMetadataNotifier, 
 };
 use blockbuster::programs::token_extensions::TokenExtensionsProgramAccount;
-use sea_orm::DatabaseConnection;
+use sea_orm::{ConnectionTrait, TransactionTrait};
 
 use self::{mint::handle_token2022_mint_account, token_account::handle_token2022_token_account};
 
-pub async fn handle_token_extensions_program_account<'a, 'b>(
+pub async fn handle_token_extensions_program_account<'a, 'b, T>(
     account_update: &AccountInfo,
     parsing_result: &'a TokenExtensionsProgramAccount,
-    db: &'b DatabaseConnection,
+    db: &'b T,
     download_metadata_notifier: &DownloadMetadataNotifier,
-) -> ProgramTransformerResult<()> {
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     match &parsing_result {
         TokenExtensionsProgramAccount::TokenAccount(ta) => {
             handle_token2022_token_account(ta, account_update, db).await?;
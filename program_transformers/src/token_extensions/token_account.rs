@@ -8,15 +8,20 @@ use crate::{
 use blockbuster::programs::token_extensions::TokenAccount;
 use cadence_macros::statsd_count;
 use digital_asset_types::dao::asset;
-use sea_orm::{entity::*, query::*, ActiveValue::Set, DatabaseConnection, EntityTrait};
+use sea_orm::{
+    entity::*, query::*, ActiveValue::Set, ConnectionTrait, EntityTrait, TransactionTrait,
+};
 use solana_sdk::program_option::COption;
 use spl_token_2022::state::AccountState;
 
-pub async fn handle_token2022_token_account<'a, 'b, 'c>(
+pub async fn handle_token2022_token_account<'a, 'b, 'c, T>(
     ta: &TokenAccount,
     account_info: &AccountInfo,
-    db: &'c DatabaseConnection,
-) -> Result<(), ProgramTransformerError> {
+    db: &'c T,
+) -> Result<(), ProgramTransformerError>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     let key = account_info.pubkey;
     let account_key = key.to_bytes().to_vec();
     let account_owner = account_info.owner.to_bytes().to_vec();
@@ -8,18 +8,21 @@ use {
         AccountInfo, DownloadMetadataNotifier,
     },
     blockbuster::programs::token_metadata::{TokenMetadataAccountData, TokenMetadataAccountState},
-    sea_orm::{DatabaseConnection, TransactionTrait},
+    sea_orm::{ConnectionTrait, TransactionTrait},
 };
 
 mod master_edition;
 mod v1_asset;
 
-pub async fn handle_token_metadata_account<'a, 'b>(
+pub async fn handle_token_metadata_account<'a, 'b, T>(
     account_info: &AccountInfo,
     parsing_result: &'a TokenMetadataAccountState,
-    db: &'b DatabaseConnection,
+    db: &'b T,
     download_metadata_notifier: &DownloadMetadataNotifier,
-) -> ProgramTransformerResult<()> {
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
     match &parsing_result.data {
         TokenMetadataAccountData::EmptyAccount => {
             burn_v1_asset(db, account_info.pubkey, account_info.slot).await?;
@@ -0,0 +1,216 @@
+use {
+    program_transformers::{
+        error::{ProgramTransformerError, ProgramTransformerResult},
+        rpc::transaction_info_from_rpc,
+        TransactionInfo,
+    },
+    serde_json::{json, Value},
+    solana_sdk::{
+        hash::Hash,
+        instruction::{CompiledInstruction, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+    solana_transaction_status::{
+        Encodable, EncodedConfirmedTransactionWithStatusMeta, InnerInstruction, InnerInstructions,
+        UiTransactionEncoding,
+    },
+};
+
+fn transaction() -> Transaction {
+    let payer = Keypair::new();
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+    Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    )
+}
+
+fn meta(inner_instructions: Value, loaded_addresses: Value) -> Value {
+    json!({
+        "err": null,
+        "status": { "Ok": null },
+        "fee": 5000,
+        "preBalances": [],
+        "postBalances": [],
+        "innerInstructions": inner_instructions,
+        "logMessages": [],
+        "preTokenBalances": [],
+        "postTokenBalances": [],
+        "rewards": [],
+        "loadedAddresses": loaded_addresses,
+    })
+}
+
+fn rpc_transaction(
+    transaction: &Transaction,
+    encoding: UiTransactionEncoding,
+    meta: Value,
+) -> EncodedConfirmedTransactionWithStatusMeta {
+    serde_json::from_value(json!({
+        "slot": 42,
+        "blockTime": null,
+        "transaction": transaction.encode(encoding),
+        "meta": meta,
+    }))
+    .expect("valid getTransaction result")
+}
+
+fn no_loaded_addresses() -> Value {
+    json!({ "writable": [], "readonly": [] })
+}
+
+#[test]
+fn converts_keys_and_inner_instructions() -> ProgramTransformerResult<()> {
+    let transaction = transaction();
+    let (writable, readonly) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let inner_instructions = json!([{
+        "index": 0,
+        "instructions": [{
+            "programIdIndex": 3,
+            "accounts": [0, 2],
+            "data": bs58::encode([4, 5]).into_string(),
+            "stackHeight": 2,
+        }],
+    }]);
+    let loaded_addresses = json!({
+        "writable": [writable.to_string()],
+        "readonly": [readonly.to_string()],
+    });
+
+    for encoding in [UiTransactionEncoding::Base58, UiTransactionEncoding::Base64] {
+        let info = transaction_info_from_rpc(rpc_transaction(
+            &transaction,
+            encoding,
+            meta(inner_instructions.clone(), loaded_addresses.clone()),
+        ))?;
+
+        let mut account_keys = transaction.message.account_keys.clone();
+        account_keys.extend([writable, readonly]);
+        assert_eq!(
+            info,
+            TransactionInfo {
+                slot: 42,
+                signature: transaction.signatures[0],
+                account_keys,
+                message_instructions: transaction.message.instructions.clone(),
+                meta_inner_instructions: vec![InnerInstructions {
+                    index: 0,
+                    instructions: vec![InnerInstruction {
+                        instruction: CompiledInstruction {
+                            program_id_index: 3,
+                            accounts: vec![0, 2],
+                            data: vec![4, 5],
+                        },
+                        stack_height: Some(2),
+                    }],
+                }],
+            }
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn failed_transactions_are_converted() -> ProgramTransformerResult<()> {
+    let transaction = transaction();
+    let mut meta = meta(json!([]), no_loaded_addresses());
+    meta["err"] = json!({ "InstructionError": [0, "InvalidArgument"] });
+    meta["status"] = json!({ "Err": meta["err"] });
+
+    let info = transaction_info_from_rpc(rpc_transaction(
+        &transaction,
+        UiTransactionEncoding::Base64,
+        meta,
+    ))?;
+    assert_eq!(info.signature, transaction.signatures[0]);
+    Ok(())
+}
+
+#[test]
+fn missing_meta_is_rejected() {
+    let transaction = transaction();
+    let result = transaction_info_from_rpc(rpc_transaction(
+        &transaction,
+        UiTransactionEncoding::Base64,
+        Value::Null,
+    ));
+    assert!(matches!(
+        result,
+        Err(ProgramTransformerError::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn json_encoding_is_rejected() {
+    let transaction = transaction();
+    let result = transaction_info_from_rpc(rpc_transaction(
+        &transaction,
+        UiTransactionEncoding::Json,
+        meta(json!([]), no_loaded_addresses()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProgramTransformerError::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn invalid_loaded_address_is_rejected() {
+    let transaction = transaction();
+    let result = transaction_info_from_rpc(rpc_transaction(
+        &transaction,
+        UiTransactionEncoding::Base64,
+        meta(
+            json!([]),
+            json!({ "writable": ["not a pubkey"], "readonly": [] }),
+        ),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProgramTransformerError::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn parsed_inner_instructions_are_rejected() {
+    let transaction = transaction();
+    let inner_instructions = json!([{
+        "index": 0,
+        "instructions": [{
+            "programId": Pubkey::new_unique().to_string(),
+            "accounts": [],
+            "data": "",
+        }],
+    }]);
+    let result = transaction_info_from_rpc(rpc_transaction(
+        &transaction,
+        UiTransactionEncoding::Base64,
+        meta(inner_instructions, no_loaded_addresses()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProgramTransformerError::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn invalid_inner_instruction_data_is_rejected() {
+    let transaction = transaction();
+    let inner_instructions = json!([{
+        "index": 0,
+        "instructions": [{ "programIdIndex": 1, "accounts": [], "data": "0OIl" }],
+    }]);
+    let result = transaction_info_from_rpc(rpc_transaction(
+        &transaction,
+        UiTransactionEncoding::Base64,
+        meta(inner_instructions, no_loaded_addresses()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProgramTransformerError::DeserializationError(_))
+    ));
+}